@@ -0,0 +1,181 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ipp_encoder::{
+    encoder::{
+        Attribute, AttributeGroup, AttributeName, AttributeValue, IppEncode, IppVersion, Operation,
+        OperationRef,
+    },
+    spec::{
+        attribute::{JobTemplateAttribute, OperationAttribute, PrinterAttribute},
+        operation::OperationID,
+        tag::{DelimiterTag, ValueTag},
+    },
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use strum::IntoEnumIterator;
+
+/// system allocator counting allocations, to compare owned and borrowed decoding
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// number of allocations made by `f`
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn text(tag: ValueTag, name: AttributeName, value: &str) -> Attribute {
+    Attribute {
+        tag,
+        name,
+        values: vec![AttributeValue::string(tag, value)],
+    }
+}
+
+/// a Print-Job request carrying a 16 MB document
+fn print_job_request() -> Vec<u8> {
+    let attributes = [
+        text(
+            ValueTag::Charset,
+            AttributeName::Operation(OperationAttribute::AttributesCharset),
+            "utf-8",
+        ),
+        text(
+            ValueTag::NaturalLanguage,
+            AttributeName::Operation(OperationAttribute::AttributesNaturalLanguage),
+            "en-US",
+        ),
+        text(
+            ValueTag::Uri,
+            AttributeName::Operation(OperationAttribute::PrinterUri),
+            "ipp://localhost:6363/",
+        ),
+        text(
+            ValueTag::NameWithoutLanguage,
+            AttributeName::Operation(OperationAttribute::RequestingUserName),
+            "user",
+        ),
+        text(
+            ValueTag::MimeMediaType,
+            AttributeName::Operation(OperationAttribute::DocumentFormat),
+            "application/postscript",
+        ),
+    ];
+
+    Operation {
+        version: IppVersion { major: 1, minor: 1 },
+        operation_id_or_status_code: OperationID::PrintJob as u16,
+        request_id: 1,
+        attribute_groups: vec![AttributeGroup {
+            tag: DelimiterTag::OperationAttributes,
+            attributes: attributes
+                .into_iter()
+                .map(|attr| (attr.name.clone(), attr))
+                .collect(),
+        }],
+        data: vec![b'%'; 16 * 1024 * 1024],
+    }
+    .to_ipp()
+}
+
+/// a Get-Printer-Attributes request asking for 50 attributes by name
+fn get_printer_attributes_request() -> Vec<u8> {
+    let requested_attributes: Vec<AttributeValue> = PrinterAttribute::iter()
+        .map(|attr| attr.to_string())
+        .chain(JobTemplateAttribute::iter().map(|attr| attr.to_string()))
+        .take(50)
+        .map(AttributeValue::Keyword)
+        .collect();
+
+    let attributes = [
+        text(
+            ValueTag::Charset,
+            AttributeName::Operation(OperationAttribute::AttributesCharset),
+            "utf-8",
+        ),
+        text(
+            ValueTag::NaturalLanguage,
+            AttributeName::Operation(OperationAttribute::AttributesNaturalLanguage),
+            "en-US",
+        ),
+        text(
+            ValueTag::Uri,
+            AttributeName::Operation(OperationAttribute::PrinterUri),
+            "ipp://localhost:6363/",
+        ),
+        text(
+            ValueTag::NameWithoutLanguage,
+            AttributeName::Operation(OperationAttribute::RequestingUserName),
+            "user",
+        ),
+        Attribute {
+            tag: ValueTag::Keyword,
+            name: AttributeName::Operation(OperationAttribute::RequestedAttributes),
+            values: requested_attributes,
+        },
+    ];
+
+    Operation {
+        version: IppVersion { major: 1, minor: 1 },
+        operation_id_or_status_code: OperationID::GetPrinterAttributes as u16,
+        request_id: 1,
+        attribute_groups: vec![AttributeGroup {
+            tag: DelimiterTag::OperationAttributes,
+            attributes: attributes
+                .into_iter()
+                .map(|attr| (attr.name.clone(), attr))
+                .collect(),
+        }],
+        data: Vec::new(),
+    }
+    .to_ipp()
+}
+
+fn decode(c: &mut Criterion) {
+    let request = print_job_request();
+
+    c.bench_function("from_ipp", |b| {
+        b.iter(|| Operation::from_ipp(black_box(&request), 0))
+    });
+    c.bench_function("parse (borrowed)", |b| {
+        b.iter(|| Operation::parse(black_box(&request)))
+    });
+}
+
+fn decode_attributes(c: &mut Criterion) {
+    let request = get_printer_attributes_request();
+
+    println!(
+        "Get-Printer-Attributes allocations: owned {}, borrowed {}",
+        allocations(|| Operation::from_ipp(&request, 0)),
+        allocations(|| OperationRef::from_ipp(&request)),
+    );
+
+    c.bench_function("get-printer-attributes from_ipp", |b| {
+        b.iter(|| Operation::from_ipp(black_box(&request), 0))
+    });
+    c.bench_function("get-printer-attributes from_ipp (borrowed)", |b| {
+        b.iter(|| OperationRef::from_ipp(black_box(&request)))
+    });
+}
+
+criterion_group!(benches, decode, decode_attributes);
+criterion_main!(benches);
@@ -0,0 +1,149 @@
+use chrono::Utc;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use indexmap::IndexMap;
+use ipp_encoder::{
+    encoder::{
+        Attribute, AttributeGroup, AttributeName, AttributeValue, IppEncode, IppVersion, Operation,
+        TextWithLang,
+    },
+    spec::{
+        attribute::{OperationAttribute, PrinterAttribute},
+        operation::StatusCode,
+        tag::{DelimiterTag, ValueTag},
+    },
+};
+
+fn text(tag: ValueTag, name: AttributeName, values: &[&str]) -> Attribute {
+    Attribute {
+        tag,
+        name,
+        values: values
+            .iter()
+            .map(|value| AttributeValue::string(tag, *value))
+            .collect(),
+    }
+}
+
+/// a Get-Printer-Attributes response similar to what the example server sends for 'all'
+fn get_printer_attributes_response() -> Operation {
+    let operation_attributes = [
+        text(
+            ValueTag::Charset,
+            AttributeName::Operation(OperationAttribute::AttributesCharset),
+            &["utf-8"],
+        ),
+        text(
+            ValueTag::NaturalLanguage,
+            AttributeName::Operation(OperationAttribute::AttributesNaturalLanguage),
+            &["en-US"],
+        ),
+    ];
+
+    let mut printer_attributes = vec![
+        text(
+            ValueTag::Uri,
+            AttributeName::Printer(PrinterAttribute::PrinterUriSupported),
+            &["ipp://localhost:6363/"],
+        ),
+        text(
+            ValueTag::Keyword,
+            AttributeName::Printer(PrinterAttribute::UriSecuritySupported),
+            &["none"],
+        ),
+        text(
+            ValueTag::Keyword,
+            AttributeName::Printer(PrinterAttribute::UriAuthenticationSupported),
+            &["none"],
+        ),
+        text(
+            ValueTag::MimeMediaType,
+            AttributeName::Printer(PrinterAttribute::DocumentFormatSupported),
+            &[
+                "text/html",
+                "text/plain",
+                "application/vnd.hp-PCL",
+                "application/octet-stream",
+                "application/pdf",
+                "application/postscript",
+            ],
+        ),
+        text(
+            ValueTag::Keyword,
+            AttributeName::Printer(PrinterAttribute::CompressionSupported),
+            &["deflate", "gzip"],
+        ),
+        Attribute {
+            tag: ValueTag::NameWithLanguage,
+            name: AttributeName::Printer(PrinterAttribute::PrinterName),
+            values: vec![AttributeValue::TextWithLang(TextWithLang {
+                lang: String::from("en"),
+                text: String::from("Rust IPP Printer"),
+            })],
+        },
+        Attribute {
+            tag: ValueTag::Enum,
+            name: AttributeName::Printer(PrinterAttribute::OperationsSupported),
+            values: [0x02, 0x04, 0x08, 0x09, 0x0A, 0x0B]
+                .into_iter()
+                .map(AttributeValue::Number)
+                .collect(),
+        },
+        Attribute {
+            tag: ValueTag::Boolean,
+            name: AttributeName::Printer(PrinterAttribute::PrinterIsAcceptingJobs),
+            values: vec![AttributeValue::Boolean(true)],
+        },
+        Attribute {
+            tag: ValueTag::DateTime,
+            name: AttributeName::Printer(PrinterAttribute::PrinterCurrentTime),
+            values: vec![AttributeValue::DateTime(Utc::now())],
+        },
+    ];
+    for i in 0..50 {
+        printer_attributes.push(text(
+            ValueTag::Keyword,
+            AttributeName::Unsupported(format!("vendor-attribute-{}", i)),
+            &["value-one", "value-two"],
+        ));
+    }
+
+    Operation {
+        version: IppVersion { major: 1, minor: 1 },
+        operation_id_or_status_code: StatusCode::SuccessfulOk as u16,
+        request_id: 1,
+        attribute_groups: vec![
+            AttributeGroup {
+                tag: DelimiterTag::OperationAttributes,
+                attributes: operation_attributes
+                    .into_iter()
+                    .map(|attr| (attr.name.clone(), attr))
+                    .collect::<IndexMap<_, _>>(),
+            },
+            AttributeGroup {
+                tag: DelimiterTag::PrinterAttributes,
+                attributes: printer_attributes
+                    .into_iter()
+                    .map(|attr| (attr.name.clone(), attr))
+                    .collect::<IndexMap<_, _>>(),
+            },
+        ],
+        data: Vec::new(),
+    }
+}
+
+fn encode(c: &mut Criterion) {
+    let response = get_printer_attributes_response();
+
+    c.bench_function("to_ipp", |b| b.iter(|| black_box(&response).to_ipp()));
+
+    let mut buf = Vec::with_capacity(response.ipp_len());
+    c.bench_function("to_ipp_into (reused buffer)", |b| {
+        b.iter(|| {
+            buf.clear();
+            black_box(&response).to_ipp_into(&mut buf);
+        })
+    });
+}
+
+criterion_group!(benches, encode);
+criterion_main!(benches);
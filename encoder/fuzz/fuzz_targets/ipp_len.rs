@@ -0,0 +1,24 @@
+#![no_main]
+
+use ipp_encoder::encoder::{assert_ipp_len_matches, Operation};
+use libfuzzer_sys::fuzz_target;
+
+// whatever decodes must encode back to exactly ipp_len bytes
+fuzz_target!(|data: &[u8]| {
+    let operation = match Operation::try_from_ipp(data) {
+        Ok(operation) => operation,
+        Err(_) => return,
+    };
+
+    assert_ipp_len_matches(&operation);
+    assert_ipp_len_matches(&operation.attribute_groups);
+
+    for group in &operation.attribute_groups {
+        for attribute in group.attributes.values() {
+            assert_eq!(attribute.to_ipp().len(), attribute.ipp_len());
+            for value in &attribute.values {
+                assert_eq!(value.to_ipp().len(), value.ipp_len());
+            }
+        }
+    }
+});
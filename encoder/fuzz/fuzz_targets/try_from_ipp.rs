@@ -0,0 +1,19 @@
+#![no_main]
+
+use ipp_encoder::encoder::{CompatQuirks, Operation};
+use libfuzzer_sys::fuzz_target;
+
+// decoding arbitrary bytes must return an error rather than panic
+fuzz_target!(|data: &[u8]| {
+    let _ = Operation::try_from_ipp(data);
+    let _ = Operation::try_parse(data);
+    let _ = Operation::parse_header(data);
+
+    let lenient = CompatQuirks {
+        case_insensitive_names: true,
+        charset_not_first: true,
+        repeated_names_as_values: true,
+    };
+    let _ = Operation::try_from_ipp_with_quirks(data, &CompatQuirks::default());
+    let _ = Operation::try_from_ipp_with_quirks(data, &lenient);
+});
@@ -1,13 +1,25 @@
-use serde::{Deserialize, Serialize};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::str::FromStr;
 
-use crate::spec::tag::{DelimiterTag, ValueTag};
+use crate::spec::{
+    operation::{JobState, OperationID, PrinterState},
+    tag::{DelimiterTag, ValueTag},
+    IppEnum, KeywordAttribute,
+};
 
-use super::{AttributeName, AttributeValue, IppEncode};
+use super::{AttributeName, AttributeValue, IppEncode, RangeOfInteger, Resolution, TextWithLang};
+use crate::io::{self, Write};
+#[cfg(feature = "std")]
+use chrono::{DateTime, Utc};
 
 ///
 /// Wrapper for IPP attribute
 ///
-/// ```
+/// ```text
 /// -----------------------------------------------
 /// |          attribute-with-one-value           |  q bytes
 /// ----------------------------------------------------------
@@ -23,7 +35,7 @@ use super::{AttributeName, AttributeValue, IppEncode};
 ///
 /// Encoded with just an "attribute-with-one-value" field
 ///
-/// ```
+/// ```text
 /// -----------------------------------------------
 /// |                   value-tag                 |   1 byte
 /// -----------------------------------------------
@@ -45,7 +57,7 @@ use super::{AttributeName, AttributeValue, IppEncode};
 ///
 /// Encoded with an "attribute-with-one-value" field followed by n-1 "additional-value" fields
 ///
-/// ```
+/// ```text
 /// -----------------------------------------------
 /// |                   value-tag                 |   1 byte
 /// -----------------------------------------------
@@ -59,7 +71,9 @@ use super::{AttributeName, AttributeValue, IppEncode};
 ///
 /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.1.5)
 ///
-#[derive(Serialize, Deserialize, Debug)]
+/// Serialized with its values flattened, see [`attribute_serde`](super::attribute_serde)
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Attribute {
     pub tag: ValueTag,
     pub name: AttributeName,
@@ -67,102 +81,88 @@ pub struct Attribute {
 }
 
 impl Attribute {
-    fn decode_one(bytes: &[u8], offset: usize) -> (usize, bool, Option<Self>) {
-        let mut shifting_offset = offset;
-
-        let slice: [u8; 1] = bytes[shifting_offset..shifting_offset + 1]
-            .try_into()
-            .unwrap();
-        let raw_int = u8::from_be_bytes(slice);
-        shifting_offset += 1;
+    /// Whether `offset` is past the data or at a delimiter tag, that is
+    /// the end of the attributes of the current group
+    ///
+    /// Delimiter tags only appear where a value-tag is expected, so this is only
+    /// meaningful at an attribute (or additional value) boundary
+    pub(crate) fn at_group_boundary(bytes: &[u8], offset: usize) -> bool {
+        offset >= bytes.len() || DelimiterTag::is_delimiter(bytes[offset])
+    }
 
-        let decoded: Option<Self>;
+    /// Decode one value with its tag and name, the leading byte is always a value-tag
+    ///
+    /// The name is None for the zero-length name of an additional value
+    fn decode_one(
+        bytes: &[u8],
+        offset: usize,
+    ) -> (usize, (ValueTag, Option<AttributeName>, AttributeValue)) {
+        let raw_int = bytes[offset];
 
-        let mut has_name = false;
+        let mut shifting_offset = offset + 1;
 
-        if DelimiterTag::from_repr(raw_int as usize).is_some() {
-            // if reach any other delimiter tag, return
-            // (either a new attribute group or end-of-attributes)
-            decoded = None;
-            shifting_offset = offset;
+        // decode attribute-name
+        let (delta, raw_name) = String::from_ipp(bytes, shifting_offset);
+        shifting_offset += delta;
+        let name = if raw_name.is_empty() {
+            None
         } else {
-            // decode attribute-name
-            let (delta, name) = AttributeName::from_ipp(bytes, shifting_offset);
-            shifting_offset += delta;
-            has_name = !name.is_empty();
-
-            // decode actual value
-            let value_tag = ValueTag::from_repr(raw_int as usize).unwrap();
-            let (delta, value) = AttributeValue::from_ipp(bytes, shifting_offset, value_tag);
-            shifting_offset += delta;
-
-            decoded = Some(Attribute {
-                tag: value_tag,
-                name,
-                values: vec![value],
-            });
-        }
+            Some(AttributeName::from_str(&raw_name).unwrap())
+        };
+
+        // decode actual value
+        let value_tag = ValueTag::from_wire(raw_int).unwrap();
+        let (delta, value) = AttributeValue::from_ipp(bytes, shifting_offset, value_tag);
+        shifting_offset += delta;
 
-        (shifting_offset - offset, has_name, decoded)
+        (shifting_offset - offset, (value_tag, name, value))
     }
 
+    /// Decode one attribute with its additional values, None when a group boundary
+    /// (see [`Attribute::at_group_boundary`]) is reached first
+    ///
+    /// Panics if the field at `offset` is an additional value, which has no attribute
+    /// to belong to.
     pub fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Option<Self>) {
-        let (mut first_offset, _, first_attribute_opt) = Self::decode_one(bytes, offset);
-
-        let next_offset = offset + first_offset;
-
-        if let Some(mut first_attribute) = first_attribute_opt {
-            if next_offset > bytes.len() {
-                (0, None)
-            } else {
-                let (mut next_offset, mut has_name, mut next_attribute_opt) =
-                    Self::decode_one(bytes, next_offset);
-
-                while let Some(mut next_attribute) = next_attribute_opt {
-                    if has_name || (offset + first_offset + next_offset >= bytes.len()) {
-                        break;
-                    }
-                    // add additional_value
-                    first_attribute.values.append(&mut next_attribute.values);
-
-                    // add to offset
-                    first_offset += next_offset;
-
-                    let next = Self::decode_one(bytes, offset + first_offset);
-                    next_offset = next.0;
-                    has_name = next.1;
-                    next_attribute_opt = next.2;
-                }
-
-                (first_offset, Some(first_attribute))
-            }
-        } else {
-            (0, None)
-        }
+        let (len, fields) = decode_fields(bytes, offset, Self::decode_one);
+        (
+            len,
+            fields.map(|(tag, name, values)| Self { tag, name, values }),
+        )
     }
 
     pub fn to_ipp(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::with_capacity(self.ipp_len());
-        if !self.values.is_empty() {
-            for i in 0..self.values.len() {
-                // write tag
-                bytes.append(&mut (self.tag as u8).to_be_bytes().to_vec());
-
-                // write name
-                if i == 0 {
-                    // first attribute write name-length and name
-                    bytes.append(&mut self.name.to_ipp());
-                } else {
-                    // next attributes only write 2 bytes of name-length (0x00)
-                    bytes.append(&mut String::from("").to_ipp());
-                }
-
-                // write value
-                let value = &self.values[i];
-                bytes.append(&mut value.to_ipp());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
+
+    pub fn to_ipp_into(&self, buf: &mut Vec<u8>) {
+        // writing into a Vec only fails on a length that does not fit in 2 bytes
+        self.to_writer(buf).expect(io::TOO_LONG);
+    }
+
+    pub fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let mut len = 0;
+        for (i, value) in self.values.iter().enumerate() {
+            // write tag
+            writer.write_all(&self.tag.raw().to_be_bytes())?;
+            len += 1;
+
+            // write name
+            if i == 0 {
+                // first attribute write name-length and name
+                len += self.name.to_writer(writer)?;
+            } else {
+                // next attributes only write 2 bytes of name-length (0x00)
+                writer.write_all(&0_u16.to_be_bytes())?;
+                len += 2;
             }
+
+            // write value, none for out-of-band tags
+            len += value.tagged_to_writer(self.tag, writer)?;
         }
-        bytes
+        Ok(len)
     }
 
     pub fn ipp_len(&self) -> usize {
@@ -177,10 +177,568 @@ impl Attribute {
 
             let mut value_len: usize = 0;
             for value in &self.values {
-                value_len += value.ipp_len();
+                value_len += value.tagged_ipp_len(self.tag);
             }
 
             tag_len + name_len + value_len
         }
     }
 }
+
+/// Group the field at `offset` and its additional values into the tag, name and values
+/// of one attribute, None when a group boundary is reached first
+///
+/// `decode_one` decodes a single field, with a None name for the zero-length name of
+/// an additional value. Shared by [`Attribute::from_ipp`] and the borrowed decoder,
+/// so both tell attributes and additional values apart the same way.
+///
+/// Panics if the field at `offset` is an additional value.
+pub(crate) fn decode_fields<'a, N, V>(
+    bytes: &'a [u8],
+    offset: usize,
+    decode_one: impl Fn(&'a [u8], usize) -> (usize, (ValueTag, Option<N>, V)),
+) -> (usize, Option<(ValueTag, N, Vec<V>)>) {
+    if Attribute::at_group_boundary(bytes, offset) {
+        return (0, None);
+    }
+
+    // an additional value first in its group has nothing to belong to, the fallible
+    // entry points refuse it with `IppError::AdditionalValueWithoutAttribute`
+    let (mut len, (tag, name, value)) = decode_one(bytes, offset);
+    let name = name.expect("additional value without a preceding attribute");
+    let mut values = vec![value];
+
+    // additional values have an empty name, scan them up to the group boundary
+    while !Attribute::at_group_boundary(bytes, offset + len) {
+        match decode_one(bytes, offset + len) {
+            (delta, (_, None, value)) => {
+                values.push(value);
+                len += delta;
+            }
+            _ => break,
+        }
+    }
+
+    (len, Some((tag, name, values)))
+}
+
+// constructor setting the value-tag of the value syntax
+impl Attribute {
+    fn with_values(
+        tag: ValueTag,
+        name: impl Into<AttributeName>,
+        values: Vec<AttributeValue>,
+    ) -> Self {
+        Self {
+            tag,
+            name: name.into(),
+            values,
+        }
+    }
+
+    fn with_strings<S: Into<String>>(
+        tag: ValueTag,
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let values = values
+            .into_iter()
+            .map(|value| AttributeValue::string(tag, value))
+            .collect();
+        Self::with_values(tag, name, values)
+    }
+
+    pub fn keyword(name: impl Into<AttributeName>, value: impl Into<String>) -> Self {
+        Self::keyword_set(name, [value])
+    }
+
+    pub fn keyword_set<S: Into<String>>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_strings(ValueTag::Keyword, name, values)
+    }
+
+    pub fn integer(name: impl Into<AttributeName>, value: i32) -> Self {
+        Self::integer_set(name, [value])
+    }
+
+    pub fn integer_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = i32>,
+    ) -> Self {
+        let values = values.into_iter().map(AttributeValue::Number).collect();
+        Self::with_values(ValueTag::Integer, name, values)
+    }
+
+    pub fn boolean(name: impl Into<AttributeName>, value: bool) -> Self {
+        Self::boolean_set(name, [value])
+    }
+
+    pub fn boolean_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = bool>,
+    ) -> Self {
+        let values = values.into_iter().map(AttributeValue::Boolean).collect();
+        Self::with_values(ValueTag::Boolean, name, values)
+    }
+
+    pub fn uri(name: impl Into<AttributeName>, value: &str) -> Self {
+        Self::uri_set(name, [value])
+    }
+
+    pub fn uri_set<'a>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self::with_strings(ValueTag::Uri, name, values)
+    }
+
+    pub fn enum_value(name: impl Into<AttributeName>, value: i32) -> Self {
+        Self::enum_value_set(name, [value])
+    }
+
+    pub fn enum_value_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = i32>,
+    ) -> Self {
+        let values = values.into_iter().map(AttributeValue::Number).collect();
+        Self::with_values(ValueTag::Enum, name, values)
+    }
+
+    /// textWithoutLanguage
+    pub fn text(name: impl Into<AttributeName>, value: impl Into<String>) -> Self {
+        Self::text_set(name, [value])
+    }
+
+    pub fn text_set<S: Into<String>>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_strings(ValueTag::TextWithoutLanguage, name, values)
+    }
+
+    /// nameWithoutLanguage
+    pub fn name(name: impl Into<AttributeName>, value: impl Into<String>) -> Self {
+        Self::name_set(name, [value])
+    }
+
+    pub fn name_set<S: Into<String>>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_strings(ValueTag::NameWithoutLanguage, name, values)
+    }
+
+    /// nameWithLanguage
+    pub fn name_with_lang(
+        name: impl Into<AttributeName>,
+        lang: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self::name_with_lang_set(name, [TextWithLang::new(lang, text)])
+    }
+
+    pub fn name_with_lang_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = TextWithLang>,
+    ) -> Self {
+        let values = values
+            .into_iter()
+            .map(AttributeValue::TextWithLang)
+            .collect();
+        Self::with_values(ValueTag::NameWithLanguage, name, values)
+    }
+
+    /// textWithLanguage
+    pub fn text_with_lang(
+        name: impl Into<AttributeName>,
+        lang: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self::text_with_lang_set(name, [TextWithLang::new(lang, text)])
+    }
+
+    pub fn text_with_lang_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = TextWithLang>,
+    ) -> Self {
+        let values = values
+            .into_iter()
+            .map(AttributeValue::TextWithLang)
+            .collect();
+        Self::with_values(ValueTag::TextWithLanguage, name, values)
+    }
+
+    pub fn charset(name: impl Into<AttributeName>, value: impl Into<String>) -> Self {
+        Self::charset_set(name, [value])
+    }
+
+    pub fn charset_set<S: Into<String>>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_strings(ValueTag::Charset, name, values)
+    }
+
+    pub fn natural_language(name: impl Into<AttributeName>, value: impl Into<String>) -> Self {
+        Self::natural_language_set(name, [value])
+    }
+
+    pub fn natural_language_set<S: Into<String>>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_strings(ValueTag::NaturalLanguage, name, values)
+    }
+
+    pub fn mime_media_type(name: impl Into<AttributeName>, value: impl Into<String>) -> Self {
+        Self::mime_media_type_set(name, [value])
+    }
+
+    pub fn mime_media_type_set<S: Into<String>>(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::with_strings(ValueTag::MimeMediaType, name, values)
+    }
+
+    pub fn resolution(name: impl Into<AttributeName>, value: Resolution) -> Self {
+        Self::resolution_set(name, [value])
+    }
+
+    pub fn resolution_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = Resolution>,
+    ) -> Self {
+        let values = values.into_iter().map(AttributeValue::Resolution).collect();
+        Self::with_values(ValueTag::Resolution, name, values)
+    }
+
+    pub fn range_of_integer(name: impl Into<AttributeName>, value: RangeOfInteger) -> Self {
+        Self::range_of_integer_set(name, [value])
+    }
+
+    pub fn range_of_integer_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = RangeOfInteger>,
+    ) -> Self {
+        let values = values
+            .into_iter()
+            .map(AttributeValue::RangeOfInteger)
+            .collect();
+        Self::with_values(ValueTag::RangeOfInteger, name, values)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn datetime(name: impl Into<AttributeName>, value: DateTime<Utc>) -> Self {
+        Self::datetime_set(name, [value])
+    }
+
+    #[cfg(feature = "std")]
+    pub fn datetime_set(
+        name: impl Into<AttributeName>,
+        values: impl IntoIterator<Item = DateTime<Utc>>,
+    ) -> Self {
+        let values = values.into_iter().map(AttributeValue::DateTime).collect();
+        Self::with_values(ValueTag::DateTime, name, values)
+    }
+}
+
+// 1setOf values
+impl Attribute {
+    /// Append an additional value, keeping the value-tag of the attribute
+    pub fn push_value(&mut self, value: AttributeValue) {
+        self.values.push(value);
+    }
+
+    /// 'integer' and 'enum' values, other variants are skipped
+    pub fn as_integers(&self) -> impl Iterator<Item = i32> + '_ {
+        self.values.iter().filter_map(AttributeValue::as_i32)
+    }
+
+    /// character-string values (e.g. 'keyword', 'uri'), other variants are skipped
+    pub fn as_strings(&self) -> impl Iterator<Item = &str> + '_ {
+        self.values.iter().filter_map(AttributeValue::as_str)
+    }
+
+    /// 'enum' values as the spec enum `T`, a value `T` does not list (e.g. a vendor
+    /// extension) is an error with the number, other variants are skipped
+    pub fn as_enums<T: IppEnum>(&self) -> impl Iterator<Item = Result<T, i32>> + '_ {
+        self.as_integers()
+            .map(|value| T::from_value(value).ok_or(value))
+    }
+
+    /// 'keyword' values as the spec enum `T`, a keyword `T` does not list is an error
+    /// with the keyword, other variants are skipped
+    pub fn as_keywords<T: KeywordAttribute>(&self) -> impl Iterator<Item = Result<T, &str>> + '_ {
+        self.values
+            .iter()
+            .filter_map(AttributeValue::as_keyword)
+            .map(|keyword| T::from_str(keyword).map_err(|_| keyword))
+    }
+
+    /// 'operations-supported' values, unknown operations are skipped
+    pub fn as_operation_ids(&self) -> Vec<OperationID> {
+        self.as_enums().filter_map(Result::ok).collect()
+    }
+}
+
+// typed access to the first value
+impl Attribute {
+    pub fn first_i32(&self) -> Option<i32> {
+        self.values.first()?.as_i32()
+    }
+
+    pub fn first_str(&self) -> Option<&str> {
+        self.values.first()?.as_str()
+    }
+
+    pub fn first_bool(&self) -> Option<bool> {
+        self.values.first()?.as_bool()
+    }
+
+    pub fn first_printer_state(&self) -> Option<PrinterState> {
+        self.values.first()?.as_printer_state()
+    }
+
+    pub fn first_job_state(&self) -> Option<JobState> {
+        self.values.first()?.as_job_state()
+    }
+
+    pub fn first_text_with_lang(&self) -> Option<&TextWithLang> {
+        self.values.first()?.as_text_with_lang()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn first_datetime(&self) -> Option<DateTime<Utc>> {
+        self.values.first()?.as_datetime()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::attribute::{JobTemplateAttribute, PrinterAttribute};
+    use crate::spec::value::PrintQuality;
+
+    /// runs without `std` too, `cargo test --no-default-features`
+    #[test]
+    fn integer_round_trips() {
+        let copies = Attribute::integer(JobTemplateAttribute::Copies, 3);
+        let bytes = copies.to_ipp();
+        assert_eq!(bytes.len(), copies.ipp_len());
+        // value-length & value
+        assert_eq!(
+            bytes[bytes.len() - 6..],
+            [0x00, 0x04, 0x00, 0x00, 0x00, 0x03]
+        );
+
+        let (len, decoded) = Attribute::from_ipp(&bytes, 0);
+        assert_eq!(len, bytes.len());
+        let decoded = decoded.unwrap();
+        assert_eq!(decoded.tag, ValueTag::Integer);
+        assert_eq!(decoded.values, [AttributeValue::Number(3)]);
+        assert_eq!(decoded, copies);
+    }
+
+    #[test]
+    fn constructors_set_the_tag_of_their_syntax() {
+        let sides = Attribute::keyword(JobTemplateAttribute::Sides, "one-sided");
+        assert_eq!(sides.tag, ValueTag::Keyword);
+        assert_eq!(sides.name, AttributeName::from(JobTemplateAttribute::Sides));
+        assert_eq!(sides.values, [AttributeValue::Keyword("one-sided".into())]);
+
+        let constructed = [
+            (
+                Attribute::integer(JobTemplateAttribute::Copies, 2),
+                ValueTag::Integer,
+                AttributeValue::Number(2),
+            ),
+            (
+                Attribute::boolean(PrinterAttribute::ColorSupported, true),
+                ValueTag::Boolean,
+                AttributeValue::Boolean(true),
+            ),
+            (
+                Attribute::uri(PrinterAttribute::PrinterUriSupported, "ipp://localhost/"),
+                ValueTag::Uri,
+                AttributeValue::Uri("ipp://localhost/".into()),
+            ),
+            (
+                Attribute::enum_value(PrinterAttribute::PrinterState, 3),
+                ValueTag::Enum,
+                AttributeValue::Number(3),
+            ),
+        ];
+        for (attribute, tag, value) in constructed {
+            assert_eq!(attribute.tag, tag);
+            assert_eq!(attribute.values, [value]);
+        }
+    }
+
+    #[test]
+    fn typed_views_of_operations_supported() {
+        let mut operations = Attribute::enum_value_set(
+            PrinterAttribute::OperationsSupported,
+            [
+                OperationID::PrintJob as i32,
+                OperationID::ValidateJob as i32,
+            ],
+        );
+        operations.push_value(AttributeValue::Number(
+            OperationID::GetPrinterAttributes as i32,
+        ));
+        assert_eq!(operations.values.len(), 3);
+        assert_eq!(
+            operations.as_integers().collect::<Vec<_>>(),
+            [0x02, 0x04, 0x0b]
+        );
+        assert_eq!(
+            operations.as_operation_ids(),
+            [
+                OperationID::PrintJob,
+                OperationID::ValidateJob,
+                OperationID::GetPrinterAttributes
+            ]
+        );
+        // only numbers so far, no strings to see
+        assert_eq!(operations.as_strings().count(), 0);
+
+        // values of another variant are skipped, not converted
+        operations.push_value(AttributeValue::Keyword("print-job".into()));
+        assert_eq!(operations.as_integers().count(), 3);
+        assert_eq!(operations.as_strings().collect::<Vec<_>>(), ["print-job"]);
+    }
+
+    #[test]
+    fn vendor_enum_values_are_reported() {
+        // CUPS-Get-Printers is listed, 0x7001 is a vendor's own
+        let operations = Attribute::enum_value_set(
+            PrinterAttribute::OperationsSupported,
+            [OperationID::PrintJob as i32, 0x4002, 0x7001],
+        );
+        assert_eq!(
+            operations.as_enums::<OperationID>().collect::<Vec<_>>(),
+            [
+                Ok(OperationID::PrintJob),
+                Ok(OperationID::CupsGetPrinters),
+                Err(0x7001)
+            ]
+        );
+        assert_eq!(
+            operations.as_operation_ids(),
+            [OperationID::PrintJob, OperationID::CupsGetPrinters]
+        );
+
+        let state = Attribute::enum_value(PrinterAttribute::PrinterState, 0x100);
+        assert_eq!(
+            state.as_enums::<PrinterState>().collect::<Vec<_>>(),
+            [Err(0x100)]
+        );
+        assert_eq!(state.first_printer_state(), None);
+
+        let quality = Attribute::enum_value_set(JobTemplateAttribute::PrintQuality, [5, 6]);
+        assert_eq!(
+            quality.as_enums::<PrintQuality>().collect::<Vec<_>>(),
+            [Ok(PrintQuality::High), Err(6)]
+        );
+    }
+
+    #[test]
+    fn unregistered_character_string_tag_round_trips() {
+        // value-tag 0x4f, name-length & name, value-length & value
+        let mut bytes = vec![0x4f, 0x00, 0x0b];
+        bytes.extend(b"printer-foo");
+        bytes.extend([0x00, 0x03]);
+        bytes.extend(b"bar");
+
+        let (len, decoded) = Attribute::from_ipp(&bytes, 0);
+        let decoded = decoded.unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(decoded.tag, ValueTag::CharacterString(0x4f));
+        assert_eq!(decoded.first_str(), Some("bar"));
+        assert_eq!(decoded.ipp_len(), bytes.len());
+        assert_eq!(decoded.to_ipp(), bytes);
+    }
+
+    #[test]
+    fn set_constructors_keep_the_tag_of_their_syntax() {
+        let sides = Attribute::keyword_set(
+            PrinterAttribute::SidesSupported,
+            ["one-sided", "two-sided-long-edge"],
+        );
+        assert_eq!(sides.tag, ValueTag::Keyword);
+        assert_eq!(
+            sides.as_strings().collect::<Vec<_>>(),
+            ["one-sided", "two-sided-long-edge"]
+        );
+        assert_eq!(
+            Attribute::keyword(PrinterAttribute::SidesDefault, "one-sided").tag,
+            ValueTag::Keyword
+        );
+
+        let sets = [
+            (
+                Attribute::name_with_lang_set(
+                    JobTemplateAttribute::JobSheets,
+                    [
+                        TextWithLang::new("en", "none"),
+                        TextWithLang::new("fr", "aucune"),
+                    ],
+                ),
+                ValueTag::NameWithLanguage,
+            ),
+            (
+                Attribute::resolution_set(
+                    JobTemplateAttribute::PrinterResolution,
+                    ["300x300dpi".parse().unwrap(), "600x600dpi".parse().unwrap()],
+                ),
+                ValueTag::Resolution,
+            ),
+            (
+                Attribute::range_of_integer_set(
+                    JobTemplateAttribute::PageRanges,
+                    [RangeOfInteger::new(1, 2), RangeOfInteger::new(5, 9)],
+                ),
+                ValueTag::RangeOfInteger,
+            ),
+            (
+                Attribute::boolean_set(PrinterAttribute::ColorSupported, [true, false]),
+                ValueTag::Boolean,
+            ),
+        ];
+        for (attribute, tag) in sets {
+            assert_eq!(attribute.tag, tag);
+            assert_eq!(attribute.values.len(), 2);
+
+            let bytes = attribute.to_ipp();
+            assert_eq!(
+                Attribute::from_ipp(&bytes, 0),
+                (bytes.len(), Some(attribute))
+            );
+        }
+    }
+
+    #[test]
+    fn empty_value_is_not_out_of_band() {
+        let empty = Attribute::text(PrinterAttribute::PrinterInfo, "");
+        let bytes = empty.to_ipp();
+        // value-tag, name-length & name, then a value-length of 0
+        assert_eq!(bytes[bytes.len() - 2..], [0x00, 0x00]);
+
+        let (len, decoded) = Attribute::from_ipp(&bytes, 0);
+        let decoded = decoded.unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(decoded.tag, ValueTag::TextWithoutLanguage);
+        assert_eq!(decoded.first_str(), Some(""));
+        assert_eq!(decoded.to_ipp(), bytes);
+
+        // 'no-value' is told apart by its tag only
+        let mut no_value = bytes.clone();
+        no_value[0] = ValueTag::NoValue.raw();
+        let decoded = Attribute::from_ipp(&no_value, 0).1.unwrap();
+        assert_eq!(decoded.tag, ValueTag::NoValue);
+        assert_eq!(decoded.to_ipp(), no_value);
+    }
+}
@@ -1,13 +1,20 @@
-use crate::spec::tag::DelimiterTag;
+use crate::spec::{
+    attribute::{JobAttribute, JobTemplateAttribute, OperationAttribute, PrinterAttribute},
+    tag::DelimiterTag,
+};
 
-use super::{Attribute, AttributeName, IppEncode};
-use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, DisplayFromStr};
-use std::collections::HashMap;
+use super::{
+    operation::check_groups, Attribute, AttributeGroupRef, AttributeName, DecodeLimits, IppEncode,
+    IppError,
+};
+use indexmap::IndexMap;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::io::Write;
+use std::str::FromStr;
 
 /// An "attribute-group" field contains zero or more "attribute" fields.
 ///
-/// ```
+/// ```text
 /// -----------------------------------------------
 /// |           begin-attribute-group-tag         |  1 byte
 /// ----------------------------------------------------------
@@ -17,108 +24,216 @@ use std::collections::HashMap;
 ///
 /// ----------------------------------------------------------
 ///
+/// Attributes keep the order they were inserted or decoded in, and are encoded in it.
+/// Equality compares attributes by name, regardless of that order;
+/// the order of values within an attribute is significant.
+///
 /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.1.2)
 ///
-#[serde_as]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct AttributeGroup {
     pub tag: DelimiterTag,
-    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
-    pub attributes: HashMap<AttributeName, Attribute>,
+    /// serialized as a map keyed by the attribute names as on the wire
+    #[serde(
+        serialize_with = "serialize_attributes",
+        deserialize_with = "deserialize_attributes"
+    )]
+    pub attributes: IndexMap<AttributeName, Attribute>,
 }
 
-impl IppEncode for HashMap<DelimiterTag, AttributeGroup> {
-    fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
-        let mut decoded: Self = HashMap::new();
-
-        let mut shifting_offset = offset;
-
-        let read_tag = |bytes: &[u8], offset: usize| -> (usize, Option<DelimiterTag>) {
-            let slice: [u8; 1] = bytes[offset..offset + 1].try_into().unwrap();
-            let raw_int = u8::from_be_bytes(slice);
-            (1, DelimiterTag::from_repr(raw_int as usize))
-        };
-
-        let (delta, mut tag_opt) = read_tag(bytes, shifting_offset);
-        shifting_offset += delta;
-
-        let mut attributes: HashMap<AttributeName, Attribute> = HashMap::new();
-
-        while shifting_offset < bytes.len() {
-            if let Some(tag) = tag_opt {
-                if tag == DelimiterTag::EndOfAttributes {
-                    break;
-                }
-
-                // read attributes in group
-                let (mut delta, mut attribute_opt) = Attribute::from_ipp(bytes, shifting_offset);
-                loop {
-                    if shifting_offset > bytes.len() {
-                        break;
-                    }
-
-                    if let Some(attribute) = attribute_opt {
-                        attributes.insert(attribute.name.clone(), attribute);
-                        shifting_offset += delta;
-                        let next = Attribute::from_ipp(bytes, shifting_offset);
-                        delta = next.0;
-                        attribute_opt = next.1;
-                    } else {
-                        break;
-                    }
-                }
-
-                decoded.insert(tag, AttributeGroup { tag, attributes });
-
-                attributes = HashMap::new();
-                let next_tag = read_tag(bytes, shifting_offset);
-                shifting_offset += next_tag.0;
-                tag_opt = next_tag.1;
-            } else {
-                break;
-            }
+fn serialize_attributes<S: Serializer>(
+    attributes: &IndexMap<AttributeName, Attribute>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        attributes
+            .iter()
+            .map(|(name, attribute)| (name.to_string(), attribute)),
+    )
+}
+
+fn deserialize_attributes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<IndexMap<AttributeName, Attribute>, D::Error> {
+    IndexMap::<String, Attribute>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, attribute)| {
+            Ok((
+                AttributeName::from_str(&name).map_err(D::Error::custom)?,
+                attribute,
+            ))
+        })
+        .collect()
+}
+
+/// [`AttributeGroup`] with the given tag and attributes, see [`AttributeGroup::with`]
+///
+/// ```
+/// use ipp_encoder::{attributes, encoder::Attribute};
+/// use ipp_encoder::spec::{attribute::PrinterAttribute, tag::DelimiterTag};
+///
+/// let group = attributes!(
+///     DelimiterTag::PrinterAttributes,
+///     Attribute::name(PrinterAttribute::PrinterName, "office"),
+///     Attribute::boolean(PrinterAttribute::ColorSupported, true),
+/// );
+/// assert_eq!(group.attributes.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! attributes {
+    ($tag:expr $(, $attribute:expr)* $(,)?) => {
+        $crate::encoder::AttributeGroup::new($tag)$(.with($attribute))*
+    };
+}
+
+impl AttributeGroup {
+    /// Empty group with the given tag
+    pub fn new(tag: DelimiterTag) -> Self {
+        Self {
+            tag,
+            attributes: IndexMap::new(),
+        }
+    }
+
+    /// Same as [`AttributeGroup::insert`], for chaining
+    pub fn with(mut self, attribute: Attribute) -> Self {
+        self.insert(attribute);
+        self
+    }
+}
+
+/// Inserts each attribute, see [`AttributeGroup::insert`]
+impl Extend<Attribute> for AttributeGroup {
+    fn extend<I: IntoIterator<Item = Attribute>>(&mut self, attributes: I) {
+        for attribute in attributes {
+            self.insert(attribute);
+        }
+    }
+}
+
+// typed access keyed by attribute name
+impl AttributeGroup {
+    /// Insert keyed by `attribute.name`, returning the attribute it replaces
+    pub fn insert(&mut self, attribute: Attribute) -> Option<Attribute> {
+        self.attributes.insert(attribute.name.clone(), attribute)
+    }
+
+    pub fn remove(&mut self, name: &AttributeName) -> Option<Attribute> {
+        self.attributes.shift_remove(name)
+    }
+
+    pub fn get_operation(&self, name: OperationAttribute) -> Option<&Attribute> {
+        self.attributes.get(&AttributeName::Operation(name))
+    }
+
+    pub fn get_printer(&self, name: PrinterAttribute) -> Option<&Attribute> {
+        self.attributes.get(&AttributeName::Printer(name))
+    }
+
+    pub fn get_job(&self, name: JobAttribute) -> Option<&Attribute> {
+        self.attributes.get(&AttributeName::Job(name))
+    }
+
+    pub fn get_job_template(&self, name: JobTemplateAttribute) -> Option<&Attribute> {
+        self.attributes.get(&AttributeName::JobTemplate(name))
+    }
+
+    /// First value of the attribute named `name` (as on the wire),
+    /// if it is a character-string value
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        let name = AttributeName::from_str(name).ok()?;
+        self.attributes.get(&name)?.first_str()
+    }
+}
+
+impl AttributeGroup {
+    /// attributes in encoding order: 'attributes-charset' and 'attributes-natural-language'
+    /// first, as the operation-attributes group requires, then the rest
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.4)
+    pub(crate) fn ordered_attributes(&self) -> impl Iterator<Item = &Attribute> {
+        let charset = AttributeName::Operation(OperationAttribute::AttributesCharset);
+        let natural_language =
+            AttributeName::Operation(OperationAttribute::AttributesNaturalLanguage);
+
+        let leading = [
+            self.attributes.get(&charset),
+            self.attributes.get(&natural_language),
+        ];
+        let rest = self.attributes.values().filter(move |attribute| {
+            attribute.name != charset && attribute.name != natural_language
+        });
+
+        leading.into_iter().flatten().chain(rest)
+    }
+}
+
+impl AttributeGroup {
+    /// Decode attribute groups from `offset` through [`AttributeGroupRef`], also telling
+    /// whether decoding stopped on an end-of-attributes tag
+    fn groups_from_ipp(bytes: &[u8], offset: usize) -> (usize, Vec<Self>, bool) {
+        let (delta, groups, terminated) = AttributeGroupRef::groups_from_ipp(bytes, offset);
+        let groups = groups.iter().map(AttributeGroupRef::to_owned).collect();
+
+        (delta, groups, terminated)
+    }
+}
+
+impl super::IppDecode for Vec<AttributeGroup> {
+    fn min_len() -> usize {
+        // end-of-attributes tag
+        1
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        check_groups(bytes, offset, &DecodeLimits::default())?;
+
+        let (delta, decoded, terminated) = AttributeGroup::groups_from_ipp(bytes, offset);
+        if !terminated {
+            return Err(IppError::MissingEndOfAttributes);
         }
 
-        (shifting_offset - offset, decoded)
+        Ok((delta, decoded))
+    }
+}
+
+impl IppEncode for Vec<AttributeGroup> {
+    fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
+        let (delta, decoded, _) = AttributeGroup::groups_from_ipp(bytes, offset);
+        (delta, decoded)
     }
 
     fn to_ipp(&self) -> Vec<u8> {
         let mut vec: Vec<u8> = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut vec);
+        vec
+    }
 
-        let mut groups: Vec<&AttributeGroup> = Vec::new();
-        if let Some(group) = self.get(&DelimiterTag::OperationAttributes) {
-            groups.push(group);
-        }
-        if let Some(group) = self.get(&DelimiterTag::UnsupportedAttributes) {
-            groups.push(group);
-        }
-        if let Some(group) = self.get(&DelimiterTag::PrinterAttributes) {
-            groups.push(group);
-        }
-        if let Some(group) = self.get(&DelimiterTag::JobAttributes) {
-            groups.push(group);
-        }
+    fn to_writer<W: Write>(&self, writer: &mut W) -> std::io::Result<usize> {
+        let mut len = 0;
 
-        for group in groups {
+        for group in self {
             // write delimiter tag
-            vec.append(&mut (group.tag as u8).to_be_bytes().to_vec());
+            writer.write_all(&(group.tag as u8).to_be_bytes())?;
+            len += 1;
 
-            for attribute in group.attributes.values() {
+            for attribute in group.ordered_attributes() {
                 // write attribute
-                vec.append(&mut attribute.to_ipp());
+                len += attribute.to_writer(writer)?;
             }
         }
 
         // end-of-attributes tag
-        vec.append(&mut (DelimiterTag::EndOfAttributes as u8).to_be_bytes().to_vec());
+        writer.write_all(&(DelimiterTag::EndOfAttributes as u8).to_be_bytes())?;
+        len += 1;
 
-        vec
+        Ok(len)
     }
 
     fn ipp_len(&self) -> usize {
         let mut len: usize = 0;
 
-        for group in self.values() {
+        for group in self {
             len += 1; // delimiter tag
             for attribute in group.attributes.values() {
                 len += attribute.ipp_len();
@@ -130,3 +245,56 @@ impl IppEncode for HashMap<DelimiterTag, AttributeGroup> {
         len
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::assert_ipp_len_matches;
+
+    fn names(group: &AttributeGroup) -> Vec<String> {
+        group
+            .attributes
+            .keys()
+            .map(AttributeName::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn attributes_keep_their_order() {
+        let group = attributes!(
+            DelimiterTag::PrinterAttributes,
+            Attribute::name(PrinterAttribute::PrinterName, "office"),
+            Attribute::boolean(PrinterAttribute::ColorSupported, true),
+        );
+        assert_eq!(group.attributes.len(), 2);
+        assert_eq!(
+            group
+                .get_printer(PrinterAttribute::PrinterName)
+                .and_then(Attribute::first_str),
+            Some("office")
+        );
+        assert!(group
+            .get_printer(PrinterAttribute::ColorSupported)
+            .is_some());
+        assert_eq!(names(&group), ["printer-name", "color-supported"]);
+
+        // kept on the wire and through JSON
+        let groups = vec![group];
+        assert_ipp_len_matches(&groups);
+        let (_, decoded) = <Vec<AttributeGroup> as IppEncode>::from_ipp(&groups.to_ipp(), 0);
+        assert_eq!(names(&decoded[0]), ["printer-name", "color-supported"]);
+        assert_eq!(decoded, groups);
+        let json = serde_json::to_string(&groups[0]).unwrap();
+        let deserialized: AttributeGroup = serde_json::from_str(&json).unwrap();
+        assert_eq!(names(&deserialized), ["printer-name", "color-supported"]);
+        assert_eq!(deserialized, groups[0]);
+
+        // equality does not depend on the order
+        let reversed = attributes!(
+            DelimiterTag::PrinterAttributes,
+            Attribute::boolean(PrinterAttribute::ColorSupported, true),
+            Attribute::name(PrinterAttribute::PrinterName, "office"),
+        );
+        assert_eq!(reversed, groups[0]);
+    }
+}
@@ -1,10 +1,18 @@
-use crate::spec::attribute::{
-    JobAttribute, JobTemplateAttribute, OperationAttribute, PrinterAttribute,
+use crate::spec::{
+    attribute::{JobAttribute, JobTemplateAttribute, OperationAttribute, PrinterAttribute},
+    operation::ResponseAttribute,
+    tag::ValueTag,
 };
 
 use super::{error::AttributeNameParseError, IppEncode};
+use crate::io::{self, Write};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use strum::IntoEnumIterator;
 
 /// Generalized attribute name from different group (operation, printer, job, job-template)
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
@@ -16,7 +24,7 @@ pub enum AttributeName {
     Unsupported(String),
 }
 
-impl std::str::FromStr for AttributeName {
+impl FromStr for AttributeName {
     type Err = AttributeNameParseError;
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         if let Ok(n) = OperationAttribute::from_str(str) {
@@ -33,8 +41,8 @@ impl std::str::FromStr for AttributeName {
     }
 }
 
-impl std::fmt::Display for AttributeName {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for AttributeName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let attr = match self {
             Self::Operation(attr) => attr.to_string(),
             Self::Printer(attr) => attr.to_string(),
@@ -46,14 +54,76 @@ impl std::fmt::Display for AttributeName {
     }
 }
 
+impl From<OperationAttribute> for AttributeName {
+    fn from(name: OperationAttribute) -> Self {
+        Self::Operation(name)
+    }
+}
+
+impl From<PrinterAttribute> for AttributeName {
+    fn from(name: PrinterAttribute) -> Self {
+        Self::Printer(name)
+    }
+}
+
+impl From<JobTemplateAttribute> for AttributeName {
+    fn from(name: JobTemplateAttribute) -> Self {
+        Self::JobTemplate(name)
+    }
+}
+
+impl From<JobAttribute> for AttributeName {
+    fn from(name: JobAttribute) -> Self {
+        Self::Job(name)
+    }
+}
+
+impl From<ResponseAttribute> for AttributeName {
+    fn from(name: ResponseAttribute) -> Self {
+        match name {
+            ResponseAttribute::Operation(name) => Self::Operation(name),
+            ResponseAttribute::Job(name) => Self::Job(name),
+        }
+    }
+}
+
 impl AttributeName {
-    pub fn is_empty(&self) -> bool {
-        if let Self::Unsupported(attr) = self {
-            attr.is_empty()
-        } else {
-            false
+    /// Parse a name from one of the registries, erring on any other name
+    ///
+    /// [`FromStr`] keeps unknown names as [`AttributeName::Unsupported`], which suits
+    /// decoding; this suits names typed by hand (e.g. in configuration) where an
+    /// unknown name is most likely a typo. The error suggests the closest known name.
+    pub fn parse_strict(name: &str) -> Result<Self, AttributeNameParseError> {
+        match Self::from_str(name)? {
+            Self::Unsupported(_) => {
+                Err(AttributeNameParseError::new(name, closest_known_name(name)))
+            }
+            known => Ok(known),
         }
     }
+
+    /// value-tags of the attribute syntax, None for names outside the registries
+    pub fn value_tags(&self) -> Option<&'static [ValueTag]> {
+        match self {
+            Self::Operation(attr) => Some(attr.value_tags()),
+            Self::Printer(attr) => Some(attr.value_tags()),
+            Self::JobTemplate(attr) => Some(attr.value_tags()),
+            Self::Job(attr) => Some(attr.value_tags()),
+            Self::Unsupported(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl super::IppDecode for AttributeName {
+    fn min_len() -> usize {
+        <String as super::IppDecode>::min_len()
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), super::IppError> {
+        let (delta, raw_name) = <String as super::IppDecode>::from_ipp(bytes, offset)?;
+        Ok((delta, Self::from_str(&raw_name).unwrap()))
+    }
 }
 
 impl IppEncode for AttributeName {
@@ -66,7 +136,48 @@ impl IppEncode for AttributeName {
         self.to_string().to_ipp()
     }
 
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        self.to_string().to_writer(writer)
+    }
+
     fn ipp_len(&self) -> usize {
         self.to_string().ipp_len()
     }
 }
+
+/// every name of the operation, printer, job-template and job registries
+fn known_names() -> impl Iterator<Item = String> {
+    OperationAttribute::iter()
+        .map(|name| name.to_string())
+        .chain(PrinterAttribute::iter().map(|name| name.to_string()))
+        .chain(JobTemplateAttribute::iter().map(|name| name.to_string()))
+        .chain(JobAttribute::iter().map(|name| name.to_string()))
+}
+
+/// known name at the smallest edit distance from `name`, if within a third of its length
+fn closest_known_name(name: &str) -> Option<String> {
+    let max_distance = name.len() / 3 + 1;
+
+    known_names()
+        .map(|known| (levenshtein(name, &known), known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -0,0 +1,191 @@
+//! Serialization of [`Attribute`], a projection for JSON and the like, not the wire format
+//!
+//! Tag and name are their registry strings, a tag number is also read. A single value is flattened into `value`,
+//! any other number of values is listed under `values`:
+//!
+//! ```text
+//! {"tag":"charset","name":"attributes-charset","value":"utf-8"}
+//! {"tag":"keyword","name":"requested-attributes","values":["printer-name","printer-state"]}
+//! ```
+//!
+//! Values are plain: numbers, booleans, strings (RFC 3339 for 'dateTime', `600x600dpi`
+//! for 'resolution', `1-5` for 'rangeOfInteger', the tag
+//! picks the [`AttributeValue`] variant of other strings),
+//! `{"lang": .., "text": ..}` for values with language and an array of member
+//! attributes for 'collection'.
+
+use super::{Attribute, AttributeName, AttributeValue, RangeOfInteger, Resolution, TextWithLang};
+use crate::spec::tag::ValueTag;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use chrono::{DateTime, Utc};
+use core::str::FromStr;
+use serde::{de::Error, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Attribute {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Attribute", 3)?;
+        state.serialize_field("tag", &self.tag.to_string())?;
+        state.serialize_field("name", &self.name.to_string())?;
+
+        match self.values.as_slice() {
+            [value] => state.serialize_field("value", &PlainValue(value))?,
+            values => {
+                let values: Vec<PlainValue> = values.iter().map(PlainValue).collect();
+                state.serialize_field("values", &values)?
+            }
+        }
+
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Attribute {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawAttribute::deserialize(deserializer)?;
+
+        let tag = raw.tag;
+        let name = AttributeName::from_str(&raw.name).unwrap();
+
+        let values = match (raw.value, raw.values) {
+            (Some(value), None) => vec![value],
+            (None, Some(values)) => values,
+            (None, None) => return Err(D::Error::missing_field("value")),
+            (Some(_), Some(_)) => return Err(D::Error::duplicate_field("values")),
+        };
+
+        let values = values
+            .into_iter()
+            .map(|value| value.into_value(tag))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(D::Error::custom)?;
+
+        Ok(Self { tag, name, values })
+    }
+}
+
+/// value without its variant, the attribute tag tells what it is
+struct PlainValue<'a>(&'a AttributeValue);
+
+impl Serialize for PlainValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            AttributeValue::TextWithoutLang(text)
+            | AttributeValue::Keyword(text)
+            | AttributeValue::Uri(text)
+            | AttributeValue::MimeType(text)
+            | AttributeValue::Charset(text)
+            | AttributeValue::NaturalLanguage(text) => serializer.serialize_str(text),
+            AttributeValue::Number(number) => serializer.serialize_i32(*number),
+            AttributeValue::Boolean(boolean) => serializer.serialize_bool(*boolean),
+            AttributeValue::TextWithLang(text_with_lang) => text_with_lang.serialize(serializer),
+            AttributeValue::Resolution(resolution) => resolution.serialize(serializer),
+            AttributeValue::RangeOfInteger(range) => range.serialize(serializer),
+            #[cfg(feature = "std")]
+            AttributeValue::DateTime(datetime) => datetime.serialize(serializer),
+            AttributeValue::Collection(members) => members.serialize(serializer),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawAttribute {
+    tag: ValueTag,
+    name: String,
+    #[serde(default)]
+    value: Option<RawValue>,
+    #[serde(default)]
+    values: Option<Vec<RawValue>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValue {
+    Boolean(bool),
+    Number(i32),
+    TextWithLang(TextWithLang),
+    Collection(Vec<Attribute>),
+    Text(String),
+}
+
+impl RawValue {
+    /// a string is a 'dateTime', 'resolution', 'rangeOfInteger' or a character-string
+    /// of the attribute tag
+    fn into_value(self, tag: ValueTag) -> Result<AttributeValue, &'static str> {
+        Ok(match self {
+            Self::Boolean(boolean) => AttributeValue::Boolean(boolean),
+            Self::Number(number) => AttributeValue::Number(number),
+            Self::TextWithLang(text_with_lang) => AttributeValue::TextWithLang(text_with_lang),
+            Self::Collection(members) => AttributeValue::Collection(members),
+            #[cfg(feature = "std")]
+            Self::Text(text) if tag == ValueTag::DateTime => AttributeValue::DateTime(
+                DateTime::<Utc>::from_str(&text).map_err(|_| "invalid dateTime value")?,
+            ),
+            Self::Text(text) if tag == ValueTag::Resolution => AttributeValue::Resolution(
+                Resolution::from_str(&text).map_err(|_| "invalid resolution value")?,
+            ),
+            Self::Text(text) if tag == ValueTag::RangeOfInteger => AttributeValue::RangeOfInteger(
+                RangeOfInteger::from_str(&text).map_err(|_| "invalid rangeOfInteger value")?,
+            ),
+            Self::Text(text) => AttributeValue::string(tag, text),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::spec::attribute::{OperationAttribute, PrinterAttribute};
+
+    #[test]
+    fn single_value_is_flattened() {
+        let charset = Attribute::charset(OperationAttribute::AttributesCharset, "utf-8");
+        let json = serde_json::to_string(&charset).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tag":"charset","name":"attributes-charset","value":"utf-8"}"#
+        );
+        assert_eq!(serde_json::from_str::<Attribute>(&json).unwrap(), charset);
+    }
+
+    #[test]
+    fn several_values_are_listed() {
+        let requested = Attribute::keyword_set(
+            OperationAttribute::RequestedAttributes,
+            ["printer-name", "printer-state"],
+        );
+        let json = serde_json::to_string(&requested).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tag":"keyword","name":"requested-attributes","values":["printer-name","printer-state"]}"#
+        );
+        assert_eq!(serde_json::from_str::<Attribute>(&json).unwrap(), requested);
+
+        let ranges = Attribute::range_of_integer_set(
+            PrinterAttribute::CopiesSupported,
+            [RangeOfInteger::new(1, 9), RangeOfInteger::new(20, 99)],
+        );
+        let json = serde_json::to_string(&ranges).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tag":"rangeOfInteger","name":"copies-supported","values":["1-9","20-99"]}"#
+        );
+        assert_eq!(serde_json::from_str::<Attribute>(&json).unwrap(), ranges);
+    }
+
+    #[test]
+    fn values_must_suit_the_tag() {
+        for json in [
+            r#"{"tag":"bogus","name":"printer-name","value":"office"}"#,
+            r#"{"tag":"dateTime","name":"printer-current-time","value":"now"}"#,
+            r#"{"tag":"keyword","name":"printer-name"}"#,
+            r#"{"tag":"keyword","name":"sides","value":"one-sided","values":["one-sided"]}"#,
+        ] {
+            assert!(serde_json::from_str::<Attribute>(json).is_err(), "{}", json);
+        }
+    }
+}
@@ -1,5 +1,13 @@
-use super::{IppEncode, TextWithLang};
-use crate::spec::tag::ValueTag;
+use super::{Attribute, AttributeName, IppEncode, RangeOfInteger, Resolution, TextWithLang};
+use crate::io::{self, Write};
+use crate::spec::{
+    operation::{JobState, OperationID, PrinterState},
+    tag::ValueTag,
+    value::{OrientationRequested, PrintQuality},
+    IppEnum,
+};
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -8,11 +16,227 @@ use serde::{Deserialize, Serialize};
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8010#section-3.9)
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum AttributeValue {
+    /// character-string value of any other tag (e.g. 'textWithoutLanguage', 'name', 'uriScheme')
     TextWithoutLang(String),
+    Keyword(String),
+    Uri(String),
+    /// 'mimeMediaType' value
+    MimeType(String),
+    Charset(String),
+    NaturalLanguage(String),
     Number(i32),
     Boolean(bool),
     TextWithLang(TextWithLang),
+    Resolution(Resolution),
+    RangeOfInteger(RangeOfInteger),
+    /// requires the `std` feature
+    #[cfg(feature = "std")]
     DateTime(DateTime<Utc>),
+    /// 'collection' value, each member attribute carries its own tag and values
+    ///
+    /// ```text
+    /// -----------------------------------------------
+    /// |  begCollection (name: attribute or empty)   |   value-length 0x0000
+    /// -----------------------------------------------
+    /// |  memberAttrName (empty name)                |   value is member name  |- 0 or more
+    /// |  member value (empty name)                  |   1 or more             |
+    /// -----------------------------------------------
+    /// |  endCollection (empty name)                 |   value-length 0x0000
+    /// -----------------------------------------------
+    /// ```
+    ///
+    /// Member values and additional collection values both use a zero-length name;
+    /// they are told apart because the whole collection, up to its endCollection,
+    /// is consumed as one value.
+    ///
+    /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.1.6)
+    Collection(Vec<Attribute>),
+}
+
+impl AttributeValue {
+    /// character-string value in the variant of `tag`, [`AttributeValue::TextWithoutLang`]
+    /// for tags without one of their own
+    pub fn string(tag: ValueTag, value: impl Into<String>) -> Self {
+        let value = value.into();
+        match tag {
+            ValueTag::Keyword => Self::Keyword(value),
+            ValueTag::Uri => Self::Uri(value),
+            ValueTag::MimeMediaType => Self::MimeType(value),
+            ValueTag::Charset => Self::Charset(value),
+            ValueTag::NaturalLanguage => Self::NaturalLanguage(value),
+            _ => Self::TextWithoutLang(value),
+        }
+    }
+
+    /// value-tag the variant stands for, None where several tags share it
+    /// (e.g. 'integer' and 'enum' are both [`AttributeValue::Number`])
+    pub fn value_tag(&self) -> Option<ValueTag> {
+        match self {
+            Self::Keyword(_) => Some(ValueTag::Keyword),
+            Self::Uri(_) => Some(ValueTag::Uri),
+            Self::MimeType(_) => Some(ValueTag::MimeMediaType),
+            Self::Charset(_) => Some(ValueTag::Charset),
+            Self::NaturalLanguage(_) => Some(ValueTag::NaturalLanguage),
+            Self::Boolean(_) => Some(ValueTag::Boolean),
+            Self::Resolution(_) => Some(ValueTag::Resolution),
+            Self::RangeOfInteger(_) => Some(ValueTag::RangeOfInteger),
+            #[cfg(feature = "std")]
+            Self::DateTime(_) => Some(ValueTag::DateTime),
+            Self::Collection(_) => Some(ValueTag::BegCollection),
+            Self::TextWithoutLang(_) | Self::Number(_) | Self::TextWithLang(_) => None,
+        }
+    }
+}
+
+// typed access, None for any other variant
+impl AttributeValue {
+    /// 'integer' and 'enum' value
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Self::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// character-string value of any tag (e.g. 'keyword', 'uri', 'nameWithoutLanguage')
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::TextWithoutLang(text)
+            | Self::Keyword(text)
+            | Self::Uri(text)
+            | Self::MimeType(text)
+            | Self::Charset(text)
+            | Self::NaturalLanguage(text) => Some(text.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_keyword(&self) -> Option<&str> {
+        match self {
+            Self::Keyword(keyword) => Some(keyword.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_uri(&self) -> Option<&str> {
+        match self {
+            Self::Uri(uri) => Some(uri.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    /// 'textWithLanguage' and 'nameWithLanguage' value
+    pub fn as_text_with_lang(&self) -> Option<&TextWithLang> {
+        match self {
+            Self::TextWithLang(text_with_lang) => Some(text_with_lang),
+            _ => None,
+        }
+    }
+
+    pub fn as_resolution(&self) -> Option<Resolution> {
+        match self {
+            Self::Resolution(resolution) => Some(*resolution),
+            _ => None,
+        }
+    }
+
+    pub fn as_range_of_integer(&self) -> Option<RangeOfInteger> {
+        match self {
+            Self::RangeOfInteger(range) => Some(*range),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::DateTime(datetime) => Some(*datetime),
+            _ => None,
+        }
+    }
+
+    /// 'enum' value as the spec enum `T`, None for values `T` does not list
+    pub fn as_enum<T: IppEnum>(&self) -> Option<T> {
+        T::from_value(self.as_i32()?)
+    }
+
+    /// 'enum' value of 'operations-supported' and the like, None for unknown operations
+    pub fn as_operation_id(&self) -> Option<OperationID> {
+        self.as_enum()
+    }
+
+    /// 'enum' value of 'printer-state'
+    pub fn as_printer_state(&self) -> Option<PrinterState> {
+        self.as_enum()
+    }
+
+    /// 'enum' value of 'job-state'
+    pub fn as_job_state(&self) -> Option<JobState> {
+        self.as_enum()
+    }
+
+    /// 'enum' value of 'orientation-requested'
+    pub fn as_orientation_requested(&self) -> Option<OrientationRequested> {
+        self.as_enum()
+    }
+
+    /// 'enum' value of 'print-quality'
+    pub fn as_print_quality(&self) -> Option<PrintQuality> {
+        self.as_enum()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AttributeValue {
+    /// JSON shape of the value itself, unlike the serde derive which tags the variant
+    ///
+    /// - 'integer' and 'enum': number
+    /// - 'boolean': boolean
+    /// - 'dateTime': RFC 3339 string
+    /// - 'resolution' and 'rangeOfInteger': string, e.g. `"600x600dpi"` and `"1-5"`
+    /// - character-strings: string
+    /// - 'textWithLanguage' and 'nameWithLanguage': `{"lang": .., "text": ..}`
+    /// - 'collection': object of member names, a member with several values is an array
+    pub fn to_json_value(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Self::TextWithoutLang(text)
+            | Self::Keyword(text)
+            | Self::Uri(text)
+            | Self::MimeType(text)
+            | Self::Charset(text)
+            | Self::NaturalLanguage(text) => Value::from(text.as_str()),
+            Self::Number(number) => Value::from(*number),
+            Self::Boolean(boolean) => Value::from(*boolean),
+            Self::TextWithLang(text_with_lang) => serde_json::json!({
+                "lang": text_with_lang.lang,
+                "text": text_with_lang.text,
+            }),
+            Self::DateTime(datetime) => Value::from(datetime.to_rfc3339()),
+            Self::Resolution(resolution) => Value::from(resolution.to_string()),
+            Self::RangeOfInteger(range) => Value::from(range.to_string()),
+            Self::Collection(members) => Value::Object(
+                members
+                    .iter()
+                    .map(|member| {
+                        let value = match member.values.as_slice() {
+                            [value] => value.to_json_value(),
+                            values => values.iter().map(Self::to_json_value).collect(),
+                        };
+                        (member.name.to_string(), value)
+                    })
+                    .collect(),
+            ),
+        }
+    }
 }
 
 impl AttributeValue {
@@ -30,33 +254,167 @@ impl AttributeValue {
                 len = delta;
                 value = Self::Boolean(raw_value);
             }
-            ValueTag::TextWithLanguage => {
+            // the attribute keeps the tag that tells 'text' and 'name' apart
+            ValueTag::TextWithLanguage | ValueTag::NameWithLanguage => {
                 let (delta, raw_value) = TextWithLang::from_ipp(bytes, offset);
                 len = delta;
                 value = Self::TextWithLang(raw_value);
             }
+            ValueTag::Resolution => {
+                let (delta, raw_value) = Resolution::from_ipp(bytes, offset);
+                len = delta;
+                value = Self::Resolution(raw_value);
+            }
+            ValueTag::RangeOfInteger => {
+                let (delta, raw_value) = RangeOfInteger::from_ipp(bytes, offset);
+                len = delta;
+                value = Self::RangeOfInteger(raw_value);
+            }
+            #[cfg(feature = "std")]
             ValueTag::DateTime => {
                 let (delta, raw_value) = DateTime::from_ipp(bytes, offset);
                 len = delta;
                 value = Self::DateTime(raw_value);
             }
+            ValueTag::BegCollection => {
+                let (delta, members) = Self::collection_from_ipp(bytes, offset);
+                len = delta;
+                value = Self::Collection(members);
+            }
+            tag if tag.is_out_of_band() => {
+                // out-of-band: there is no value, whatever value-length says is skipped.
+                // Decoded as an empty string, the attribute tag tells it apart from
+                // a character-string value of length 0 (e.g. an empty 'printer-info')
+                let value_length = u16::from_be_bytes([bytes[offset], bytes[offset + 1]]);
+                len = 2 + value_length as usize;
+                value = Self::TextWithoutLang(String::new());
+            }
             _ => {
                 let (delta, raw_value) = String::from_ipp(bytes, offset);
                 len = delta;
-                value = Self::TextWithoutLang(raw_value);
+                value = Self::string(value_tag, raw_value);
             }
         }
 
         (len, value)
     }
 
+    /// whether `value`, the bytes after value-length, can be decoded as `value_tag`
+    #[cfg(feature = "std")]
+    pub(crate) fn is_valid(value_tag: ValueTag, value: &[u8]) -> bool {
+        match value_tag {
+            ValueTag::Boolean => value == [0x00] || value == [0x01],
+            tag if tag.is_integer_type() => Some(value.len()) == tag.expected_fixed_len(),
+            // units are 3 (dots per inch) or 4 (dots per centimeter)
+            ValueTag::Resolution => value.len() == 9 && matches!(value[8], 3 | 4),
+            ValueTag::RangeOfInteger => Some(value.len()) == value_tag.expected_fixed_len(),
+            ValueTag::TextWithLanguage | ValueTag::NameWithLanguage => {
+                // lang-length, lang, text-length, text
+                let lang_end = match value.get(0..2) {
+                    Some(len) => 2 + u16::from_be_bytes([len[0], len[1]]) as usize,
+                    None => return false,
+                };
+                let text_end = match value.get(lang_end..lang_end + 2) {
+                    Some(len) => lang_end + 2 + u16::from_be_bytes([len[0], len[1]]) as usize,
+                    None => return false,
+                };
+                text_end == value.len()
+                    && core::str::from_utf8(&value[2..lang_end]).is_ok()
+                    && core::str::from_utf8(&value[lang_end + 2..]).is_ok()
+            }
+            #[cfg(feature = "std")]
+            ValueTag::DateTime => super::datetime::datetime_from_value(value).is_some(),
+            // out-of-band values are skipped
+            tag if tag.is_out_of_band() => true,
+            // every other value is decoded as a string
+            _ => core::str::from_utf8(value).is_ok(),
+        }
+    }
+
     pub fn to_ipp(&self) -> Vec<u8> {
         match self {
             Self::Boolean(raw_value) => raw_value.to_ipp(),
             Self::Number(raw_value) => raw_value.to_ipp(),
+            #[cfg(feature = "std")]
             Self::DateTime(raw_value) => raw_value.to_ipp(),
             Self::TextWithLang(raw_value) => raw_value.to_ipp(),
-            Self::TextWithoutLang(raw_value) => raw_value.to_ipp(),
+            Self::Resolution(raw_value) => raw_value.to_ipp(),
+            Self::RangeOfInteger(raw_value) => raw_value.to_ipp(),
+            Self::TextWithoutLang(raw_value)
+            | Self::Keyword(raw_value)
+            | Self::Uri(raw_value)
+            | Self::MimeType(raw_value)
+            | Self::Charset(raw_value)
+            | Self::NaturalLanguage(raw_value) => raw_value.to_ipp(),
+            Self::Collection(_) => {
+                let mut bytes = Vec::with_capacity(self.ipp_len());
+                self.to_ipp_into(&mut bytes);
+                bytes
+            }
+        }
+    }
+
+    pub fn to_ipp_into(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Boolean(raw_value) => raw_value.to_ipp_into(buf),
+            Self::Number(raw_value) => raw_value.to_ipp_into(buf),
+            #[cfg(feature = "std")]
+            Self::DateTime(raw_value) => raw_value.to_ipp_into(buf),
+            Self::TextWithLang(raw_value) => raw_value.to_ipp_into(buf),
+            Self::Resolution(raw_value) => raw_value.to_ipp_into(buf),
+            Self::RangeOfInteger(raw_value) => raw_value.to_ipp_into(buf),
+            Self::TextWithoutLang(raw_value)
+            | Self::Keyword(raw_value)
+            | Self::Uri(raw_value)
+            | Self::MimeType(raw_value)
+            | Self::Charset(raw_value)
+            | Self::NaturalLanguage(raw_value) => raw_value.to_ipp_into(buf),
+            // writing into a Vec only fails on a length that does not fit in 2 bytes
+            Self::Collection(members) => {
+                Self::collection_to_writer(members, buf).expect(io::TOO_LONG);
+            }
+        }
+    }
+
+    pub fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        match self {
+            Self::Boolean(raw_value) => raw_value.to_writer(writer),
+            Self::Number(raw_value) => raw_value.to_writer(writer),
+            #[cfg(feature = "std")]
+            Self::DateTime(raw_value) => raw_value.to_writer(writer),
+            Self::TextWithLang(raw_value) => raw_value.to_writer(writer),
+            Self::Resolution(raw_value) => raw_value.to_writer(writer),
+            Self::RangeOfInteger(raw_value) => raw_value.to_writer(writer),
+            Self::TextWithoutLang(raw_value)
+            | Self::Keyword(raw_value)
+            | Self::Uri(raw_value)
+            | Self::MimeType(raw_value)
+            | Self::Charset(raw_value)
+            | Self::NaturalLanguage(raw_value) => raw_value.to_writer(writer),
+            Self::Collection(members) => Self::collection_to_writer(members, writer),
+        }
+    }
+
+    /// write as a value of `tag`; out-of-band tags only get a 0x0000 value-length
+    pub(crate) fn tagged_to_writer<W: Write>(
+        &self,
+        tag: ValueTag,
+        writer: &mut W,
+    ) -> io::Result<usize> {
+        if tag.is_out_of_band() {
+            writer.write_all(&0_u16.to_be_bytes())?;
+            Ok(2)
+        } else {
+            self.to_writer(writer)
+        }
+    }
+
+    /// length of [`AttributeValue::tagged_to_writer`]
+    pub(crate) fn tagged_ipp_len(&self, tag: ValueTag) -> usize {
+        if tag.is_out_of_band() {
+            2
+        } else {
+            self.ipp_len()
         }
     }
 
@@ -64,9 +422,536 @@ impl AttributeValue {
         match self {
             Self::Boolean(raw_value) => raw_value.ipp_len(),
             Self::Number(raw_value) => raw_value.ipp_len(),
+            #[cfg(feature = "std")]
             Self::DateTime(raw_value) => raw_value.ipp_len(),
             Self::TextWithLang(raw_value) => raw_value.ipp_len(),
-            Self::TextWithoutLang(raw_value) => raw_value.ipp_len(),
+            Self::Resolution(raw_value) => raw_value.ipp_len(),
+            Self::RangeOfInteger(raw_value) => raw_value.ipp_len(),
+            Self::TextWithoutLang(raw_value)
+            | Self::Keyword(raw_value)
+            | Self::Uri(raw_value)
+            | Self::MimeType(raw_value)
+            | Self::Charset(raw_value)
+            | Self::NaturalLanguage(raw_value) => raw_value.ipp_len(),
+            Self::Collection(members) => {
+                // value-length of begCollection (0x0000)
+                let mut len = 2;
+                for member in members {
+                    // memberAttrName: value-tag, name-length (0x0000) and member name as value
+                    len += 1 + 2 + member.name.ipp_len();
+                    for value in &member.values {
+                        // member value: value-tag, name-length (0x0000) and value
+                        len += 1 + 2 + value.tagged_ipp_len(member.tag);
+                    }
+                }
+                // endCollection: value-tag, name-length & value-length (0x0000)
+                len + 1 + 2 + 2
+            }
         }
     }
 }
+
+impl AttributeValue {
+    /// decode members from `offset`, which points at the value-length of begCollection,
+    /// up to and including the matching endCollection
+    fn collection_from_ipp(bytes: &[u8], offset: usize) -> (usize, Vec<Attribute>) {
+        // begCollection has no value, skip its value-length
+        let (mut shifting_offset, _) = String::from_ipp(bytes, offset);
+        shifting_offset += offset;
+
+        let mut members: Vec<Attribute> = Vec::new();
+
+        while shifting_offset < bytes.len() {
+            let tag = match ValueTag::from_wire(bytes[shifting_offset]) {
+                Some(tag) => tag,
+                // not a value-tag, the collection is malformed
+                None => break,
+            };
+            shifting_offset += 1;
+
+            // every member field has an empty name
+            let (delta, _) = String::from_ipp(bytes, shifting_offset);
+            shifting_offset += delta;
+
+            match tag {
+                ValueTag::EndCollection => {
+                    let (delta, _) = String::from_ipp(bytes, shifting_offset);
+                    shifting_offset += delta;
+                    break;
+                }
+                ValueTag::MemberAttrName => {
+                    let (delta, name) = AttributeName::from_ipp(bytes, shifting_offset);
+                    shifting_offset += delta;
+
+                    members.push(Attribute {
+                        // takes the tag of its first value
+                        tag: ValueTag::NoValue,
+                        name,
+                        values: Vec::new(),
+                    });
+                }
+                _ => {
+                    // nested collections are decoded recursively
+                    let (delta, value) = Self::from_ipp(bytes, shifting_offset, tag);
+                    shifting_offset += delta;
+
+                    if let Some(member) = members.last_mut() {
+                        if member.values.is_empty() {
+                            member.tag = tag;
+                        }
+                        member.values.push(value);
+                    }
+                }
+            }
+        }
+
+        (shifting_offset - offset, members)
+    }
+
+    fn collection_to_writer<W: Write>(members: &[Attribute], writer: &mut W) -> io::Result<usize> {
+        // begCollection value-length
+        writer.write_all(&0_u16.to_be_bytes())?;
+        let mut len = 2;
+
+        for member in members {
+            writer.write_all(&ValueTag::MemberAttrName.raw().to_be_bytes())?;
+            writer.write_all(&0_u16.to_be_bytes())?;
+            len += 3 + member.name.to_writer(writer)?;
+
+            for value in &member.values {
+                writer.write_all(&member.tag.raw().to_be_bytes())?;
+                writer.write_all(&0_u16.to_be_bytes())?;
+                len += 3 + value.tagged_to_writer(member.tag, writer)?;
+            }
+        }
+
+        writer.write_all(&ValueTag::EndCollection.raw().to_be_bytes())?;
+        writer.write_all(&0_u16.to_be_bytes())?;
+        writer.write_all(&0_u16.to_be_bytes())?;
+
+        Ok(len + 5)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::spec::value::ResolutionUnits;
+    use chrono::TimeZone;
+    use core::str::FromStr;
+    use proptest::prelude::*;
+
+    /// value of any variant but collections, with a tag it can be sent with
+    fn scalar() -> impl Strategy<Value = (ValueTag, AttributeValue)> {
+        let text = || ".{0,64}";
+        prop_oneof![
+            (
+                prop::sample::select(vec![ValueTag::Integer, ValueTag::Enum]),
+                any::<i32>()
+            )
+                .prop_map(|(tag, number)| (tag, AttributeValue::Number(number))),
+            any::<bool>().prop_map(|value| (ValueTag::Boolean, AttributeValue::Boolean(value))),
+            (
+                prop::sample::select(vec![
+                    ValueTag::OctetStringUnspecified,
+                    ValueTag::TextWithoutLanguage,
+                    ValueTag::NameWithoutLanguage,
+                    ValueTag::Keyword,
+                    ValueTag::Uri,
+                    ValueTag::UriScheme,
+                    ValueTag::Charset,
+                    ValueTag::NaturalLanguage,
+                    ValueTag::MimeMediaType,
+                ]),
+                text(),
+            )
+                .prop_map(|(tag, value)| (tag, AttributeValue::string(tag, value))),
+            (
+                prop::sample::select(vec![ValueTag::TextWithLanguage, ValueTag::NameWithLanguage]),
+                text(),
+                text(),
+            )
+                .prop_map(|(tag, lang, text)| {
+                    (
+                        tag,
+                        AttributeValue::TextWithLang(TextWithLang { lang, text }),
+                    )
+                }),
+            (
+                any::<i32>(),
+                any::<i32>(),
+                prop::sample::select(vec![
+                    ResolutionUnits::DotsPerInch,
+                    ResolutionUnits::DotsPerCentimeter
+                ]),
+            )
+                .prop_map(|(cross_feed, feed, units)| {
+                    let value = Resolution::new(cross_feed, feed, units);
+                    (ValueTag::Resolution, AttributeValue::Resolution(value))
+                }),
+            (any::<i32>(), any::<i32>()).prop_map(|(lower, upper)| {
+                let value = RangeOfInteger::new(lower, upper);
+                (
+                    ValueTag::RangeOfInteger,
+                    AttributeValue::RangeOfInteger(value),
+                )
+            }),
+            // 'dateTime' has deciseconds, years up to 9999
+            (0..2_534_022_720_000_i64 / 100).prop_map(|deciseconds| {
+                let value = Utc.timestamp_millis_opt(deciseconds * 100).unwrap();
+                (ValueTag::DateTime, AttributeValue::DateTime(value))
+            }),
+        ]
+    }
+
+    fn value() -> impl Strategy<Value = (ValueTag, AttributeValue)> {
+        let member = ("[a-z][a-z-]{0,15}", scalar()).prop_map(|(name, (tag, value))| Attribute {
+            tag,
+            name: AttributeName::from_str(&name).unwrap(),
+            values: vec![value],
+        });
+        prop_oneof![
+            4 => scalar(),
+            1 => prop::collection::vec(member, 0..4).prop_map(|members| {
+                (ValueTag::BegCollection, AttributeValue::Collection(members))
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip((tag, value) in value()) {
+            let bytes = value.to_ipp();
+            prop_assert_eq!(bytes.len(), value.ipp_len());
+
+            let (len, decoded) = AttributeValue::from_ipp(&bytes, 0, tag);
+            prop_assert_eq!(len, bytes.len());
+            prop_assert_eq!(decoded, value);
+        }
+    }
+
+    /// append a field: value-tag, name and value, each length-prefixed
+    fn push_field(bytes: &mut Vec<u8>, tag: ValueTag, name: &str, value: &[u8]) {
+        bytes.push(tag.raw());
+        bytes.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(value);
+    }
+
+    /// append a collection value of keyword members, `name` is empty for an additional value
+    fn push_collection(bytes: &mut Vec<u8>, name: &str, members: &[(&str, &str)]) {
+        push_field(bytes, ValueTag::BegCollection, name, b"");
+        for (member, value) in members {
+            push_field(bytes, ValueTag::MemberAttrName, "", member.as_bytes());
+            push_field(bytes, ValueTag::Keyword, "", value.as_bytes());
+        }
+        push_field(bytes, ValueTag::EndCollection, "", b"");
+    }
+
+    /// decode the attribute at the start of `bytes`, which must stop before 'copies'
+    fn decode_before_copies(mut bytes: Vec<u8>) -> Attribute {
+        let len = bytes.len();
+        push_field(
+            &mut bytes,
+            ValueTag::Integer,
+            "copies",
+            &1_i32.to_be_bytes(),
+        );
+
+        let (delta, attribute) = Attribute::from_ipp(&bytes, 0);
+        assert_eq!(delta, len);
+        attribute.unwrap()
+    }
+
+    fn keyword_members(value: &AttributeValue) -> Vec<(String, &str)> {
+        match value {
+            AttributeValue::Collection(members) => members
+                .iter()
+                .map(|member| {
+                    assert_eq!(member.tag, ValueTag::Keyword);
+                    assert_eq!(member.values.len(), 1);
+                    (member.name.to_string(), member.values[0].as_str().unwrap())
+                })
+                .collect(),
+            other => panic!("not a collection: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn single_collection_is_one_value() {
+        let mut bytes = Vec::new();
+        push_collection(&mut bytes, "media-col", &[("media-source", "main")]);
+
+        let attribute = decode_before_copies(bytes);
+        assert_eq!(attribute.tag, ValueTag::BegCollection);
+        assert_eq!(attribute.values.len(), 1);
+        assert_eq!(
+            keyword_members(&attribute.values[0]),
+            [("media-source".to_string(), "main")]
+        );
+    }
+
+    #[test]
+    fn additional_collection_is_a_second_value() {
+        let mut bytes = Vec::new();
+        push_collection(
+            &mut bytes,
+            "media-col-database",
+            &[("media-source", "main")],
+        );
+        push_collection(&mut bytes, "", &[("media-source", "manual")]);
+
+        let attribute = decode_before_copies(bytes);
+        assert_eq!(attribute.values.len(), 2);
+        assert_eq!(
+            keyword_members(&attribute.values[0]),
+            [("media-source".to_string(), "main")]
+        );
+        assert_eq!(
+            keyword_members(&attribute.values[1]),
+            [("media-source".to_string(), "manual")]
+        );
+    }
+
+    #[test]
+    fn members_stay_in_their_collection() {
+        let members = [
+            ("media-source", "main"),
+            ("media-type", "stationery"),
+            ("media-color", "white"),
+        ];
+        let mut bytes = Vec::new();
+        push_collection(&mut bytes, "media-col", &members);
+
+        let attribute = decode_before_copies(bytes);
+        assert_eq!(attribute.values.len(), 1);
+        let expected: Vec<_> = members
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        assert_eq!(keyword_members(&attribute.values[0]), expected);
+    }
+
+    #[test]
+    fn typed_accessors_reject_other_variants() {
+        let datetime = Utc.timestamp_millis_opt(1_600_000_000_100).unwrap();
+        let text_with_lang = TextWithLang::new("en", "office");
+        let values = [
+            AttributeValue::Number(3),
+            AttributeValue::Boolean(true),
+            AttributeValue::TextWithoutLang("text".into()),
+            AttributeValue::Keyword("keyword".into()),
+            AttributeValue::Uri("ipp://localhost/".into()),
+            AttributeValue::TextWithLang(text_with_lang.clone()),
+            AttributeValue::DateTime(datetime),
+            AttributeValue::Resolution(Resolution::new(600, 600, ResolutionUnits::DotsPerInch)),
+            AttributeValue::RangeOfInteger(RangeOfInteger::new(1, 5)),
+            AttributeValue::Collection(Vec::new()),
+        ];
+
+        for value in &values {
+            let expected_i32 = matches!(value, AttributeValue::Number(_)).then_some(3);
+            assert_eq!(value.as_i32(), expected_i32, "{:?}", value);
+
+            let expected_bool = matches!(value, AttributeValue::Boolean(_)).then_some(true);
+            assert_eq!(value.as_bool(), expected_bool, "{:?}", value);
+
+            let expected_str = match value {
+                AttributeValue::TextWithoutLang(_) => Some("text"),
+                AttributeValue::Keyword(_) => Some("keyword"),
+                AttributeValue::Uri(_) => Some("ipp://localhost/"),
+                _ => None,
+            };
+            assert_eq!(value.as_str(), expected_str, "{:?}", value);
+
+            let expected_text_with_lang =
+                matches!(value, AttributeValue::TextWithLang(_)).then_some(&text_with_lang);
+            assert_eq!(
+                value.as_text_with_lang(),
+                expected_text_with_lang,
+                "{:?}",
+                value
+            );
+
+            let expected_datetime =
+                matches!(value, AttributeValue::DateTime(_)).then_some(datetime);
+            assert_eq!(value.as_datetime(), expected_datetime, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn first_value_accessors() {
+        use crate::spec::attribute::{JobAttribute, PrinterAttribute};
+
+        let compression =
+            Attribute::keyword_set(PrinterAttribute::CompressionSupported, ["none", "gzip"]);
+        assert_eq!(compression.first_str(), Some("none"));
+        assert_eq!(compression.first_i32(), None);
+        assert_eq!(compression.first_bool(), None);
+
+        let job_id = Attribute::integer(JobAttribute::JobId, 2);
+        assert_eq!(job_id.first_i32(), Some(2));
+        assert_eq!(job_id.first_str(), None);
+        assert_eq!(job_id.first_datetime(), None);
+
+        let accepting = Attribute::boolean(PrinterAttribute::PrinterIsAcceptingJobs, false);
+        assert_eq!(accepting.first_bool(), Some(false));
+        assert_eq!(accepting.first_text_with_lang(), None);
+
+        let empty = Attribute {
+            values: Vec::new(),
+            ..job_id
+        };
+        assert_eq!(empty.first_i32(), None);
+    }
+
+    #[test]
+    fn enum_values_as_spec_enums() {
+        let mut bytes = Vec::new();
+        push_field(
+            &mut bytes,
+            ValueTag::Enum,
+            "printer-state",
+            &4_i32.to_be_bytes(),
+        );
+        let (_, printer_state) = Attribute::from_ipp(&bytes, 0);
+        let value = &printer_state.unwrap().values[0];
+        assert_eq!(value.as_printer_state(), Some(PrinterState::Processing));
+
+        // outside of 'printer-state', or not a number at all
+        assert_eq!(AttributeValue::Number(9).as_printer_state(), None);
+        assert_eq!(
+            AttributeValue::Keyword("processing".into()).as_printer_state(),
+            None
+        );
+
+        assert_eq!(
+            AttributeValue::Number(9).as_job_state(),
+            Some(JobState::Completed)
+        );
+        assert_eq!(
+            AttributeValue::Number(0x0b).as_operation_id(),
+            Some(OperationID::GetPrinterAttributes)
+        );
+        assert_eq!(
+            AttributeValue::Number(0x4001).as_operation_id(),
+            Some(OperationID::CupsGetDefault)
+        );
+        // operations of other vendors are not listed
+        assert_eq!(AttributeValue::Number(0x7fff).as_operation_id(), None);
+    }
+
+    #[test]
+    fn json_value_shape_of_each_variant() {
+        use crate::spec::attribute::JobTemplateAttribute;
+        use serde_json::json;
+
+        let shapes = [
+            (AttributeValue::Number(5), json!(5)),
+            (AttributeValue::Boolean(true), json!(true)),
+            (
+                AttributeValue::TextWithoutLang("text".into()),
+                json!("text"),
+            ),
+            (
+                AttributeValue::Keyword("one-sided".into()),
+                json!("one-sided"),
+            ),
+            (
+                AttributeValue::Uri("ipp://localhost/".into()),
+                json!("ipp://localhost/"),
+            ),
+            (
+                AttributeValue::MimeType("application/pdf".into()),
+                json!("application/pdf"),
+            ),
+            (AttributeValue::Charset("utf-8".into()), json!("utf-8")),
+            (AttributeValue::NaturalLanguage("en".into()), json!("en")),
+            (
+                AttributeValue::TextWithLang(TextWithLang::new("fr", "rapport")),
+                json!({"lang": "fr", "text": "rapport"}),
+            ),
+            (
+                AttributeValue::DateTime(Utc.timestamp_millis_opt(1_577_934_245_000).unwrap()),
+                json!("2020-01-02T03:04:05+00:00"),
+            ),
+            (
+                AttributeValue::Resolution(Resolution::new(600, 300, ResolutionUnits::DotsPerInch)),
+                json!("600x300dpi"),
+            ),
+            (
+                AttributeValue::RangeOfInteger(RangeOfInteger::new(1, 5)),
+                json!("1-5"),
+            ),
+            (
+                AttributeValue::Collection(vec![
+                    Attribute::keyword(
+                        AttributeName::Unsupported("media-type".into()),
+                        "stationery",
+                    ),
+                    Attribute::integer_set(JobTemplateAttribute::Copies, [1, 2]),
+                ]),
+                json!({"media-type": "stationery", "copies": [1, 2]}),
+            ),
+        ];
+        for (value, shape) in shapes {
+            assert_eq!(value.to_json_value(), shape, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn character_string_variants_keep_their_tag() {
+        use crate::spec::attribute::{JobTemplateAttribute, OperationAttribute};
+
+        let attributes = [
+            Attribute::keyword(JobTemplateAttribute::Sides, "two-sided-long-edge"),
+            Attribute::uri(OperationAttribute::PrinterUri, "ipp://localhost/"),
+            Attribute::mime_media_type(OperationAttribute::DocumentFormat, "application/pdf"),
+            Attribute::charset(OperationAttribute::AttributesCharset, "utf-8"),
+            Attribute::natural_language(OperationAttribute::AttributesNaturalLanguage, "en"),
+        ];
+        for attribute in attributes {
+            let value = &attribute.values[0];
+            assert_eq!(value.value_tag(), Some(attribute.tag));
+            assert_eq!(
+                AttributeValue::string(attribute.tag, value.as_str().unwrap()),
+                *value
+            );
+
+            let bytes = attribute.to_ipp();
+            let (_, decoded) = Attribute::from_ipp(&bytes, 0);
+            assert_eq!(decoded.as_ref(), Some(&attribute));
+
+            let json = serde_json::to_string(&attribute).unwrap();
+            assert_eq!(serde_json::from_str::<Attribute>(&json).unwrap(), attribute);
+        }
+
+        // a keyword is not the text it spells
+        let keyword = AttributeValue::Keyword("two-sided-long-edge".into());
+        assert_eq!(keyword.as_keyword(), Some("two-sided-long-edge"));
+        assert_ne!(
+            keyword,
+            AttributeValue::TextWithoutLang("two-sided-long-edge".into())
+        );
+        assert_eq!(
+            AttributeValue::string(ValueTag::NameWithoutLanguage, "office").value_tag(),
+            None
+        );
+    }
+
+    #[test]
+    fn too_long_value_fails_to_encode() {
+        let value = Attribute::text(
+            crate::spec::attribute::PrinterAttribute::PrinterInfo,
+            "x".repeat(0x10000),
+        );
+        assert!(value.to_writer(&mut Vec::new()).is_err());
+
+        let fits = Attribute::text(
+            crate::spec::attribute::PrinterAttribute::PrinterInfo,
+            "x".repeat(0xffff),
+        );
+        assert_eq!(fits.to_writer(&mut Vec::new()).unwrap(), fits.ipp_len());
+    }
+}
@@ -0,0 +1,76 @@
+//! `#[serde(with)]` module for [`Operation::data`](super::Operation::data),
+//! enabled by the `base64` feature
+//!
+//! Serialized as `{ "length": <bytes>, "base64": "<standard base64>" }`
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+struct Base64Data {
+    length: usize,
+    base64: String,
+}
+
+pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    Base64Data {
+        length: data.len(),
+        base64: base64::encode(data),
+    }
+    .serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = Base64Data::deserialize(deserializer)?;
+    let data = base64::decode(&encoded.base64).map_err(D::Error::custom)?;
+
+    if data.len() != encoded.length {
+        return Err(D::Error::custom(format!(
+            "data length mismatch: expected {}, decoded {}",
+            encoded.length,
+            data.len()
+        )));
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        encoder::{IppVersion, Operation},
+        spec::operation::OperationID,
+    };
+
+    fn print_job(data: Vec<u8>) -> Operation {
+        let mut operation = Operation::new_request(IppVersion::V1_1, OperationID::PrintJob, 1);
+        operation.set_attributes_charset("utf-8");
+        operation.set_attributes_natural_language("en");
+        operation.data = data;
+        operation
+    }
+
+    #[test]
+    fn data_round_trips() {
+        // every byte value, 4 KiB of them
+        let data: Vec<u8> = (0..4096).map(|i| (i * 7 % 256) as u8).collect();
+        let operation = print_job(data.clone());
+
+        let json = operation.to_json().unwrap();
+        assert!(json.contains("\"length\":4096"));
+        assert_eq!(Operation::from_json(&json).unwrap().data, data);
+
+        // no data at all round-trips too
+        let empty = print_job(Vec::new());
+        assert_eq!(
+            Operation::from_json(&empty.to_json().unwrap()).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn length_mismatch_is_refused() {
+        let json = print_job(vec![1, 2, 3]).to_json().unwrap();
+        let json = json.replace("\"length\":3", "\"length\":4");
+        assert!(Operation::from_json(&json).is_err());
+    }
+}
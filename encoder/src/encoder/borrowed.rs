@@ -0,0 +1,486 @@
+use crate::spec::tag::{DelimiterTag, ValueTag};
+
+use super::{
+    attribute::decode_fields,
+    operation::{check_bounds, read_header},
+    reader::ByteReader,
+    Attribute, AttributeGroup, AttributeName, AttributeValue, IppEncode, IppError, IppVersion,
+    Operation, RangeOfInteger, Resolution, TextWithLang,
+};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// Operation borrowing its text values and document data from the decoded bytes
+///
+/// Produced by [`Operation::parse`] or [`OperationRef::from_ipp`]. Unlike [`Operation`],
+/// attributes are kept in wire order and names are left as the raw strings found on the wire.
+/// Use [`OperationRef::into_owned`] to get an [`Operation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationRef<'a> {
+    pub version: IppVersion,
+    pub operation_id_or_status_code: u16,
+    pub request_id: u32,
+    /// attribute groups in wire order, a tag may repeat (e.g. job-attributes in Get-Jobs)
+    pub attribute_groups: Vec<AttributeGroupRef<'a>>,
+    /// additional data in trailing bytes
+    pub data: &'a [u8],
+}
+
+/// Borrowed counterpart of [`AttributeGroup`], attributes in wire order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeGroupRef<'a> {
+    pub tag: DelimiterTag,
+    pub attributes: Vec<AttributeRef<'a>>,
+}
+
+/// Borrowed counterpart of [`Attribute`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeRef<'a> {
+    pub tag: ValueTag,
+    pub name: &'a str,
+    pub values: Vec<AttributeValueRef<'a>>,
+}
+
+/// Borrowed counterpart of [`AttributeValue`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeValueRef<'a> {
+    TextWithoutLang(&'a str),
+    Keyword(&'a str),
+    Uri(&'a str),
+    MimeType(&'a str),
+    Charset(&'a str),
+    NaturalLanguage(&'a str),
+    Number(i32),
+    Boolean(bool),
+    TextWithLang { lang: &'a str, text: &'a str },
+    Resolution(Resolution),
+    RangeOfInteger(RangeOfInteger),
+    DateTime(DateTime<Utc>),
+    Collection(Vec<AttributeRef<'a>>),
+}
+
+impl Operation {
+    /// Decode `bytes` without copying text values or the trailing document data
+    ///
+    /// Same wire handling as [`IppEncode::from_ipp`], see [`OperationRef`].
+    /// Panics on malformed input, use [`Operation::try_parse`] for bytes off the wire.
+    pub fn parse(bytes: &[u8]) -> OperationRef<'_> {
+        OperationRef::parse(bytes)
+    }
+
+    /// Fallible counterpart of [`Operation::parse`], never panics
+    ///
+    /// `bytes` is checked the same way as in [`Operation::try_from_ipp`], so a name or value
+    /// whose length runs past the end of `bytes` is [`IppError::Truncated`].
+    pub fn try_parse(bytes: &[u8]) -> Result<OperationRef<'_>, IppError> {
+        OperationRef::from_ipp(bytes)
+    }
+}
+
+impl<'a> OperationRef<'a> {
+    /// Decode `bytes` borrowing text values and document data, never panics
+    ///
+    /// Borrowed counterpart of [`IppDecode::from_ipp`](super::IppDecode::from_ipp) for
+    /// an [`Operation`] starting at the beginning of `bytes`.
+    pub fn from_ipp(bytes: &'a [u8]) -> Result<Self, IppError> {
+        check_bounds(bytes)?;
+
+        let (operation, terminated) = Self::decode(bytes);
+        if !terminated {
+            return Err(IppError::MissingEndOfAttributes);
+        }
+
+        Ok(operation)
+    }
+
+    /// Panicking counterpart of [`OperationRef::from_ipp`], see [`Operation::parse`]
+    pub fn parse(bytes: &'a [u8]) -> Self {
+        Self::decode(bytes).0
+    }
+
+    /// [`OperationRef::parse`], also telling whether the attribute groups ended
+    /// on an end-of-attributes tag
+    ///
+    /// The owned decoder goes through here too, see [`Operation::from_ipp`](IppEncode::from_ipp).
+    pub(crate) fn decode(bytes: &'a [u8]) -> (Self, bool) {
+        // version-number, operation-id or status-code and request-id
+        // a short header panics like any other malformed input here, the fallible
+        // entry points go through `check_bounds` before decoding
+        let mut reader = ByteReader::new(bytes, 0);
+        let (version, operation_id_or_status_code, request_id) = read_header(&mut reader).unwrap();
+        let offset = reader.position();
+
+        let (delta, attribute_groups, terminated) =
+            AttributeGroupRef::groups_from_ipp(bytes, offset);
+
+        (
+            Self {
+                version,
+                operation_id_or_status_code,
+                request_id,
+                attribute_groups,
+                data: &bytes[offset + delta..],
+            },
+            terminated,
+        )
+    }
+
+    /// Attributes of the first operation-attributes group in wire order, empty without one
+    ///
+    /// Unlike [`Operation::operation_attributes_ordered`], names repeated in the group are
+    /// still there, each with the values it came with.
+    pub fn operation_attributes_ordered(&self) -> &[AttributeRef<'a>] {
+        self.attribute_groups
+            .iter()
+            .find(|group| group.tag == DelimiterTag::OperationAttributes)
+            .map_or(&[], |group| &group.attributes)
+    }
+
+    /// Convert into an owned [`Operation`], copying the borrowed names and values
+    pub fn into_owned(self) -> Operation {
+        self.to_owned()
+    }
+
+    /// Copy into an owned [`Operation`]
+    pub fn to_owned(&self) -> Operation {
+        Operation {
+            version: self.version,
+            operation_id_or_status_code: self.operation_id_or_status_code,
+            request_id: self.request_id,
+            attribute_groups: self
+                .attribute_groups
+                .iter()
+                .map(AttributeGroupRef::to_owned)
+                .collect(),
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl<'a> AttributeGroupRef<'a> {
+    /// Decode attribute groups from `offset`, also telling whether decoding stopped
+    /// on an end-of-attributes tag rather than on malformed or missing bytes
+    pub(crate) fn groups_from_ipp(bytes: &'a [u8], offset: usize) -> (usize, Vec<Self>, bool) {
+        let mut groups: Vec<Self> = Vec::new();
+        let mut shifting_offset = offset;
+
+        loop {
+            // a delimiter tag is expected here, anything else leaves the groups unterminated
+            let raw_tag = match bytes.get(shifting_offset) {
+                Some(&raw_tag) if DelimiterTag::is_delimiter(raw_tag) => raw_tag,
+                _ => return (shifting_offset - offset, groups, false),
+            };
+            shifting_offset += 1;
+
+            // a reserved delimiter tag is None, see [`DelimiterTag::is_delimiter`]
+            let tag = DelimiterTag::from_repr(raw_tag as usize);
+            if tag == Some(DelimiterTag::EndOfAttributes) {
+                return (shifting_offset - offset, groups, true);
+            }
+
+            // read attributes in group up to the next delimiter tag,
+            // skipped bytes count even when no attribute follows
+            let mut attributes: Vec<AttributeRef<'a>> = Vec::new();
+            while !Attribute::at_group_boundary(bytes, shifting_offset) {
+                let (delta, attribute) = AttributeRef::from_ipp(bytes, shifting_offset);
+                shifting_offset += delta;
+                attributes.extend(attribute);
+            }
+
+            // groups are kept in wire order, the same tag may appear more than once
+            // (for example one job-attributes group per job in a Get-Jobs response);
+            // the attributes of a reserved group are dropped
+            if let Some(tag) = tag {
+                groups.push(Self { tag, attributes });
+            }
+        }
+    }
+
+    /// First attribute with the given name
+    pub fn attribute(&self, name: &str) -> Option<&AttributeRef<'a>> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    pub fn to_owned(&self) -> AttributeGroup {
+        AttributeGroup {
+            tag: self.tag,
+            attributes: self
+                .attributes
+                .iter()
+                .map(|attribute| {
+                    let attribute = attribute.to_owned();
+                    (attribute.name.clone(), attribute)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> AttributeRef<'a> {
+    /// Borrowed counterpart of [`Attribute::from_ipp`]
+    pub fn from_ipp(bytes: &'a [u8], offset: usize) -> (usize, Option<Self>) {
+        let (len, fields) = decode_fields(bytes, offset, Self::decode_one);
+        (
+            len,
+            fields.map(|(tag, name, values)| Self { tag, name, values }),
+        )
+    }
+
+    /// one value with its tag and name, None for the zero-length name of an additional value
+    fn decode_one(
+        bytes: &'a [u8],
+        offset: usize,
+    ) -> (usize, (ValueTag, Option<&'a str>, AttributeValueRef<'a>)) {
+        let value_tag = ValueTag::from_wire(bytes[offset]).unwrap();
+        let mut shifting_offset = offset + 1;
+
+        let (delta, name) = str_from_ipp(bytes, shifting_offset);
+        shifting_offset += delta;
+
+        let (delta, value) = AttributeValueRef::from_ipp(bytes, shifting_offset, value_tag);
+        shifting_offset += delta;
+
+        let name = (!name.is_empty()).then_some(name);
+        (shifting_offset - offset, (value_tag, name, value))
+    }
+
+    pub fn to_owned(&self) -> Attribute {
+        Attribute {
+            tag: self.tag,
+            name: AttributeName::from_str(self.name).unwrap(),
+            values: self
+                .values
+                .iter()
+                .map(AttributeValueRef::to_owned)
+                .collect(),
+        }
+    }
+}
+
+impl<'a> AttributeValueRef<'a> {
+    pub fn from_ipp(bytes: &'a [u8], offset: usize, value_tag: ValueTag) -> (usize, Self) {
+        match value_tag {
+            ValueTag::Integer | ValueTag::Enum => {
+                let (delta, raw_value) = i32::from_ipp(bytes, offset);
+                (delta, Self::Number(raw_value))
+            }
+            ValueTag::Boolean => {
+                let (delta, raw_value) = bool::from_ipp(bytes, offset);
+                (delta, Self::Boolean(raw_value))
+            }
+            ValueTag::TextWithLanguage | ValueTag::NameWithLanguage => {
+                // value-length, then lang and text each with their own length
+                let lang_offset = offset + 2;
+                let (lang_len, lang) = str_from_ipp(bytes, lang_offset);
+                let (text_len, text) = str_from_ipp(bytes, lang_offset + lang_len);
+                (2 + lang_len + text_len, Self::TextWithLang { lang, text })
+            }
+            ValueTag::Resolution => {
+                let (delta, raw_value) = Resolution::from_ipp(bytes, offset);
+                (delta, Self::Resolution(raw_value))
+            }
+            ValueTag::RangeOfInteger => {
+                let (delta, raw_value) = RangeOfInteger::from_ipp(bytes, offset);
+                (delta, Self::RangeOfInteger(raw_value))
+            }
+            ValueTag::DateTime => {
+                let (delta, raw_value) = DateTime::from_ipp(bytes, offset);
+                (delta, Self::DateTime(raw_value))
+            }
+            ValueTag::BegCollection => {
+                let (delta, members) = Self::collection_from_ipp(bytes, offset);
+                (delta, Self::Collection(members))
+            }
+            tag if tag.is_out_of_band() => {
+                // out-of-band: there is no value, whatever value-length says is skipped,
+                // as in [`AttributeValue::from_ipp`]
+                let value_length = u16::from_be_bytes([bytes[offset], bytes[offset + 1]]);
+                (2 + value_length as usize, Self::str(value_tag, ""))
+            }
+            _ => {
+                let (delta, raw_value) = str_from_ipp(bytes, offset);
+                (delta, Self::str(value_tag, raw_value))
+            }
+        }
+    }
+
+    /// see [`AttributeValue::string`]
+    fn str(tag: ValueTag, value: &'a str) -> Self {
+        match tag {
+            ValueTag::Keyword => Self::Keyword(value),
+            ValueTag::Uri => Self::Uri(value),
+            ValueTag::MimeMediaType => Self::MimeType(value),
+            ValueTag::Charset => Self::Charset(value),
+            ValueTag::NaturalLanguage => Self::NaturalLanguage(value),
+            _ => Self::TextWithoutLang(value),
+        }
+    }
+
+    pub fn to_owned(&self) -> AttributeValue {
+        match self {
+            Self::TextWithoutLang(text) => AttributeValue::TextWithoutLang(String::from(*text)),
+            Self::Keyword(keyword) => AttributeValue::Keyword(String::from(*keyword)),
+            Self::Uri(uri) => AttributeValue::Uri(String::from(*uri)),
+            Self::MimeType(mime_type) => AttributeValue::MimeType(String::from(*mime_type)),
+            Self::Charset(charset) => AttributeValue::Charset(String::from(*charset)),
+            Self::NaturalLanguage(natural_language) => {
+                AttributeValue::NaturalLanguage(String::from(*natural_language))
+            }
+            Self::Number(number) => AttributeValue::Number(*number),
+            Self::Boolean(boolean) => AttributeValue::Boolean(*boolean),
+            Self::TextWithLang { lang, text } => AttributeValue::TextWithLang(TextWithLang {
+                lang: String::from(*lang),
+                text: String::from(*text),
+            }),
+            Self::Resolution(resolution) => AttributeValue::Resolution(*resolution),
+            Self::RangeOfInteger(range) => AttributeValue::RangeOfInteger(*range),
+            Self::DateTime(datetime) => AttributeValue::DateTime(*datetime),
+            Self::Collection(members) => {
+                AttributeValue::Collection(members.iter().map(AttributeRef::to_owned).collect())
+            }
+        }
+    }
+
+    /// see [`AttributeValue::Collection`], `offset` points at the value-length of begCollection
+    fn collection_from_ipp(bytes: &'a [u8], offset: usize) -> (usize, Vec<AttributeRef<'a>>) {
+        // begCollection has no value, skip its value-length
+        let (mut shifting_offset, _) = str_from_ipp(bytes, offset);
+        shifting_offset += offset;
+
+        let mut members: Vec<AttributeRef<'a>> = Vec::new();
+
+        while shifting_offset < bytes.len() {
+            let tag = match ValueTag::from_wire(bytes[shifting_offset]) {
+                Some(tag) => tag,
+                // not a value-tag, the collection is malformed
+                None => break,
+            };
+            shifting_offset += 1;
+
+            // every member field has an empty name
+            let (delta, _) = str_from_ipp(bytes, shifting_offset);
+            shifting_offset += delta;
+
+            match tag {
+                ValueTag::EndCollection => {
+                    let (delta, _) = str_from_ipp(bytes, shifting_offset);
+                    shifting_offset += delta;
+                    break;
+                }
+                ValueTag::MemberAttrName => {
+                    let (delta, name) = str_from_ipp(bytes, shifting_offset);
+                    shifting_offset += delta;
+
+                    members.push(AttributeRef {
+                        // takes the tag of its first value
+                        tag: ValueTag::NoValue,
+                        name,
+                        values: Vec::new(),
+                    });
+                }
+                _ => {
+                    let (delta, value) = Self::from_ipp(bytes, shifting_offset, tag);
+                    shifting_offset += delta;
+
+                    if let Some(member) = members.last_mut() {
+                        if member.values.is_empty() {
+                            member.tag = tag;
+                        }
+                        member.values.push(value);
+                    }
+                }
+            }
+        }
+
+        (shifting_offset - offset, members)
+    }
+}
+
+/// borrow a length-prefixed string, returning the bytes consumed including the 2-byte length
+fn str_from_ipp(bytes: &[u8], offset: usize) -> (usize, &str) {
+    let len = u16::from_be_bytes([bytes[offset], bytes[offset + 1]]) as usize;
+    let value = std::str::from_utf8(&bytes[offset + 2..offset + 2 + len]).unwrap();
+
+    (2 + len, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::CompatQuirks;
+    use crate::spec::{attribute::OperationAttribute, tag::DelimiterTag};
+
+    fn push_attribute(bytes: &mut Vec<u8>, tag: u8, name: &str, value: &[u8]) {
+        bytes.push(tag);
+        bytes.extend((name.len() as u16).to_be_bytes());
+        bytes.extend(name.as_bytes());
+        bytes.extend((value.len() as u16).to_be_bytes());
+        bytes.extend(value);
+    }
+
+    /// Get-Printer-Attributes request with an 'unknown' out-of-band 'printer-uri'
+    /// whose value-length is not 0
+    fn out_of_band_request(value: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![1, 1, 0x00, 0x0b, 0, 0, 0, 1, 0x01];
+        push_attribute(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_attribute(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        push_attribute(&mut bytes, 0x12, "printer-uri", value);
+        bytes.push(0x03);
+        bytes
+    }
+
+    #[test]
+    fn out_of_band_value_is_skipped() {
+        for value in [&[0xe8, 0x01][..], b"ipp://localhost"] {
+            let bytes = out_of_band_request(value);
+
+            let owned = Operation::try_from_ipp(&bytes).unwrap();
+            let borrowed = Operation::try_parse(&bytes).unwrap().into_owned();
+            let (quirked, _) =
+                Operation::try_from_ipp_with_quirks(&bytes, &CompatQuirks::default()).unwrap();
+
+            let uri = owned
+                .attribute(
+                    DelimiterTag::OperationAttributes,
+                    OperationAttribute::PrinterUri,
+                )
+                .unwrap();
+            assert_eq!(uri.tag, ValueTag::Unknown);
+            assert_eq!(uri.values, [AttributeValue::TextWithoutLang(String::new())]);
+            assert_eq!(borrowed, owned);
+            assert_eq!(quirked, owned);
+        }
+    }
+
+    #[test]
+    fn name_length_past_the_end_is_truncated() {
+        // 'attributes-charset' announced with a name-length of 0xffff
+        let mut bytes = vec![1, 1, 0x00, 0x0b, 0, 0, 0, 1, 0x01, 0x47];
+        bytes.extend(0xffff_u16.to_be_bytes());
+        bytes.extend(b"attributes-charset");
+
+        let truncated = |result: Result<(), IppError>| match result {
+            Err(IppError::Truncated {
+                offset,
+                needed,
+                available,
+            }) => (offset, needed, available),
+            other => panic!("not truncated: {:?}", other),
+        };
+        // the field at offset 9 needs its name-length and name past its value-tag
+        let expected = (9, 2 + 0xffff, bytes.len() - 10);
+
+        assert_eq!(
+            truncated(Operation::try_from_ipp(&bytes).map(drop)),
+            expected
+        );
+        assert_eq!(truncated(Operation::try_parse(&bytes).map(drop)), expected);
+        assert_eq!(
+            truncated(OperationRef::from_ipp(&bytes).map(drop)),
+            expected
+        );
+        assert!(Operation::parse_header(&bytes).is_err());
+    }
+}
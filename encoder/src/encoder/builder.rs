@@ -0,0 +1,249 @@
+use crate::spec::{
+    attribute::OperationAttribute,
+    operation::{OperationID, StatusCode},
+    tag::DelimiterTag,
+};
+
+use super::{Attribute, AttributeGroup, BuildError, IppVersion, Operation};
+
+/// Builder for request operations, checked by [`OperationBuilder::build`]
+///
+/// ```rust
+/// use ipp_encoder::prelude::*;
+///
+/// let request = OperationBuilder::request(OperationID::GetPrinterAttributes)
+///     .request_id(1)
+///     .charset("utf-8")
+///     .natural_language("en-US")
+///     .operation_attribute(Attribute::uri(
+///         AttributeName::Operation(OperationAttribute::PrinterUri),
+///         "ipp://localhost:631/printers/ipp",
+///     ))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperationBuilder {
+    operation: Operation,
+}
+
+/// Builder for response operations, see [`OperationBuilder`]
+///
+/// Unlike requests, a response may carry request-id 0,
+/// used when the request-id of the request could not be read.
+#[derive(Debug, Clone)]
+pub struct ResponseBuilder {
+    operation: Operation,
+}
+
+impl OperationBuilder {
+    /// Request with version 1.1 and request-id 1
+    pub fn request(operation_id: OperationID) -> Self {
+        Self {
+            operation: Operation::new_request(IppVersion::V1_1, operation_id, 1),
+        }
+    }
+
+    pub fn version(mut self, version: IppVersion) -> Self {
+        self.operation.version = version;
+        self
+    }
+
+    pub fn request_id(mut self, request_id: u32) -> Self {
+        self.operation.request_id = request_id;
+        self
+    }
+
+    pub fn charset(mut self, charset: &str) -> Self {
+        self.operation.set_attributes_charset(charset);
+        self
+    }
+
+    pub fn natural_language(mut self, natural_language: &str) -> Self {
+        self.operation
+            .set_attributes_natural_language(natural_language);
+        self
+    }
+
+    pub fn operation_attribute(mut self, attribute: Attribute) -> Self {
+        insert(
+            &mut self.operation,
+            DelimiterTag::OperationAttributes,
+            attribute,
+        );
+        self
+    }
+
+    pub fn job_attribute(mut self, attribute: Attribute) -> Self {
+        insert(&mut self.operation, DelimiterTag::JobAttributes, attribute);
+        self
+    }
+
+    pub fn printer_attribute(mut self, attribute: Attribute) -> Self {
+        insert(
+            &mut self.operation,
+            DelimiterTag::PrinterAttributes,
+            attribute,
+        );
+        self
+    }
+
+    /// Append a whole group, merged into the operation-attributes group if it is one
+    pub fn group(mut self, group: AttributeGroup) -> Self {
+        append(&mut self.operation, group);
+        self
+    }
+
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.operation.data = data;
+        self
+    }
+
+    pub fn build(self) -> Result<Operation, BuildError> {
+        if self.operation.request_id == 0 {
+            return Err(BuildError::ZeroRequestId);
+        }
+        check_operation_attributes(&self.operation)?;
+
+        Ok(self.operation)
+    }
+}
+
+impl ResponseBuilder {
+    /// Response with version 1.1 and request-id 0
+    pub fn new(status_code: StatusCode) -> Self {
+        Self {
+            operation: Operation::new_response(IppVersion::V1_1, status_code, 0),
+        }
+    }
+
+    /// Response echoing `request`, see [`Operation::response_to`]
+    pub fn to(request: &Operation, status_code: StatusCode) -> Self {
+        Self {
+            operation: Operation::response_to(request, status_code),
+        }
+    }
+
+    pub fn version(mut self, version: IppVersion) -> Self {
+        self.operation.version = version;
+        self
+    }
+
+    pub fn request_id(mut self, request_id: u32) -> Self {
+        self.operation.request_id = request_id;
+        self
+    }
+
+    pub fn charset(mut self, charset: &str) -> Self {
+        self.operation.set_attributes_charset(charset);
+        self
+    }
+
+    pub fn natural_language(mut self, natural_language: &str) -> Self {
+        self.operation
+            .set_attributes_natural_language(natural_language);
+        self
+    }
+
+    pub fn operation_attribute(mut self, attribute: Attribute) -> Self {
+        insert(
+            &mut self.operation,
+            DelimiterTag::OperationAttributes,
+            attribute,
+        );
+        self
+    }
+
+    pub fn unsupported_attribute(mut self, attribute: Attribute) -> Self {
+        insert(
+            &mut self.operation,
+            DelimiterTag::UnsupportedAttributes,
+            attribute,
+        );
+        self
+    }
+
+    pub fn job_attribute(mut self, attribute: Attribute) -> Self {
+        insert(&mut self.operation, DelimiterTag::JobAttributes, attribute);
+        self
+    }
+
+    pub fn printer_attribute(mut self, attribute: Attribute) -> Self {
+        insert(
+            &mut self.operation,
+            DelimiterTag::PrinterAttributes,
+            attribute,
+        );
+        self
+    }
+
+    /// Append a whole group, merged into the operation-attributes group if it is one
+    ///
+    /// Other groups are appended even when their tag is already present,
+    /// e.g. one job-attributes group per job in a Get-Jobs response.
+    pub fn group(mut self, group: AttributeGroup) -> Self {
+        append(&mut self.operation, group);
+        self
+    }
+
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.operation.data = data;
+        self
+    }
+
+    pub fn build(self) -> Result<Operation, BuildError> {
+        check_operation_attributes(&self.operation)?;
+
+        Ok(self.operation)
+    }
+}
+
+/// insert into the last group with `tag`, appending the group if there is none
+fn insert(operation: &mut Operation, tag: DelimiterTag, attribute: Attribute) {
+    let group = match tag {
+        DelimiterTag::OperationAttributes => operation.operation_attributes_mut(),
+        _ => {
+            if operation.attribute_groups.last().map(|group| group.tag) != Some(tag) {
+                operation.attribute_groups.push(AttributeGroup {
+                    tag,
+                    attributes: Default::default(),
+                });
+            }
+            operation.attribute_groups.last_mut().unwrap()
+        }
+    };
+
+    group.insert(attribute);
+}
+
+fn append(operation: &mut Operation, group: AttributeGroup) {
+    if group.tag == DelimiterTag::OperationAttributes {
+        operation
+            .operation_attributes_mut()
+            .attributes
+            .extend(group.attributes);
+    } else {
+        operation.attribute_groups.push(group);
+    }
+}
+
+/// the operation-attributes group comes first and has charset and natural-language,
+/// which are encoded ahead of the other attributes
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.4)
+fn check_operation_attributes(operation: &Operation) -> Result<(), BuildError> {
+    let has = |name: OperationAttribute| {
+        operation
+            .attribute(DelimiterTag::OperationAttributes, name)
+            .is_some()
+    };
+
+    if !has(OperationAttribute::AttributesCharset) {
+        return Err(BuildError::MissingCharset);
+    }
+    if !has(OperationAttribute::AttributesNaturalLanguage) {
+        return Err(BuildError::MissingNaturalLanguage);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use super::{AttributeName, Operation, OperationRef};
+use crate::spec::attribute::OperationAttribute;
+use std::str::FromStr;
+
+/// Opt-in leniency for non-conforming clients, used by [`Operation::from_ipp_with_quirks`]
+///
+/// Defaults to strict decoding, every quirk disabled.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CompatQuirks {
+    /// Lowercase attribute names before resolving them,
+    /// so `Printer-Uri` resolves to `printer-uri`.
+    ///
+    /// RFC 8011 defines attribute names as case-sensitive, see
+    /// [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.1.4)
+    pub case_insensitive_names: bool,
+    /// Accept an operation-attributes group that has 'attributes-charset' but not first,
+    /// [`Operation::try_from_ipp_with_quirks`] otherwise fails with
+    /// [`IppError::CharsetNotFirst`](super::IppError::CharsetNotFirst).
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.4)
+    pub charset_not_first: bool,
+    /// Take a value whose name repeats the name of the attribute right before it
+    /// (with the same value-tag) as an additional value of that attribute,
+    /// as some printers send every value of a 1setOf with its full name.
+    ///
+    /// Additional values have a zero-length name, see
+    /// [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.1.5);
+    /// otherwise the repeated attribute replaces the previous one and only its last value is kept.
+    pub repeated_names_as_values: bool,
+}
+
+/// Irregularity found while decoding that did not prevent decoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeWarning {
+    /// attribute name with uppercase characters, as spelled on the wire
+    MixedCaseName(String),
+    /// 'attributes-charset' is not the first operation attribute
+    CharsetNotFirst,
+    /// attribute name repeated on a value that should have been an additional value
+    RepeatedName(String),
+}
+
+/// Warnings collected by [`Operation::from_ipp_with_quirks`]
+#[derive(Debug, Default, Clone)]
+pub struct DecodeReport {
+    pub warnings: Vec<DecodeWarning>,
+}
+
+impl CompatQuirks {
+    pub(crate) fn apply(&self, operation: &mut Operation) -> DecodeReport {
+        let mut report = DecodeReport::default();
+
+        for group in &mut operation.attribute_groups {
+            let attributes = std::mem::take(&mut group.attributes);
+
+            for mut attribute in attributes.into_values() {
+                if let AttributeName::Unsupported(raw_name) = &attribute.name {
+                    if raw_name.chars().any(|c| c.is_ascii_uppercase()) {
+                        report
+                            .warnings
+                            .push(DecodeWarning::MixedCaseName(raw_name.clone()));
+
+                        if self.case_insensitive_names {
+                            attribute.name =
+                                AttributeName::from_str(&raw_name.to_ascii_lowercase()).unwrap();
+                        }
+                    }
+                }
+
+                group.insert(attribute);
+            }
+        }
+
+        report
+    }
+}
+
+/// names repeated on consecutive values of `operation`, merged into additional values
+/// of the attribute before them if `merge` is set
+pub(crate) fn repeated_names(operation: &mut OperationRef, merge: bool) -> Vec<String> {
+    let mut repeated: Vec<String> = Vec::new();
+
+    for group in &mut operation.attribute_groups {
+        let attributes = std::mem::take(&mut group.attributes);
+
+        for attribute in attributes {
+            match group.attributes.last_mut() {
+                Some(previous)
+                    if previous.name == attribute.name && previous.tag == attribute.tag =>
+                {
+                    if !repeated.iter().any(|name| name == attribute.name) {
+                        repeated.push(String::from(attribute.name));
+                    }
+                    if merge {
+                        previous.values.extend(attribute.values);
+                    } else {
+                        group.attributes.push(attribute);
+                    }
+                }
+                _ => group.attributes.push(attribute),
+            }
+        }
+    }
+
+    repeated
+}
+
+/// whether the operation-attributes group of `operation` has 'attributes-charset'
+/// anywhere but first, as some clients send it
+pub(crate) fn is_charset_not_first(operation: &OperationRef) -> bool {
+    let charset = OperationAttribute::AttributesCharset.to_string();
+    let attributes = operation.operation_attributes_ordered();
+
+    attributes.iter().any(|attribute| attribute.name == charset) && attributes[0].name != charset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::IppError;
+    use crate::spec::{attribute::PrinterAttribute, tag::DelimiterTag};
+
+    fn push_field(bytes: &mut Vec<u8>, tag: u8, name: &str, value: &[u8]) {
+        bytes.push(tag);
+        bytes.extend((name.len() as u16).to_be_bytes());
+        bytes.extend(name.as_bytes());
+        bytes.extend((value.len() as u16).to_be_bytes());
+        bytes.extend(value);
+    }
+
+    /// a Get-Printer-Attributes request up to its operation attributes,
+    /// with 'attributes-charset' first unless `charset_not_first`
+    fn request(charset_not_first: bool) -> Vec<u8> {
+        let mut bytes = vec![1, 1, 0x00, 0x0b, 0, 0, 0, 1];
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        if !charset_not_first {
+            push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        }
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        if charset_not_first {
+            push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        }
+        bytes
+    }
+
+    fn strict() -> CompatQuirks {
+        CompatQuirks::default()
+    }
+
+    #[test]
+    fn case_insensitive_names() {
+        let mut bytes = request(false);
+        push_field(&mut bytes, 0x45, "Printer-Uri", b"ipp://localhost");
+        push_field(&mut bytes, 0x44, "REQUESTED-ATTRIBUTES", b"printer-name");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let warnings = [
+            DecodeWarning::MixedCaseName("Printer-Uri".to_string()),
+            DecodeWarning::MixedCaseName("REQUESTED-ATTRIBUTES".to_string()),
+        ];
+        let (operation, report) = Operation::try_from_ipp_with_quirks(&bytes, &strict()).unwrap();
+        assert_eq!(report.warnings, warnings);
+        assert_eq!(operation.printer_uri(), None);
+        assert!(operation.requested_attributes().is_empty());
+
+        let lenient = CompatQuirks {
+            case_insensitive_names: true,
+            ..strict()
+        };
+        let (operation, report) = Operation::try_from_ipp_with_quirks(&bytes, &lenient).unwrap();
+        assert_eq!(report.warnings, warnings);
+        assert_eq!(operation.printer_uri(), Some("ipp://localhost"));
+        assert_eq!(operation.requested_attributes(), ["printer-name"]);
+    }
+
+    #[test]
+    fn charset_not_first() {
+        let mut bytes = request(true);
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        assert!(matches!(
+            Operation::try_from_ipp_with_quirks(&bytes, &strict()),
+            Err(IppError::CharsetNotFirst)
+        ));
+
+        let lenient = CompatQuirks {
+            charset_not_first: true,
+            ..strict()
+        };
+        let (operation, report) = Operation::try_from_ipp_with_quirks(&bytes, &lenient).unwrap();
+        assert_eq!(report.warnings, [DecodeWarning::CharsetNotFirst]);
+        assert_eq!(operation.attributes_charset(), Some("utf-8"));
+    }
+
+    #[test]
+    fn repeated_names_as_values() {
+        let mut bytes = request(false);
+        bytes.push(DelimiterTag::PrinterAttributes as u8);
+        for format in ["application/pdf", "image/pwg-raster", "image/jpeg"] {
+            push_field(
+                &mut bytes,
+                0x49,
+                "document-format-supported",
+                format.as_bytes(),
+            );
+        }
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let formats = |operation: &Operation| {
+            operation
+                .attribute(
+                    DelimiterTag::PrinterAttributes,
+                    PrinterAttribute::DocumentFormatSupported,
+                )
+                .unwrap()
+                .as_strings()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        let warnings = [DecodeWarning::RepeatedName(
+            "document-format-supported".to_string(),
+        )];
+
+        // the last repetition replaces the ones before it
+        let (operation, report) = Operation::try_from_ipp_with_quirks(&bytes, &strict()).unwrap();
+        assert_eq!(report.warnings, warnings);
+        assert_eq!(formats(&operation), ["image/jpeg"]);
+        let (_, panicking, _) = Operation::from_ipp_with_quirks(&bytes, 0, &strict());
+        assert_eq!(panicking, operation);
+
+        let lenient = CompatQuirks {
+            repeated_names_as_values: true,
+            ..strict()
+        };
+        let (operation, report) = Operation::try_from_ipp_with_quirks(&bytes, &lenient).unwrap();
+        assert_eq!(report.warnings, warnings);
+        assert_eq!(
+            formats(&operation),
+            ["application/pdf", "image/pwg-raster", "image/jpeg"]
+        );
+        let (_, panicking, report) = Operation::from_ipp_with_quirks(&bytes, 0, &lenient);
+        assert_eq!(report.warnings, warnings);
+        assert_eq!(panicking, operation);
+    }
+
+    #[test]
+    fn repeated_names_among_additional_values() {
+        // 'document-format-supported' 1setOf repeating its name on some of its values
+        let mut bytes = request(false);
+        bytes.push(DelimiterTag::PrinterAttributes as u8);
+        push_field(
+            &mut bytes,
+            0x49,
+            "document-format-supported",
+            b"application/pdf",
+        );
+        push_field(&mut bytes, 0x49, "", b"image/urf");
+        push_field(&mut bytes, 0x49, "document-format-supported", b"image/jpeg");
+        push_field(&mut bytes, 0x49, "", b"text/plain");
+        push_field(&mut bytes, 0x42, "printer-name", b"office");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let lenient = CompatQuirks {
+            repeated_names_as_values: true,
+            ..strict()
+        };
+        let (operation, report) = Operation::try_from_ipp_with_quirks(&bytes, &lenient).unwrap();
+        assert_eq!(
+            report.warnings,
+            [DecodeWarning::RepeatedName(
+                "document-format-supported".to_string()
+            )]
+        );
+        let printer_attributes = operation
+            .attribute_group(DelimiterTag::PrinterAttributes)
+            .unwrap();
+        assert_eq!(printer_attributes.attributes.len(), 2);
+        assert_eq!(
+            operation
+                .attribute(
+                    DelimiterTag::PrinterAttributes,
+                    PrinterAttribute::DocumentFormatSupported,
+                )
+                .unwrap()
+                .as_strings()
+                .collect::<Vec<_>>(),
+            ["application/pdf", "image/urf", "image/jpeg", "text/plain"]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::spec::{
+    attribute::OperationAttribute, tag::DelimiterTag, value::CompressionSupportedKeyword,
+};
+
+use super::{AttributeValue, Operation};
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use std::borrow::Cow;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+impl Operation {
+    /// Document data decompressed according to the 'compression' operation attribute
+    ///
+    /// Borrowed as-is when the attribute is absent or 'none'.
+    /// 'compress' is not supported and yields [`io::ErrorKind::Unsupported`].
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.1.1)
+    pub fn decompressed_data(&self) -> Result<Cow<'_, [u8]>, io::Error> {
+        let compression = self
+            .attribute(
+                DelimiterTag::OperationAttributes,
+                OperationAttribute::Compression,
+            )
+            .and_then(|attribute| attribute.values.first());
+
+        let keyword = match compression {
+            None => CompressionSupportedKeyword::None,
+            Some(AttributeValue::Keyword(keyword)) => {
+                CompressionSupportedKeyword::from_str(keyword).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown compression '{}'", keyword),
+                    )
+                })?
+            }
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "compression is not a keyword",
+                ))
+            }
+        };
+
+        let mut decompressed = Vec::new();
+        match keyword {
+            CompressionSupportedKeyword::None => return Ok(Cow::Borrowed(&self.data)),
+            CompressionSupportedKeyword::Deflate => {
+                DeflateDecoder::new(self.data.as_slice()).read_to_end(&mut decompressed)?;
+            }
+            CompressionSupportedKeyword::Gzip => {
+                MultiGzDecoder::new(self.data.as_slice()).read_to_end(&mut decompressed)?;
+            }
+            CompressionSupportedKeyword::Compress => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "'compress' is not supported",
+                ))
+            }
+        }
+
+        Ok(Cow::Owned(decompressed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{Attribute, IppVersion};
+    use crate::spec::operation::OperationID;
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder},
+        Compression,
+    };
+    use std::io::Write;
+
+    const POSTSCRIPT: &[u8] =
+        b"%!PS-Adobe-3.0\n/Helvetica findfont 12 scalefont setfont\nshowpage\n";
+
+    /// Print-Job carrying `data`, with 'compression' set to `compression` if any
+    fn print_job(compression: Option<&str>, data: Vec<u8>) -> Operation {
+        let mut request = Operation::new_request(IppVersion::V1_1, OperationID::PrintJob, 1);
+        if let Some(compression) = compression {
+            request
+                .operation_attributes_mut()
+                .insert(Attribute::keyword(
+                    OperationAttribute::Compression,
+                    compression,
+                ));
+        }
+        request.data = data;
+        request
+    }
+
+    #[test]
+    fn gzip_postscript_is_decompressed() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(POSTSCRIPT).unwrap();
+        let request = print_job(Some("gzip"), gzip.finish().unwrap());
+        assert_ne!(request.data, POSTSCRIPT);
+
+        let decompressed = request.decompressed_data().unwrap();
+        assert!(matches!(decompressed, Cow::Owned(_)));
+        assert_eq!(decompressed, POSTSCRIPT);
+
+        // not gzip at all
+        let request = print_job(Some("gzip"), POSTSCRIPT.to_vec());
+        assert!(request.decompressed_data().is_err());
+    }
+
+    #[test]
+    fn deflate_is_decompressed() {
+        let mut deflate = DeflateEncoder::new(Vec::new(), Compression::default());
+        deflate.write_all(POSTSCRIPT).unwrap();
+        let request = print_job(Some("deflate"), deflate.finish().unwrap());
+        assert_eq!(request.decompressed_data().unwrap(), POSTSCRIPT);
+    }
+
+    #[test]
+    fn uncompressed_data_is_borrowed() {
+        for compression in [None, Some("none")] {
+            let request = print_job(compression, POSTSCRIPT.to_vec());
+            let data = request.decompressed_data().unwrap();
+            assert!(matches!(data, Cow::Borrowed(_)));
+            assert_eq!(data, POSTSCRIPT);
+        }
+
+        let request = print_job(Some("compress"), POSTSCRIPT.to_vec());
+        assert_eq!(
+            request.decompressed_data().unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+}
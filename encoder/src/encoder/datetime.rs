@@ -1,5 +1,7 @@
-use super::IppEncode;
-use chrono::{DateTime, Datelike, Offset, TimeZone, Timelike, Utc};
+use super::{operation::length_prefixed, IppEncode, IppError};
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, Offset, TimeZone, Timelike, Utc,
+};
 
 impl IppEncode for DateTime<Utc> {
     fn ipp_bytes() -> usize {
@@ -8,88 +10,41 @@ impl IppEncode for DateTime<Utc> {
 
     fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
         let start = offset + Self::ipp_value_length_bytes();
-
-        let slice_offset = start + 8;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let direction = u8::from_be_bytes(slice) as char;
-
-        let slice_offset = start + 9;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let hour_from_utc = u8::from_be_bytes(slice);
-
-        let slice_offset = start + 10;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let minutes_from_utc = u8::from_be_bytes(slice);
-
-        let mut drift = (hour_from_utc * 60 - minutes_from_utc) as i8;
-        if direction == '-' {
-            drift *= -1;
-        }
-
-        let slice_offset = start;
-        let slice: [u8; 2] = bytes[slice_offset..slice_offset + 2].try_into().unwrap();
-        let year = u16::from_be_bytes(slice);
-
-        let slice_offset = start + 2;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let month = u8::from_be_bytes(slice);
-
-        let slice_offset = start + 3;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let day = u8::from_be_bytes(slice);
-
-        let slice_offset = start + 4;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let hour = u8::from_be_bytes(slice);
-
-        let slice_offset = start + 5;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let minutes = (i8::from_be_bytes(slice) + drift) as u8;
-
-        let slice_offset = start + 6;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let seconds = u8::from_be_bytes(slice);
-
-        let slice_offset = start + 7;
-        let slice: [u8; 1] = bytes[slice_offset..slice_offset + 1].try_into().unwrap();
-        let deciseconds = u8::from_be_bytes(slice);
-
-        let value = Utc
-            .ymd(year as i32, month as u32, day as u32)
-            .and_hms_micro(
-                hour as u32,
-                minutes as u32,
-                seconds as u32,
-                deciseconds as u32 * 100,
-            );
+        let value = datetime_from_value(&bytes[start..start + Self::ipp_bytes()]).unwrap();
 
         (value.ipp_len(), value)
     }
 
     fn to_ipp(&self) -> Vec<u8> {
-        let value_length = self.ipp_len() as u16;
-        let value_length_bytes = value_length.to_be_bytes().to_vec();
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
+
+    fn to_ipp_into(&self, buf: &mut Vec<u8>) {
+        let value_length = Self::ipp_bytes() as u16;
+        buf.extend_from_slice(&value_length.to_be_bytes());
 
         let year = self.year() as u16;
-        let year_bytes = year.to_be_bytes().to_vec();
+        buf.extend_from_slice(&year.to_be_bytes());
 
         let month = self.month() as u8;
-        let month_bytes = month.to_be_bytes().to_vec();
+        buf.push(month);
 
         let day = self.day() as u8;
-        let day_bytes = day.to_be_bytes().to_vec();
+        buf.push(day);
 
         let hour = self.hour() as u8;
-        let hour_bytes = hour.to_be_bytes().to_vec();
+        buf.push(hour);
 
         let minutes = self.minute() as u8;
-        let minutes_bytes = minutes.to_be_bytes().to_vec();
+        buf.push(minutes);
 
         let seconds = self.second() as u8;
-        let seconds_bytes = seconds.to_be_bytes().to_vec();
+        buf.push(seconds);
 
-        let deciseconds = 0_u8;
-        let deciseconds_bytes = deciseconds.to_be_bytes().to_vec();
+        let deciseconds = (self.nanosecond() / 100_000_000) as u8;
+        buf.push(deciseconds);
 
         let local_minus_utc = self.timezone().fix().local_minus_utc() / 60;
 
@@ -97,27 +52,56 @@ impl IppEncode for DateTime<Utc> {
         if local_minus_utc < 0 {
             direction = '-';
         }
-        let direction_bytes = (direction as u8).to_be_bytes().to_vec();
+        buf.push(direction as u8);
 
         let hour_from_utc = (local_minus_utc / 60) as u8;
-        let hour_from_utc_bytes = hour_from_utc.to_be_bytes().to_vec();
+        buf.push(hour_from_utc);
 
         let minutes_from_utc = (local_minus_utc % 60) as u8;
-        let minutes_from_utc_bytes = minutes_from_utc.to_be_bytes().to_vec();
-
-        [
-            value_length_bytes,
-            year_bytes,
-            month_bytes,
-            day_bytes,
-            hour_bytes,
-            minutes_bytes,
-            deciseconds_bytes,
-            seconds_bytes,
-            direction_bytes,
-            hour_from_utc_bytes,
-            minutes_from_utc_bytes,
-        ]
-        .concat()
+        buf.push(minutes_from_utc);
+    }
+}
+
+impl super::IppDecode for DateTime<Utc> {
+    fn min_len() -> usize {
+        2 + 11
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let value = datetime_from_value(length_prefixed(bytes, offset, offset)?)
+            .ok_or(IppError::InvalidDateTime(offset))?;
+
+        Ok((Self::min_len(), value))
+    }
+}
+
+/// decode the 11 bytes of a 'dateTime' value, None unless they form a valid date and time
+///
+/// ref: [rfc2579](https://datatracker.ietf.org/doc/html/rfc2579) DateAndTime
+pub(crate) fn datetime_from_value(value: &[u8]) -> Option<DateTime<Utc>> {
+    if value.len() != 11 {
+        return None;
     }
+
+    let year = u16::from_be_bytes([value[0], value[1]]);
+    let date = NaiveDate::from_ymd_opt(year as i32, value[2] as u32, value[3] as u32)?;
+    let time = NaiveTime::from_hms_milli_opt(
+        value[4] as u32,
+        value[5] as u32,
+        value[6] as u32,
+        value[7] as u32 * 100,
+    )?;
+
+    // direction, hours & minutes from UTC
+    let offset_seconds = (value[9] as i32 * 60 + value[10] as i32) * 60;
+    let offset = match value[8] {
+        b'+' => FixedOffset::east_opt(offset_seconds)?,
+        b'-' => FixedOffset::west_opt(offset_seconds)?,
+        _ => return None,
+    };
+
+    offset
+        .from_local_datetime(&date.and_time(time))
+        .single()
+        .map(|datetime| datetime.with_timezone(&Utc))
 }
@@ -0,0 +1,152 @@
+//! Human-readable dump in the style of `ipptool -v`
+//!
+//! ```text
+//! version 1.1
+//! operation-id Get-Printer-Attributes (0x000b)
+//! request-id 1
+//! operation-attributes-tag
+//!     attributes-charset (charset) = utf-8
+//!     requested-attributes (1setOf keyword) = printer-name,printer-state
+//! end-of-attributes-tag
+//! ```
+
+use super::{Attribute, AttributeValue};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use super::{AttributeGroup, Operation};
+#[cfg(feature = "std")]
+use crate::spec::tag::DelimiterTag;
+
+#[cfg(feature = "std")]
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "version {}", self.version)?;
+
+        // the same field carries operation-id in requests and status-code in responses,
+        // 0x0002 is both Print-Job and successful-ok-conflicting-attributes
+        let code = self.operation_id_or_status_code;
+        match (self.operation_id(), self.status_code()) {
+            (Some(operation_id), None) => {
+                writeln!(f, "operation-id {} ({:#06x})", operation_id, code)?
+            }
+            (None, Some(status_code)) => {
+                writeln!(f, "status-code {} ({:#06x})", status_code, code)?
+            }
+            (Some(operation_id), Some(status_code)) => writeln!(
+                f,
+                "operation-id-or-status-code {} / {} ({:#06x})",
+                operation_id, status_code, code
+            )?,
+            (None, None) => writeln!(f, "operation-id-or-status-code {:#06x}", code)?,
+        }
+
+        writeln!(f, "request-id {}", self.request_id)?;
+
+        for group in &self.attribute_groups {
+            write!(f, "{}", group)?;
+        }
+
+        writeln!(f, "{}", group_name(DelimiterTag::EndOfAttributes))?;
+
+        if !self.data.is_empty() {
+            writeln!(f, "data ({} bytes)", self.data.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for AttributeGroup {
+    /// group name, then one attribute per line, sorted by name
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", group_name(self.tag))?;
+
+        let mut attributes: Vec<&Attribute> = self.attributes.values().collect();
+        attributes.sort_by_cached_key(|attribute| attribute.name.to_string());
+
+        for attribute in attributes {
+            writeln!(f, "    {}", attribute)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Attribute {
+    /// `name (tag) = value[,value...]`, `1setOf` is prefixed to the tag of multi-valued attributes
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.values.len() > 1 {
+            write!(f, "{} (1setOf {})", self.name, self.tag)?;
+        } else {
+            write!(f, "{} ({})", self.name, self.tag)?;
+        }
+
+        // out-of-band values carry no value
+        if self.tag.is_out_of_band() {
+            return Ok(());
+        }
+
+        f.write_str(" = ")?;
+        write_values(f, &self.values)
+    }
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TextWithoutLang(text)
+            | Self::Keyword(text)
+            | Self::Uri(text)
+            | Self::MimeType(text)
+            | Self::Charset(text)
+            | Self::NaturalLanguage(text) => f.write_str(text),
+            Self::Number(number) => write!(f, "{}", number),
+            Self::Boolean(boolean) => write!(f, "{}", boolean),
+            Self::TextWithLang(text_with_lang) => {
+                write!(f, "{} ({})", text_with_lang.text, text_with_lang.lang)
+            }
+            Self::Resolution(resolution) => write!(f, "{}", resolution),
+            Self::RangeOfInteger(range) => write!(f, "{}", range),
+            #[cfg(feature = "std")]
+            Self::DateTime(datetime) => write!(f, "{}", datetime.format("%Y-%m-%dT%H:%M:%SZ")),
+            Self::Collection(members) => {
+                f.write_str("{")?;
+                for (i, member) in members.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{}=", member.name)?;
+                    write_values(f, &member.values)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_values(f: &mut fmt::Formatter<'_>, values: &[AttributeValue]) -> fmt::Result {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{}", value)?;
+    }
+
+    Ok(())
+}
+
+/// name of the delimiter tag as listed in the "Attribute Group Tags" registry
+#[cfg(feature = "std")]
+pub(crate) fn group_name(tag: DelimiterTag) -> &'static str {
+    match tag {
+        DelimiterTag::OperationAttributes => "operation-attributes-tag",
+        DelimiterTag::JobAttributes => "job-attributes-tag",
+        DelimiterTag::EndOfAttributes => "end-of-attributes-tag",
+        DelimiterTag::PrinterAttributes => "printer-attributes-tag",
+        DelimiterTag::UnsupportedAttributes => "unsupported-attributes-tag",
+        DelimiterTag::SubscriptionAttributes => "subscription-attributes-tag",
+        DelimiterTag::EventNotificationAttributes => "event-notification-attributes-tag",
+    }
+}
@@ -1,10 +1,336 @@
-#[derive(Debug)]
+use alloc::{format, string::String};
+
+/// Name in none of the attribute registries, from [`AttributeName::parse_strict`]
+///
+/// [`AttributeName::parse_strict`]: super::AttributeName::parse_strict
+#[derive(Debug, PartialEq, Eq)]
 pub struct AttributeNameParseError {
+    name: String,
+    suggestion: Option<String>,
+}
+
+impl AttributeNameParseError {
+    pub(crate) fn new(name: &str, suggestion: Option<String>) -> Self {
+        Self {
+            name: String::from(name),
+            suggestion,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// closest known attribute name, if any is close enough to be a likely typo
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+}
+
+impl core::fmt::Display for AttributeNameParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "AttributeNameParseError: unknown attribute '{}'",
+            &self.name
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean '{}'?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AttributeNameParseError {}
+
+#[derive(Debug)]
+pub struct IppVersionParseError {
+    message: String,
+}
+
+impl IppVersionParseError {
+    pub(crate) fn new(version: &str) -> Self {
+        Self {
+            message: format!("expected <major>.<minor>, got '{}'", version),
+        }
+    }
+}
+
+impl core::fmt::Display for IppVersionParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "IppVersionParseError: {}", &self.message)
+    }
+}
+
+/// String that is not in the textual form of a value syntax,
+/// e.g. `600x600dpi` for a [`Resolution`](super::Resolution)
+#[derive(Debug)]
+pub struct ValueParseError {
     message: String,
 }
 
-impl std::fmt::Display for AttributeNameParseError {
+impl ValueParseError {
+    pub(crate) fn new(form: &str, value: &str) -> Self {
+        Self {
+            message: format!("expected {}, got '{}'", form, value),
+        }
+    }
+}
+
+impl core::fmt::Display for ValueParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ValueParseError: {}", &self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValueParseError {}
+
+/// Error surfaced by the fallible decoding APIs
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum IppError {
+    /// underlying reader failed or ended before the end-of-attributes tag
+    Io(std::io::Error),
+    /// a tag that is neither a known delimiter-tag nor a known value-tag
+    UnknownTag(u8),
+    /// the field starting at `offset` needs `needed` bytes from its length on,
+    /// only `available` are left
+    Truncated {
+        offset: usize,
+        needed: usize,
+        available: usize,
+    },
+    /// the name or character-string value of the field at this offset is not UTF-8
+    InvalidUtf8(usize),
+    /// the 'dateTime' value of the field at this offset is not a valid date and time
+    InvalidDateTime(usize),
+    /// the value at this offset does not match the syntax of its value-tag
+    InvalidValue(usize),
+    /// decoding of the attribute groups stopped before an end-of-attributes tag
+    MissingEndOfAttributes,
+    /// a version-number outside 1.x to 3.x, the bytes are most likely not IPP at all
+    NotIpp(super::IppVersion),
+    /// 'attributes-charset' is not the first operation attribute, see
+    /// [`CompatQuirks::charset_not_first`](super::CompatQuirks::charset_not_first)
+    CharsetNotFirst,
+    /// a request whose operation-id is not one of [`OperationID`](crate::spec::operation::OperationID)
+    UnknownOperationId(u16),
+    /// a response whose status-code is not one of [`StatusCode`](crate::spec::operation::StatusCode)
+    UnknownStatusCode(u16),
+    /// the message goes beyond one of the [`DecodeLimits`](super::DecodeLimits),
+    /// `limit` is the name of the field and `max` its value
+    LimitExceeded { limit: &'static str, max: usize },
+    /// the field at this offset has a zero-length name, making it an additional value,
+    /// but it comes first in its group with no attribute before it to belong to
+    AdditionalValueWithoutAttribute(usize),
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for IppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "IppError: {}", err),
+            Self::UnknownTag(tag) => write!(f, "IppError: unknown tag 0x{:02x}", tag),
+            Self::Truncated {
+                offset,
+                needed,
+                available,
+            } => write!(
+                f,
+                "IppError: field at offset {} is truncated, needs {} bytes but {} are left",
+                offset, needed, available
+            ),
+            Self::InvalidUtf8(offset) => write!(f, "IppError: invalid UTF-8 at offset {}", offset),
+            Self::InvalidDateTime(offset) => {
+                write!(f, "IppError: invalid dateTime at offset {}", offset)
+            }
+            Self::InvalidValue(offset) => write!(f, "IppError: invalid value at offset {}", offset),
+            Self::MissingEndOfAttributes => write!(f, "IppError: missing end-of-attributes tag"),
+            Self::NotIpp(version) => {
+                write!(
+                    f,
+                    "IppError: not an IPP message, version-number {}",
+                    version
+                )
+            }
+            Self::CharsetNotFirst => {
+                write!(
+                    f,
+                    "IppError: attributes-charset is not the first operation attribute"
+                )
+            }
+            Self::UnknownOperationId(id) => {
+                write!(f, "IppError: unknown operation-id 0x{:04x}", id)
+            }
+            Self::UnknownStatusCode(code) => {
+                write!(f, "IppError: unknown status-code 0x{:04x}", code)
+            }
+            Self::LimitExceeded { limit, max } => {
+                write!(f, "IppError: {} of {} exceeded", limit, max)
+            }
+            Self::AdditionalValueWithoutAttribute(offset) => write!(
+                f,
+                "IppError: additional value at offset {} has no attribute to belong to",
+                offset
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for IppError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Invariant violated by [`OperationBuilder::build`](super::OperationBuilder::build)
+/// or [`ResponseBuilder::build`](super::ResponseBuilder::build)
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// no 'attributes-charset' in the operation-attributes group
+    MissingCharset,
+    /// no 'attributes-natural-language' in the operation-attributes group
+    MissingNaturalLanguage,
+    /// request-id 0 is reserved, requests must use 1 or more
+    ZeroRequestId,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCharset => write!(f, "BuildError: missing attributes-charset"),
+            Self::MissingNaturalLanguage => {
+                write!(f, "BuildError: missing attributes-natural-language")
+            }
+            Self::ZeroRequestId => write!(f, "BuildError: request-id must not be 0"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// How far a [`ValidationIssue`] is from the RFCs
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// breaks a MUST, a printer rejects the request
+    Error,
+    /// breaks a SHOULD
+    Warning,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Problem found by [`Operation::validate`](super::Operation::validate), with the group
+/// and attribute it concerns where there is one
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub group: Option<crate::spec::tag::DelimiterTag>,
+    pub attribute: Option<super::AttributeName>,
+    pub kind: ValidationIssueKind,
+}
+
+#[cfg(feature = "std")]
+impl ValidationIssue {
+    pub fn message(&self) -> String {
+        self.kind.to_string()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for ValidationIssue {
+    /// `severity: message`, then the group and attribute in parentheses
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.severity, self.kind)?;
+
+        match (&self.group, &self.attribute) {
+            (Some(group), Some(attribute)) => {
+                write!(
+                    f,
+                    " ({}, {})",
+                    super::display::group_name(*group),
+                    attribute
+                )
+            }
+            (Some(group), None) => write!(f, " ({})", super::display::group_name(*group)),
+            (None, Some(attribute)) => write!(f, " ({})", attribute),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// What a [`ValidationIssue`] is about
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssueKind {
+    /// no operation-attributes group
+    MissingOperationAttributes,
+    /// more than one operation-attributes group
+    DuplicateOperationAttributes,
+    /// another group comes before the operation-attributes group
+    OperationAttributesNotFirst,
+    /// a group the operation does not take
+    UnexpectedGroup,
+    /// an attribute the operation requires is absent
+    MissingRequiredAttribute,
+    /// an attribute without any value
+    EmptyValues,
+    /// a single-valued attribute with several values
+    MultipleValues,
+    /// a value-tag outside the syntax of the attribute
+    UnexpectedValueTag(crate::spec::tag::ValueTag),
+    /// a version outside [`IppVersion::KNOWN`](super::IppVersion::KNOWN)
+    UnsupportedVersion(super::IppVersion),
+    /// request-id 0 is reserved
+    ZeroRequestId,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for ValidationIssueKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "AttributeNameParseError: {}", &self.message)
+        match self {
+            Self::MissingOperationAttributes => write!(f, "missing operation-attributes group"),
+            Self::DuplicateOperationAttributes => {
+                write!(f, "more than one operation-attributes group")
+            }
+            Self::OperationAttributesNotFirst => {
+                write!(f, "operation-attributes group is not first")
+            }
+            Self::UnexpectedGroup => write!(f, "group not taken by the operation"),
+            Self::MissingRequiredAttribute => write!(f, "missing required attribute"),
+            Self::EmptyValues => write!(f, "attribute without values"),
+            Self::MultipleValues => write!(f, "several values for a single-valued attribute"),
+            Self::UnexpectedValueTag(tag) => {
+                write!(f, "value-tag {} is outside the attribute syntax", tag)
+            }
+            Self::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            Self::ZeroRequestId => write!(f, "request-id must not be 0"),
+        }
     }
 }
@@ -0,0 +1,253 @@
+use super::{operation::check_version, DecodeLimits, IppError};
+use crate::spec::tag::{DelimiterTag, ValueTag};
+use std::ops::Range;
+
+/// Field completed by [`FrameScanner::next_field`]
+pub(crate) enum Field {
+    /// the bytes must grow to this length before the next field can complete
+    NeedMore(usize),
+    /// version-number, operation-id or status-code and request-id
+    Header,
+    /// begin-attribute-group-tag, reserved ones included
+    Delimiter,
+    /// value-tag, name-length & name, value-length & value;
+    /// `name` and `value` are the ranges of the name and value without their lengths
+    Attribute {
+        field_offset: usize,
+        value_tag: ValueTag,
+        name: Range<usize>,
+        value: Range<usize>,
+    },
+    /// end-of-attributes tag, with the offset just past it
+    End(usize),
+}
+
+/// Framing of a message up to its end-of-attributes tag, shared by every decoder
+/// that checks its input
+///
+/// Each call picks up at the first field not yet complete, so the bytes can grow
+/// between calls; [`Field::NeedMore`] tells exactly how many are needed to go on.
+/// Groups, attributes and collection depth are counted as fields complete, so a stream
+/// going past its [`DecodeLimits`] fails before more of it is buffered.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameScanner {
+    limits: DecodeLimits,
+    /// start of the next field
+    position: usize,
+    header_pending: bool,
+    /// start of the incomplete field and of its part waiting for bytes,
+    /// None while waiting for a tag
+    pending: Option<(usize, usize)>,
+    groups: usize,
+    attributes_in_group: usize,
+    collection_depth: usize,
+}
+
+impl FrameScanner {
+    /// scanner for a message starting at `offset`, header first
+    pub fn new(offset: usize, limits: &DecodeLimits) -> Self {
+        Self {
+            limits: *limits,
+            position: offset,
+            header_pending: true,
+            pending: None,
+            groups: 0,
+            attributes_in_group: 0,
+            collection_depth: 0,
+        }
+    }
+
+    /// scanner for attribute groups starting at `offset`, without a header
+    pub fn groups(offset: usize, limits: &DecodeLimits) -> Self {
+        Self {
+            header_pending: false,
+            ..Self::new(offset, limits)
+        }
+    }
+
+    pub fn next_field(&mut self, bytes: &[u8]) -> Result<Field, IppError> {
+        let exceeded = |limit: &'static str, max: usize| IppError::LimitExceeded { limit, max };
+        let field_offset = self.position;
+
+        if self.header_pending {
+            // version-number (2 bytes), operation-id or status-code (2 bytes), request-id (4 bytes)
+            let end = field_offset + 8;
+            if bytes.len() < end {
+                self.pending = Some((field_offset, field_offset));
+                return Ok(Field::NeedMore(end));
+            }
+            check_version(bytes, field_offset)?;
+
+            self.header_pending = false;
+            self.position = end;
+            return Ok(Field::Header);
+        }
+
+        let tag = match bytes.get(field_offset) {
+            Some(tag) => *tag,
+            None => {
+                self.pending = None;
+                return Ok(Field::NeedMore(field_offset + 1));
+            }
+        };
+
+        if DelimiterTag::is_delimiter(tag) {
+            self.position += 1;
+            if DelimiterTag::from_repr(tag as usize) == Some(DelimiterTag::EndOfAttributes) {
+                return Ok(Field::End(self.position));
+            }
+
+            // a reserved begin-attribute-group-tag too, its attributes are dropped but still count
+            self.groups += 1;
+            if self.groups > self.limits.max_groups {
+                return Err(exceeded("max_groups", self.limits.max_groups));
+            }
+            self.attributes_in_group = 0;
+            return Ok(Field::Delimiter);
+        }
+
+        let value_tag = ValueTag::from_wire(tag).ok_or(IppError::UnknownTag(tag))?;
+
+        // name-length & name, then value-length & value
+        let name = match self.length_prefixed(bytes, field_offset, field_offset + 1) {
+            Ok(name) => name,
+            Err(needed) => return Ok(Field::NeedMore(needed)),
+        };
+        let value = match self.length_prefixed(bytes, field_offset, name.end) {
+            Ok(value) => value,
+            Err(needed) => return Ok(Field::NeedMore(needed)),
+        };
+
+        // a name outside of collections starts an attribute, an empty one
+        // is an additional value of the attribute before it
+        if self.collection_depth == 0 && !name.is_empty() {
+            self.attributes_in_group += 1;
+            if self.attributes_in_group > self.limits.max_attributes_per_group {
+                return Err(exceeded(
+                    "max_attributes_per_group",
+                    self.limits.max_attributes_per_group,
+                ));
+            }
+        } else if self.collection_depth == 0 && self.attributes_in_group == 0 {
+            return Err(IppError::AdditionalValueWithoutAttribute(field_offset));
+        }
+
+        match value_tag {
+            ValueTag::BegCollection => {
+                self.collection_depth += 1;
+                if self.collection_depth > self.limits.max_collection_depth {
+                    return Err(exceeded(
+                        "max_collection_depth",
+                        self.limits.max_collection_depth,
+                    ));
+                }
+            }
+            ValueTag::EndCollection => {
+                self.collection_depth = self.collection_depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+
+        self.position = value.end;
+        Ok(Field::Attribute {
+            field_offset,
+            value_tag,
+            name,
+            value,
+        })
+    }
+
+    /// error for bytes that end while [`Field::NeedMore`] is still `needed`
+    ///
+    /// Ending between fields is a group section without its end tag, ending within one
+    /// is [`IppError::Truncated`], counted from the part of the field that is cut off.
+    pub fn incomplete(&self, bytes: &[u8], needed: usize) -> IppError {
+        match self.pending {
+            None => IppError::MissingEndOfAttributes,
+            Some((field_offset, part_offset)) => IppError::Truncated {
+                offset: field_offset,
+                needed: needed - part_offset,
+                available: bytes.len().saturating_sub(part_offset),
+            },
+        }
+    }
+
+    /// range of the bytes declared by the 2-byte length at `offset`,
+    /// or the length `bytes` must grow to for them to be there
+    fn length_prefixed(
+        &mut self,
+        bytes: &[u8],
+        field_offset: usize,
+        offset: usize,
+    ) -> Result<Range<usize>, usize> {
+        self.pending = Some((field_offset, offset));
+
+        let length = bytes.get(offset..offset + 2).ok_or(offset + 2)?;
+        let end = offset + 2 + u16::from_be_bytes([length[0], length[1]]) as usize;
+
+        if bytes.len() < end {
+            Err(end)
+        } else {
+            Ok(offset + 2..end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get-Printer-Attributes request with 'attributes-charset' and document data
+    fn message() -> Vec<u8> {
+        let mut bytes = vec![1, 1, 0x00, 0x0b, 0, 0, 0, 1, 0x01];
+        bytes.push(0x47);
+        bytes.extend(18u16.to_be_bytes());
+        bytes.extend(b"attributes-charset");
+        bytes.extend(5u16.to_be_bytes());
+        bytes.extend(b"utf-8");
+        bytes.push(0x03);
+        bytes.extend(b"%PDF");
+        bytes
+    }
+
+    #[test]
+    fn asks_for_exactly_the_next_field() {
+        let message = message();
+        let mut scanner = FrameScanner::new(0, &DecodeLimits::default());
+        let mut len = 0;
+        let mut needed = Vec::new();
+
+        let end = loop {
+            match scanner.next_field(&message[..len]).unwrap() {
+                Field::NeedMore(more) => {
+                    needed.push(more);
+                    len = more;
+                }
+                Field::End(end) => break end,
+                Field::Header | Field::Delimiter | Field::Attribute { .. } => {}
+            }
+        };
+
+        // header, tag, tag & name-length, name, value-length, value, tag
+        assert_eq!(needed, [8, 9, 10, 12, 30, 32, 37, 38]);
+        assert_eq!(end, message.len() - 4);
+    }
+
+    #[test]
+    fn rejects_unknown_tag_and_non_ipp_version() {
+        let mut bytes = message();
+        bytes[9] = 0x20;
+        let mut scanner = FrameScanner::new(0, &DecodeLimits::default());
+        assert!(matches!(scanner.next_field(&bytes), Ok(Field::Header)));
+        assert!(matches!(scanner.next_field(&bytes), Ok(Field::Delimiter)));
+        assert!(matches!(
+            scanner.next_field(&bytes),
+            Err(IppError::UnknownTag(0x20))
+        ));
+
+        assert!(matches!(
+            FrameScanner::new(0, &DecodeLimits::default()).next_field(b"GET / HTTP/1.1"),
+            Err(IppError::NotIpp(_))
+        ));
+    }
+}
@@ -1,10 +1,145 @@
-use serde::{Deserialize, Serialize};
+use super::error::IppVersionParseError;
+use alloc::string::String;
+use core::str::FromStr;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// 2 bytes of IPP version
 /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.4.1)
 ///
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+/// Serialized as its dotted string, e.g. `"1.1"`; deserialization also accepts
+/// the `{"major": 1, "minor": 1}` object of earlier releases.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct IppVersion {
     pub major: u8,
     pub minor: u8,
 }
+
+impl IppVersion {
+    pub const V1_0: Self = Self { major: 1, minor: 0 };
+    pub const V1_1: Self = Self { major: 1, minor: 1 };
+    pub const V2_0: Self = Self { major: 2, minor: 0 };
+    pub const V2_1: Self = Self { major: 2, minor: 1 };
+    pub const V2_2: Self = Self { major: 2, minor: 2 };
+
+    /// versions defined by the IPP specifications
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.8)
+    pub const KNOWN: [Self; 5] = [Self::V1_0, Self::V1_1, Self::V2_0, Self::V2_1, Self::V2_2];
+
+    pub fn is_supported(&self, supported: &[IppVersion]) -> bool {
+        supported.contains(self)
+    }
+
+    /// Highest of `supported` not above this version, else the lowest of `supported`;
+    /// unchanged if `supported` is empty
+    pub fn clamp_to_supported(&self, supported: &[IppVersion]) -> Self {
+        supported
+            .iter()
+            .filter(|version| *version <= self)
+            .max()
+            .or_else(|| supported.iter().min())
+            .copied()
+            .unwrap_or(*self)
+    }
+}
+
+impl core::fmt::Display for IppVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for IppVersion {
+    type Err = IppVersionParseError;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = str
+            .split_once('.')
+            .ok_or_else(|| IppVersionParseError::new(str))?;
+
+        match (u8::from_str(major), u8::from_str(minor)) {
+            (Ok(major), Ok(minor)) => Ok(Self { major, minor }),
+            _ => Err(IppVersionParseError::new(str)),
+        }
+    }
+}
+
+impl Serialize for IppVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IppVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawIppVersion::deserialize(deserializer)? {
+            RawIppVersion::Dotted(version) => Self::from_str(&version).map_err(D::Error::custom),
+            RawIppVersion::Object { major, minor } => Ok(Self { major, minor }),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawIppVersion {
+    Dotted(String),
+    Object { major: u8, minor: u8 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn known_versions_are_ordered() {
+        let mut versions: Vec<_> = IppVersion::KNOWN.iter().rev().copied().collect();
+        versions.sort();
+        assert_eq!(versions, IppVersion::KNOWN);
+
+        assert!(IppVersion::V1_1 < IppVersion::V2_0);
+        // minor only matters within the same major
+        assert!(IppVersion { major: 1, minor: 9 } < IppVersion::V2_0);
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        for version in IppVersion::KNOWN {
+            assert_eq!(
+                version.to_string().parse::<IppVersion>().ok(),
+                Some(version)
+            );
+        }
+        assert_eq!(IppVersion::V2_0.to_string(), "2.0");
+        assert_eq!("2.0".parse::<IppVersion>().ok(), Some(IppVersion::V2_0));
+
+        for invalid in ["2", "a.b", "1.1.1", "256.0", ""] {
+            assert!(invalid.parse::<IppVersion>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn is_supported() {
+        let supported = [IppVersion::V1_0, IppVersion::V1_1];
+        assert!(IppVersion::V1_1.is_supported(&supported));
+        assert!(!IppVersion::V2_0.is_supported(&supported));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn serialized_dotted() {
+        assert_eq!(
+            serde_json::to_string(&IppVersion::V2_0).unwrap(),
+            r#""2.0""#
+        );
+        assert_eq!(
+            serde_json::from_str::<IppVersion>(r#""1.1""#).unwrap(),
+            IppVersion::V1_1
+        );
+        assert_eq!(
+            serde_json::from_str::<IppVersion>(r#"{"major":2,"minor":1}"#).unwrap(),
+            IppVersion::V2_1
+        );
+        assert!(serde_json::from_str::<IppVersion>(r#""x""#).is_err());
+    }
+}
@@ -0,0 +1,27 @@
+/// Bounds on the structure of a message checked by the fallible decoders,
+/// see [`Operation::try_from_ipp_with_limits`](super::Operation::try_from_ipp_with_limits)
+///
+/// Collections are decoded recursively, so without a depth bound a request nesting
+/// begCollection deep enough would exhaust the stack. Tripping a limit fails with
+/// [`IppError::LimitExceeded`](super::IppError::LimitExceeded).
+///
+/// The defaults are well above what any printer or client sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// collections nested within each other, a collection value at the top level is depth 1
+    pub max_collection_depth: usize,
+    /// attributes in one attribute group, additional values and collection members aside
+    pub max_attributes_per_group: usize,
+    /// attribute groups in one message, end-of-attributes aside
+    pub max_groups: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_collection_depth: 32,
+            max_attributes_per_group: 4096,
+            max_groups: 4096,
+        }
+    }
+}
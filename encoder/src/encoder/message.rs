@@ -0,0 +1,194 @@
+use crate::spec::operation::{OperationID, StatusCode};
+
+use super::{AttributeGroup, IppEncode, IppError, IppVersion, Operation};
+
+use std::fmt;
+use std::io::Write;
+
+/// [`Operation`] read as a request, its operation-id typed
+///
+/// The raw [`Operation`] cannot tell a request from a response: 0x0002 is both
+/// Print-Job and 'successful-ok-conflicting-attributes'. Servers decode with
+/// [`IppRequest::from_ipp`], clients with [`IppResponse::from_ipp`].
+/// Both convert to and from [`Operation`] for generic tooling.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IppRequest {
+    pub version: IppVersion,
+    pub operation_id: OperationID,
+    pub request_id: u32,
+    /// attribute groups in wire order, a tag may repeat
+    pub attribute_groups: Vec<AttributeGroup>,
+    /// document data in trailing bytes
+    pub data: Vec<u8>,
+}
+
+/// [`Operation`] read as a response, its status-code typed, see [`IppRequest`]
+#[derive(Clone, PartialEq, Eq)]
+pub struct IppResponse {
+    pub version: IppVersion,
+    pub status: StatusCode,
+    pub request_id: u32,
+    /// attribute groups in wire order, a tag may repeat (e.g. job-attributes in Get-Jobs)
+    pub attribute_groups: Vec<AttributeGroup>,
+    /// additional data in trailing bytes
+    pub data: Vec<u8>,
+}
+
+impl IppRequest {
+    /// Decode a request, see [`Operation::try_from_ipp`]
+    ///
+    /// Fails with [`IppError::UnknownOperationId`] for an operation-id outside [`OperationID`].
+    pub fn from_ipp(bytes: &[u8]) -> Result<Self, IppError> {
+        Self::try_from(Operation::try_from_ipp(bytes)?)
+    }
+
+    pub fn to_ipp(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // writing into a Vec never fails
+        write_message(
+            &mut bytes,
+            self.version,
+            self.operation_id as u16,
+            self.request_id,
+            &self.attribute_groups,
+            &self.data,
+        )
+        .unwrap();
+        bytes
+    }
+}
+
+impl IppResponse {
+    /// Decode a response, see [`Operation::try_from_ipp`]
+    ///
+    /// Fails with [`IppError::UnknownStatusCode`] for a status-code outside [`StatusCode`].
+    pub fn from_ipp(bytes: &[u8]) -> Result<Self, IppError> {
+        Self::try_from(Operation::try_from_ipp(bytes)?)
+    }
+
+    pub fn to_ipp(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // writing into a Vec never fails
+        write_message(
+            &mut bytes,
+            self.version,
+            self.status as u16,
+            self.request_id,
+            &self.attribute_groups,
+            &self.data,
+        )
+        .unwrap();
+        bytes
+    }
+
+    /// Whether this response carries the request-id of `request`
+    pub fn matches_request(&self, request: &IppRequest) -> bool {
+        self.request_id == request.request_id
+    }
+}
+
+impl TryFrom<Operation> for IppRequest {
+    type Error = IppError;
+
+    fn try_from(operation: Operation) -> Result<Self, Self::Error> {
+        let operation_id = operation
+            .operation_id()
+            .ok_or(IppError::UnknownOperationId(
+                operation.operation_id_or_status_code,
+            ))?;
+
+        Ok(Self {
+            version: operation.version,
+            operation_id,
+            request_id: operation.request_id,
+            attribute_groups: operation.attribute_groups,
+            data: operation.data,
+        })
+    }
+}
+
+impl TryFrom<Operation> for IppResponse {
+    type Error = IppError;
+
+    fn try_from(operation: Operation) -> Result<Self, Self::Error> {
+        let status = operation.status_code().ok_or(IppError::UnknownStatusCode(
+            operation.operation_id_or_status_code,
+        ))?;
+
+        Ok(Self {
+            version: operation.version,
+            status,
+            request_id: operation.request_id,
+            attribute_groups: operation.attribute_groups,
+            data: operation.data,
+        })
+    }
+}
+
+impl From<IppRequest> for Operation {
+    fn from(request: IppRequest) -> Self {
+        Self {
+            version: request.version,
+            operation_id_or_status_code: request.operation_id as u16,
+            request_id: request.request_id,
+            attribute_groups: request.attribute_groups,
+            data: request.data,
+        }
+    }
+}
+
+impl From<IppResponse> for Operation {
+    fn from(response: IppResponse) -> Self {
+        Self {
+            version: response.version,
+            operation_id_or_status_code: response.status as u16,
+            request_id: response.request_id,
+            attribute_groups: response.attribute_groups,
+            data: response.data,
+        }
+    }
+}
+
+impl fmt::Debug for IppRequest {
+    /// same as derived, except `data` only shows its length
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IppRequest")
+            .field("version", &self.version)
+            .field("operation_id", &self.operation_id)
+            .field("request_id", &self.request_id)
+            .field("attribute_groups", &self.attribute_groups)
+            .field("data", &format_args!("[{} bytes]", self.data.len()))
+            .finish()
+    }
+}
+
+impl fmt::Debug for IppResponse {
+    /// same as derived, except `data` only shows its length
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IppResponse")
+            .field("version", &self.version)
+            .field("status", &self.status)
+            .field("request_id", &self.request_id)
+            .field("attribute_groups", &self.attribute_groups)
+            .field("data", &format_args!("[{} bytes]", self.data.len()))
+            .finish()
+    }
+}
+
+/// same layout as [`Operation::to_writer`](IppEncode::to_writer)
+fn write_message<W: Write>(
+    writer: &mut W,
+    version: IppVersion,
+    operation_id_or_status_code: u16,
+    request_id: u32,
+    attribute_groups: &Vec<AttributeGroup>,
+    data: &[u8],
+) -> std::io::Result<usize> {
+    writer.write_all(&[version.major, version.minor])?;
+    writer.write_all(&operation_id_or_status_code.to_be_bytes())?;
+    writer.write_all(&request_id.to_be_bytes())?;
+    let attribute_groups_len = attribute_groups.to_writer(writer)?;
+    writer.write_all(data)?;
+
+    Ok(8 + attribute_groups_len + data.len())
+}
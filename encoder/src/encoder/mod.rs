@@ -3,24 +3,81 @@
 //! The `encoder` module provides the `IppEncode` trait and implements
 //! encoder / decoder for IPP operations
 //!
+//! Without the `std` feature only attributes and their values are available;
+//! attribute groups, operations and 'dateTime' values require `std`.
+//!
 
 mod attribute;
+#[cfg(feature = "std")]
 mod attribute_group;
 mod attribute_name;
+mod attribute_serde;
 mod attribute_value;
+#[cfg(feature = "base64")]
+mod base64_data;
+#[cfg(feature = "std")]
+mod borrowed;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod compat;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "std")]
 mod datetime;
+mod display;
 mod error;
+#[cfg(feature = "std")]
+mod frame;
 mod ipp_version;
+#[cfg(feature = "std")]
+mod limits;
+#[cfg(feature = "std")]
+mod message;
+#[cfg(feature = "std")]
 mod operation;
+#[cfg(feature = "std")]
+mod parser;
 mod primitives;
+mod range_of_integer;
+#[cfg(feature = "std")]
+mod reader;
+mod request_id;
+mod resolution;
 mod text_with_lang;
 mod traits;
+#[cfg(feature = "std")]
+mod validate;
 
 pub use attribute::Attribute;
+#[cfg(feature = "std")]
 pub use attribute_group::AttributeGroup;
 pub use attribute_name::AttributeName;
 pub use attribute_value::AttributeValue;
+#[cfg(feature = "std")]
+pub use borrowed::{AttributeGroupRef, AttributeRef, AttributeValueRef, OperationRef};
+#[cfg(feature = "std")]
+pub use builder::{OperationBuilder, ResponseBuilder};
+#[cfg(feature = "std")]
+pub use compat::{CompatQuirks, DecodeReport, DecodeWarning};
+pub use error::{AttributeNameParseError, IppVersionParseError, ValueParseError};
+#[cfg(feature = "std")]
+pub use error::{BuildError, IppError, Severity, ValidationIssue, ValidationIssueKind};
 pub use ipp_version::IppVersion;
-pub use operation::Operation;
+#[cfg(feature = "std")]
+pub use limits::DecodeLimits;
+#[cfg(feature = "std")]
+pub use message::{IppRequest, IppResponse};
+#[cfg(feature = "std")]
+pub use operation::{Operation, ParsedHeader};
+#[cfg(feature = "std")]
+pub use parser::{OperationParser, ParseProgress};
+pub use range_of_integer::RangeOfInteger;
+pub use request_id::RequestIdGenerator;
+pub use resolution::Resolution;
 pub use text_with_lang::TextWithLang;
+#[cfg(any(test, feature = "test-support"))]
+pub use traits::assert_ipp_len_matches;
+#[cfg(feature = "std")]
+pub use traits::IppDecode;
 pub use traits::IppEncode;
@@ -1,18 +1,28 @@
 use crate::spec::{
+    attribute::{JobAttribute, JobTemplateAttribute, OperationAttribute},
     operation::{OperationID, StatusCode},
-    tag::DelimiterTag,
+    tag::{DelimiterTag, ValueTag},
+    value::SidesKeyword,
 };
 
-use super::{AttributeGroup, IppEncode, IppVersion};
+use super::{
+    compat::{is_charset_not_first, repeated_names},
+    frame::{Field, FrameScanner},
+    reader::ByteReader,
+    Attribute, AttributeGroup, AttributeName, AttributeValue, CompatQuirks, DecodeLimits,
+    DecodeReport, DecodeWarning, IppEncode, IppError, IppVersion, OperationRef,
+};
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, DisplayFromStr};
-use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+use std::ops::Range;
 
 ///
 /// Operation request or response
 ///
-/// ```
+/// ```text
 /// -----------------------------------------------
 /// |                  version-number             |   2 bytes  - required
 /// -----------------------------------------------
@@ -32,98 +42,112 @@ use std::collections::HashMap;
 ///
 /// ----------------------------------------------------------
 ///
+/// Equality compares attribute groups in order, see [`AttributeGroup`] for attributes.
+///
 /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.1)
 ///
-#[serde_as]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Operation {
     pub version: IppVersion,
     pub operation_id_or_status_code: u16,
     pub request_id: u32,
-    #[serde_as(as = "HashMap<DisplayFromStr, _>")]
-    pub attribute_groups: HashMap<DelimiterTag, AttributeGroup>,
-    #[serde(skip)]
+    /// attribute groups in wire order, a tag may repeat (e.g. job-attributes in Get-Jobs)
+    pub attribute_groups: Vec<AttributeGroup>,
     /// additional data in trailing bytes
+    ///
+    /// Left out of serialization unless the `base64` feature is enabled
+    #[cfg_attr(not(feature = "base64"), serde(skip))]
+    #[cfg_attr(feature = "base64", serde(with = "super::base64_data", default))]
     pub data: Vec<u8>,
 }
 
-impl IppEncode for Operation {
-    fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
-        let mut shifting_offset = offset;
+/// Header and attribute groups of an [`Operation`], without its document data
+///
+/// Returned by [`Operation::parse_header`] along with the offset of the data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ParsedHeader {
+    pub version: IppVersion,
+    pub operation_id_or_status_code: u16,
+    pub request_id: u32,
+    /// attribute groups in wire order, a tag may repeat (e.g. job-attributes in Get-Jobs)
+    pub attribute_groups: Vec<AttributeGroup>,
+}
 
-        // read version.major
-        let slice: [u8; 1] = bytes[shifting_offset..shifting_offset + 1]
-            .try_into()
-            .unwrap();
-        let major = u8::from_be_bytes(slice);
-        shifting_offset += slice.len();
+impl fmt::Debug for Operation {
+    /// same as derived, except `data` only shows its length
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Operation")
+            .field("version", &self.version)
+            .field(
+                "operation_id_or_status_code",
+                &self.operation_id_or_status_code,
+            )
+            .field("request_id", &self.request_id)
+            .field("attribute_groups", &self.attribute_groups)
+            .field("data", &format_args!("[{} bytes]", self.data.len()))
+            .finish()
+    }
+}
 
-        // read version.minor
-        let slice: [u8; 1] = bytes[shifting_offset..shifting_offset + 1]
-            .try_into()
-            .unwrap();
-        let minor = u8::from_be_bytes(slice);
-        shifting_offset += slice.len();
+impl ParsedHeader {
+    pub fn operation_id(&self) -> Option<OperationID> {
+        OperationID::from_repr(self.operation_id_or_status_code as usize)
+    }
+    pub fn status_code(&self) -> Option<StatusCode> {
+        StatusCode::from_repr(self.operation_id_or_status_code as usize)
+    }
 
-        // read operation-id or status-code
-        let slice: [u8; 2] = bytes[shifting_offset..shifting_offset + 2]
-            .try_into()
-            .unwrap();
-        let operation_id_or_status_code = u16::from_be_bytes(slice);
-        shifting_offset += slice.len();
+    /// First attribute group with the given tag
+    pub fn attribute_group(&self, tag: DelimiterTag) -> Option<&AttributeGroup> {
+        self.attribute_groups.iter().find(|group| group.tag == tag)
+    }
+}
 
-        // read request-id
-        let slice: [u8; 4] = bytes[shifting_offset..shifting_offset + 4]
-            .try_into()
-            .unwrap();
-        let request_id = u32::from_be_bytes(slice);
-        shifting_offset += slice.len();
-
-        // read attribute groups
-        let (delta, attribute_groups): (usize, HashMap<DelimiterTag, AttributeGroup>) =
-            HashMap::from_ipp(bytes, shifting_offset);
-        shifting_offset += delta;
-
-        // read additional data (trailing bytes)
-        let data = (&bytes[shifting_offset..]).to_vec();
-
-        (
-            shifting_offset - offset,
-            Self {
-                version: IppVersion { major, minor },
-                request_id,
-                operation_id_or_status_code,
-                attribute_groups,
-                data,
-            },
-        )
+impl Operation {
+    /// [`IppEncode::from_ipp`], also telling whether the attribute groups ended
+    /// on an end-of-attributes tag
+    fn decode(bytes: &[u8], offset: usize) -> (usize, Self, bool) {
+        // decoded borrowing first, so both decoders share the group and attribute handling;
+        // the document data is not part of the decoded length
+        let (borrowed, terminated) = OperationRef::decode(&bytes[offset..]);
+        let delta = bytes.len() - offset - borrowed.data.len();
+
+        (delta, borrowed.into_owned(), terminated)
+    }
+}
+
+impl IppEncode for Operation {
+    fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
+        let (delta, operation, _) = Self::decode(bytes, offset);
+        (delta, operation)
     }
 
     fn to_ipp(&self) -> Vec<u8> {
-        // write version major
-        let major_bytes = self.version.major.to_be_bytes().to_vec();
+        let len = self.ipp_len();
+        let mut bytes = Vec::with_capacity(len);
+        self.to_ipp_into(&mut bytes);
+        debug_assert_eq!(bytes.len(), len, "ipp_len disagrees with to_ipp");
+        bytes
+    }
 
-        // write version minor
-        let minor_bytes = self.version.minor.to_be_bytes().to_vec();
+    fn to_writer<W: Write>(&self, writer: &mut W) -> std::io::Result<usize> {
+        // write version major & minor
+        writer.write_all(&self.version.major.to_be_bytes())?;
+        writer.write_all(&self.version.minor.to_be_bytes())?;
 
         // write operation-id or status-code
-        let operation_or_status_bytes = self.operation_id_or_status_code.to_be_bytes().to_vec();
+        writer.write_all(&self.operation_id_or_status_code.to_be_bytes())?;
 
         // write request-id
-        let request_id_bytes = self.request_id.to_be_bytes().to_vec();
+        writer.write_all(&self.request_id.to_be_bytes())?;
 
         // write attribute groups
-        let attribute_groups_bytes = self.attribute_groups.to_ipp();
+        let attribute_groups_len = self.attribute_groups.to_writer(writer)?;
+
+        // write trailing data
+        writer.write_all(&self.data)?;
 
-        [
-            major_bytes,
-            minor_bytes,
-            operation_or_status_bytes,
-            request_id_bytes,
-            attribute_groups_bytes,
-            self.data.to_vec(),
-        ]
-        .concat()
+        Ok(8 + attribute_groups_len + self.data.len())
     }
 
     fn ipp_len(&self) -> usize {
@@ -136,6 +160,305 @@ impl IppEncode for Operation {
     }
 }
 
+impl super::IppDecode for Operation {
+    fn min_len() -> usize {
+        // header and end-of-attributes tag
+        8 + 1
+    }
+
+    /// Same as [`Operation::try_from_ipp`], from `offset`
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        check_operation(bytes, offset, &DecodeLimits::default())?;
+
+        let (delta, operation, terminated) = Self::decode(bytes, offset);
+        if !terminated {
+            return Err(IppError::MissingEndOfAttributes);
+        }
+
+        Ok((delta, operation))
+    }
+}
+
+// constructor with an empty operation-attributes group
+impl Operation {
+    pub fn new_request(version: IppVersion, operation_id: OperationID, request_id: u32) -> Self {
+        Self::with_operation_group(version, operation_id as u16, request_id)
+    }
+
+    pub fn new_response(version: IppVersion, status_code: StatusCode, request_id: u32) -> Self {
+        Self::with_operation_group(version, status_code as u16, request_id)
+    }
+
+    /// Response echoing the request-id of `request`
+    ///
+    /// The version is the request's, clamped to [`IppVersion::KNOWN`]. The operation-attributes
+    /// group holds 'attributes-charset' and 'attributes-natural-language' of the request,
+    /// or utf-8 and en-US where the request has none.
+    pub fn response_to(request: &Operation, status_code: StatusCode) -> Self {
+        let mut response = Self::new_response(
+            request.version.clamp_to_supported(&IppVersion::KNOWN),
+            status_code,
+            request.request_id,
+        );
+        response.set_attributes_charset(request.attributes_charset().unwrap_or("utf-8"));
+        response.set_attributes_natural_language(
+            request.attributes_natural_language().unwrap_or("en-US"),
+        );
+
+        response
+    }
+
+    /// [`Operation::response_to`] with an error `status_code`, and 'status-message' if any
+    ///
+    /// For 'client-error-charset-not-supported' the response is in utf-8
+    /// rather than the charset of the request.
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.4.1)
+    pub fn error_response(
+        request: &Operation,
+        status_code: StatusCode,
+        status_message: Option<&str>,
+    ) -> Self {
+        let mut response = Self::response_to(request, status_code);
+        if status_code == StatusCode::ClientErrorCharsetNotSupported {
+            response.set_attributes_charset("utf-8");
+        }
+        if let Some(status_message) = status_message {
+            response.set_status_message(status_message);
+        }
+
+        response
+    }
+
+    fn with_operation_group(
+        version: IppVersion,
+        operation_id_or_status_code: u16,
+        request_id: u32,
+    ) -> Self {
+        Self {
+            version,
+            operation_id_or_status_code,
+            request_id,
+            attribute_groups: vec![AttributeGroup {
+                tag: DelimiterTag::OperationAttributes,
+                attributes: IndexMap::new(),
+            }],
+            data: Vec::new(),
+        }
+    }
+
+    /// The operation-attributes group, inserted first if there is none
+    pub fn operation_attributes_mut(&mut self) -> &mut AttributeGroup {
+        let position = match self
+            .attribute_groups
+            .iter()
+            .position(|group| group.tag == DelimiterTag::OperationAttributes)
+        {
+            Some(position) => position,
+            None => {
+                self.attribute_groups.insert(
+                    0,
+                    AttributeGroup {
+                        tag: DelimiterTag::OperationAttributes,
+                        attributes: IndexMap::new(),
+                    },
+                );
+                0
+            }
+        };
+
+        &mut self.attribute_groups[position]
+    }
+
+    /// Attributes of the first operation-attributes group in the order they were decoded
+    /// or inserted, empty without one
+    ///
+    /// Encoding always puts 'attributes-charset' and 'attributes-natural-language' first,
+    /// this tells where a request decoded with [`CompatQuirks::charset_not_first`] had them.
+    pub fn operation_attributes_ordered(&self) -> impl Iterator<Item = &Attribute> + '_ {
+        self.attribute_group(DelimiterTag::OperationAttributes)
+            .into_iter()
+            .flat_map(|group| group.attributes.values())
+    }
+
+    /// Set 'attributes-charset' in the operation-attributes group
+    pub fn set_attributes_charset(&mut self, charset: &str) {
+        self.set_operation_attribute(Attribute::charset(
+            OperationAttribute::AttributesCharset,
+            charset,
+        ));
+    }
+
+    /// Set 'attributes-natural-language' in the operation-attributes group
+    pub fn set_attributes_natural_language(&mut self, natural_language: &str) {
+        self.set_operation_attribute(Attribute::natural_language(
+            OperationAttribute::AttributesNaturalLanguage,
+            natural_language,
+        ));
+    }
+
+    /// Set 'status-message' in the operation-attributes group,
+    /// the human-readable explanation of a response's status-code
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.6.2)
+    pub fn set_status_message(&mut self, status_message: &str) {
+        self.set_operation_attribute(Attribute::text(
+            OperationAttribute::StatusMessage,
+            status_message,
+        ));
+    }
+
+    fn set_operation_attribute(&mut self, attribute: Attribute) {
+        self.insert_attribute(DelimiterTag::OperationAttributes, attribute);
+    }
+}
+
+// common attributes of the operation-attributes group
+impl Operation {
+    pub fn attributes_charset(&self) -> Option<&str> {
+        self.operation_str(OperationAttribute::AttributesCharset)
+    }
+
+    pub fn attributes_natural_language(&self) -> Option<&str> {
+        self.operation_str(OperationAttribute::AttributesNaturalLanguage)
+    }
+
+    pub fn printer_uri(&self) -> Option<&str> {
+        self.operation_str(OperationAttribute::PrinterUri)
+    }
+
+    pub fn status_message(&self) -> Option<&str> {
+        self.operation_str(OperationAttribute::StatusMessage)
+    }
+
+    pub fn requesting_user_name(&self) -> Option<&str> {
+        self.operation_str(OperationAttribute::RequestingUserName)
+    }
+
+    pub fn job_name(&self) -> Option<&str> {
+        self.operation_str(JobAttribute::JobName)
+    }
+
+    pub fn job_id(&self) -> Option<i32> {
+        self.attribute(DelimiterTag::OperationAttributes, JobAttribute::JobId)
+            .and_then(Attribute::first_i32)
+    }
+
+    pub fn document_format(&self) -> Option<&str> {
+        self.operation_str(OperationAttribute::DocumentFormat)
+    }
+
+    /// 'sides' of the job-attributes group, None without one or for a keyword
+    /// [`SidesKeyword`] does not list
+    pub fn sides(&self) -> Option<SidesKeyword> {
+        self.attribute(DelimiterTag::JobAttributes, JobTemplateAttribute::Sides)?
+            .as_keywords()
+            .next()?
+            .ok()
+    }
+
+    /// Keywords of 'requested-attributes', empty when the request has none
+    pub fn requested_attributes(&self) -> Vec<&str> {
+        self.attribute(
+            DelimiterTag::OperationAttributes,
+            OperationAttribute::RequestedAttributes,
+        )
+        .map(|attribute| attribute.as_strings().collect())
+        .unwrap_or_default()
+    }
+
+    fn operation_str(&self, name: impl Into<AttributeName>) -> Option<&str> {
+        self.attribute(DelimiterTag::OperationAttributes, name)
+            .and_then(Attribute::first_str)
+    }
+}
+
+// attribute lookup by group and name, the first group of the tag is used
+impl Operation {
+    pub fn attribute(
+        &self,
+        group: DelimiterTag,
+        name: impl Into<AttributeName>,
+    ) -> Option<&Attribute> {
+        self.attribute_group(group)?.attributes.get(&name.into())
+    }
+
+    pub fn attribute_values(
+        &self,
+        group: DelimiterTag,
+        name: impl Into<AttributeName>,
+    ) -> Option<&[AttributeValue]> {
+        self.attribute(group, name)
+            .map(|attribute| attribute.values.as_slice())
+    }
+
+    /// Attributes of every group, groups in wire order, each attribute with its group tag
+    pub fn attributes(&self) -> impl Iterator<Item = (DelimiterTag, &Attribute)> + '_ {
+        self.attribute_groups.iter().flat_map(|attribute_group| {
+            attribute_group
+                .attributes
+                .values()
+                .map(move |attribute| (attribute_group.tag, attribute))
+        })
+    }
+
+    /// First attribute named `name` in any group, with the tag of its group
+    pub fn find_attribute(
+        &self,
+        name: impl Into<AttributeName>,
+    ) -> Option<(DelimiterTag, &Attribute)> {
+        let name = name.into();
+        self.attribute_groups.iter().find_map(|attribute_group| {
+            attribute_group
+                .attributes
+                .get(&name)
+                .map(|attribute| (attribute_group.tag, attribute))
+        })
+    }
+
+    pub fn attribute_mut(
+        &mut self,
+        group: DelimiterTag,
+        name: impl Into<AttributeName>,
+    ) -> Option<&mut Attribute> {
+        self.attribute_groups
+            .iter_mut()
+            .find(|attribute_group| attribute_group.tag == group)?
+            .attributes
+            .get_mut(&name.into())
+    }
+
+    /// Insert into the group of tag `group`, returning the attribute it replaces
+    ///
+    /// A missing group is created: operation-attributes first, any other at the end
+    pub fn insert_attribute(
+        &mut self,
+        group: DelimiterTag,
+        attribute: Attribute,
+    ) -> Option<Attribute> {
+        if group == DelimiterTag::OperationAttributes {
+            return self.operation_attributes_mut().insert(attribute);
+        }
+
+        let position = match self
+            .attribute_groups
+            .iter()
+            .position(|attribute_group| attribute_group.tag == group)
+        {
+            Some(position) => position,
+            None => {
+                self.attribute_groups.push(AttributeGroup {
+                    tag: group,
+                    attributes: IndexMap::new(),
+                });
+                self.attribute_groups.len() - 1
+            }
+        };
+
+        self.attribute_groups[position].insert(attribute)
+    }
+}
+
 impl Operation {
     pub fn operation_id(&self) -> Option<OperationID> {
         OperationID::from_repr(self.operation_id_or_status_code as usize)
@@ -144,8 +467,1257 @@ impl Operation {
         StatusCode::from_repr(self.operation_id_or_status_code as usize)
     }
 
-    pub fn to_json(&self) -> String {
-        // FIXME: handle error gracefully
-        serde_json::to_string(self).unwrap()
+    /// Registry name of the operation-id, "unknown(0x....)" for ids outside [`OperationID`]
+    pub fn operation_name(&self) -> String {
+        match self.operation_id() {
+            Some(operation_id) => String::from(operation_id.name()),
+            None => format!("unknown({:#06x})", self.operation_id_or_status_code),
+        }
+    }
+
+    /// Same as [`IppEncode::from_ipp`], additionally applying `quirks`
+    /// and reporting irregularities found in the decoded operation
+    pub fn from_ipp_with_quirks(
+        bytes: &[u8],
+        offset: usize,
+        quirks: &CompatQuirks,
+    ) -> (usize, Self, DecodeReport) {
+        // repeated names are only told apart in wire order
+        let (delta, mut operation, repeated) = if quirks.repeated_names_as_values {
+            let mut borrowed = OperationRef::parse(&bytes[offset..]);
+            let repeated = repeated_names(&mut borrowed, true);
+            (bytes.len() - offset, borrowed.into_owned(), repeated)
+        } else {
+            let (delta, operation) = Self::from_ipp(bytes, offset);
+            (delta, operation, Vec::new())
+        };
+
+        let mut report = quirks.apply(&mut operation);
+        report
+            .warnings
+            .extend(repeated.into_iter().map(DecodeWarning::RepeatedName));
+
+        (delta, operation, report)
+    }
+
+    /// Fallible counterpart of [`Operation::from_ipp_with_quirks`], never panics
+    ///
+    /// Also checks that 'attributes-charset' comes first in the operation-attributes group,
+    /// failing with [`IppError::CharsetNotFirst`] unless [`CompatQuirks::charset_not_first`]
+    /// is set, in which case it is reported as [`DecodeWarning::CharsetNotFirst`].
+    pub fn try_from_ipp_with_quirks(
+        bytes: &[u8],
+        quirks: &CompatQuirks,
+    ) -> Result<(Self, DecodeReport), IppError> {
+        let mut borrowed = OperationRef::from_ipp(bytes)?;
+        let charset_not_first = is_charset_not_first(&borrowed);
+        if charset_not_first && !quirks.charset_not_first {
+            return Err(IppError::CharsetNotFirst);
+        }
+        let repeated = repeated_names(&mut borrowed, quirks.repeated_names_as_values);
+
+        let mut operation = borrowed.into_owned();
+        let mut report = quirks.apply(&mut operation);
+        if charset_not_first {
+            report.warnings.push(DecodeWarning::CharsetNotFirst);
+        }
+        report
+            .warnings
+            .extend(repeated.into_iter().map(DecodeWarning::RepeatedName));
+
+        Ok((operation, report))
+    }
+
+    /// First attribute group with the given tag
+    pub fn attribute_group(&self, tag: DelimiterTag) -> Option<&AttributeGroup> {
+        self.attribute_groups.iter().find(|group| group.tag == tag)
+    }
+
+    /// Whether this response has a successful status-code, vendor codes included
+    ///
+    /// Judged by [`StatusCode::SUCCESSFUL`] rather than by the codes [`StatusCode`] lists.
+    pub fn is_successful_response(&self) -> bool {
+        StatusCode::SUCCESSFUL.contains(&self.operation_id_or_status_code)
+    }
+
+    /// Whether this response carries the request-id of `request`
+    pub fn matches_request(&self, request: &Operation) -> bool {
+        self.request_id == request.request_id
+    }
+
+    /// Required response attributes of `operation_id` that this response lacks
+    ///
+    /// See [`OperationID::required_response_attributes`]. Intended as a debug assertion
+    /// before sending a successful response.
+    pub fn missing_response_attributes(
+        &self,
+        operation_id: OperationID,
+    ) -> Vec<(DelimiterTag, AttributeName)> {
+        operation_id
+            .required_response_attributes()
+            .into_iter()
+            .map(|name| (name.group(), AttributeName::from(name)))
+            .filter(|(tag, name)| {
+                !self
+                    .attribute_groups
+                    .iter()
+                    .any(|group| group.tag == *tag && group.attributes.contains_key(name))
+            })
+            .collect()
+    }
+
+    /// Minimal 'client-error-bad-request' response for a request that could not be decoded
+    ///
+    /// Nothing can be echoed from such a request, so the response uses version 1.1,
+    /// request-id 0 and an operation-attributes group with only
+    /// 'attributes-charset' (utf-8) and 'attributes-natural-language' (en-US).
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.4.2)
+    pub fn bad_request_fallback() -> Self {
+        let mut response =
+            Self::new_response(IppVersion::V1_1, StatusCode::ClientErrorBadRequest, 0);
+        response.set_attributes_charset("utf-8");
+        response.set_attributes_natural_language("en-US");
+        response
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Same as [`Operation::to_json`], indented for human inspection
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Inverse of [`Operation::to_json`]; `data` is not serialized and is left empty
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Fallible counterpart of [`IppEncode::from_ipp`], never panics
+    ///
+    /// Every field is checked to lie within `bytes` and every value to match its value-tag
+    /// before decoding, and the attribute groups must end on an end-of-attributes tag.
+    /// The whole of `bytes` is consumed, trailing bytes become `data`.
+    ///
+    /// The structure is bounded by the default [`DecodeLimits`].
+    pub fn try_from_ipp(bytes: &[u8]) -> Result<Self, IppError> {
+        Self::try_from_ipp_with_limits(bytes, &DecodeLimits::default())
+    }
+
+    /// Same as [`Operation::try_from_ipp`], bounded by `limits` instead of the default ones
+    pub fn try_from_ipp_with_limits(bytes: &[u8], limits: &DecodeLimits) -> Result<Self, IppError> {
+        check_operation(bytes, 0, limits)?;
+
+        let (_, operation, terminated) = Self::decode(bytes, 0);
+        if !terminated {
+            return Err(IppError::MissingEndOfAttributes);
+        }
+
+        Ok(operation)
+    }
+
+    /// Decode the header and attribute groups, leaving the document data in `bytes`
+    ///
+    /// The returned offset points just past the end-of-attributes tag, so
+    /// `&bytes[offset..]` is the document data; it is `bytes.len()` when there is no data.
+    /// The data itself is never read or copied.
+    ///
+    /// Fails if `bytes` ends before the end-of-attributes tag or contains an unknown tag.
+    pub fn parse_header(bytes: &[u8]) -> Result<(ParsedHeader, usize), IppError> {
+        let mut reader = bytes;
+        let operation = Self::from_reader(&mut reader)?;
+        let offset = bytes.len() - reader.len();
+
+        Ok((
+            ParsedHeader {
+                version: operation.version,
+                operation_id_or_status_code: operation.operation_id_or_status_code,
+                request_id: operation.request_id,
+                attribute_groups: operation.attribute_groups,
+            },
+            offset,
+        ))
+    }
+
+    /// Decode an operation from a stream without buffering the document data
+    ///
+    /// Only the header and attribute groups are read, each field consuming exactly
+    /// as many bytes as it declares. Reading stops right after the end-of-attributes tag,
+    /// leaving `reader` positioned at the start of the document data; `data` is left empty.
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Self, IppError> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut scanner = FrameScanner::new(0, &DecodeLimits::default());
+
+        loop {
+            match scanner.next_field(&bytes)? {
+                Field::NeedMore(len) => {
+                    let start = bytes.len();
+                    bytes.resize(len, 0);
+                    reader.read_exact(&mut bytes[start..])?;
+                }
+                Field::End(_) => break,
+                Field::Header | Field::Delimiter | Field::Attribute { .. } => {}
+            }
+        }
+
+        // framing is complete, check the values before decoding them
+        check_bounds(&bytes)?;
+        let (_, operation) = Self::from_ipp(&bytes, 0);
+
+        Ok(operation)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Operation {
+    /// Async counterpart of [`Operation::from_reader`]
+    ///
+    /// Reads the header and attribute groups as bytes arrive and stops right after
+    /// the end-of-attributes tag, leaving the document data unread in `reader`.
+    pub async fn from_async_reader<R>(reader: &mut R) -> Result<Self, IppError>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut scanner = FrameScanner::new(0, &DecodeLimits::default());
+
+        loop {
+            match scanner.next_field(&bytes)? {
+                Field::NeedMore(len) => {
+                    let start = bytes.len();
+                    bytes.resize(len, 0);
+                    reader.read_exact(&mut bytes[start..]).await?;
+                }
+                Field::End(_) => break,
+                Field::Header | Field::Delimiter | Field::Attribute { .. } => {}
+            }
+        }
+
+        // framing is complete, check the values before decoding them
+        check_bounds(&bytes)?;
+        let (_, operation) = Self::from_ipp(&bytes, 0);
+
+        Ok(operation)
+    }
+
+    /// Async counterpart of [`IppEncode::to_writer`]
+    ///
+    /// The operation is encoded into a single pre-sized buffer, then written to `writer`.
+    pub async fn to_async_writer<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let bytes = self.to_ipp();
+        writer.write_all(&bytes).await?;
+
+        Ok(bytes.len())
+    }
+}
+
+impl Operation {
+    /// Decode several requests sent back-to-back on one connection
+    ///
+    /// Each request ends at its end-of-attributes tag. Everything after a request whose
+    /// operation carries document data (see [`OperationID::carries_document_data`]) is
+    /// its `data`, so such a request is always the last one; otherwise the following
+    /// bytes must decode as the next request.
+    ///
+    /// Returns each operation along with the byte range it spans in `bytes`,
+    /// or the error of the first request that cannot be decoded.
+    pub fn from_ipp_multi(bytes: &[u8]) -> Result<Vec<(Range<usize>, Self)>, IppError> {
+        let mut operations = Vec::new();
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let mut reader = &bytes[offset..];
+            let mut operation = Self::from_reader(&mut reader)?;
+
+            if operation
+                .operation_id()
+                .is_some_and(OperationID::carries_document_data)
+            {
+                operation.data = reader.to_vec();
+                operations.push((offset..bytes.len(), operation));
+                break;
+            }
+
+            let end = bytes.len() - reader.len();
+            operations.push((offset..end, operation));
+            offset = end;
+        }
+
+        Ok(operations)
+    }
+}
+
+/// check that header and attribute groups up to the end-of-attributes tag can be decoded,
+/// within the default [`DecodeLimits`]
+pub(crate) fn check_bounds(bytes: &[u8]) -> Result<(), IppError> {
+    check_operation(bytes, 0, &DecodeLimits::default())
+}
+
+/// [`check_bounds`] for an operation starting at `offset`, within `limits`
+fn check_operation(bytes: &[u8], offset: usize, limits: &DecodeLimits) -> Result<(), IppError> {
+    check_fields(bytes, FrameScanner::new(offset, limits))
+}
+
+/// version-number, operation-id or status-code and request-id
+pub(crate) fn read_header(reader: &mut ByteReader) -> Result<(IppVersion, u16, u32), IppError> {
+    let version = IppVersion {
+        major: reader.read_u8()?,
+        minor: reader.read_u8()?,
+    };
+
+    Ok((version, reader.read_u16_be()?, reader.read_u32_be()?))
+}
+
+/// check that the version-number at `offset` could be IPP at all (major 1 to 3)
+///
+/// Anything else is most likely another protocol reaching the IPP port,
+/// e.g. a plain HTTP request or a TLS handshake.
+pub(crate) fn check_version(bytes: &[u8], offset: usize) -> Result<(), IppError> {
+    let version = IppVersion {
+        major: bytes[offset],
+        minor: bytes[offset + 1],
+    };
+
+    if (1..=3).contains(&version.major) {
+        Ok(())
+    } else {
+        Err(IppError::NotIpp(version))
+    }
+}
+
+/// check that attribute groups from `offset` up to the end-of-attributes tag can be decoded
+/// and stay within `limits`
+pub(crate) fn check_groups(
+    bytes: &[u8],
+    offset: usize,
+    limits: &DecodeLimits,
+) -> Result<(), IppError> {
+    check_fields(bytes, FrameScanner::groups(offset, limits))
+}
+
+/// check every field `scanner` frames in `bytes`, up to the end-of-attributes tag
+fn check_fields(bytes: &[u8], mut scanner: FrameScanner) -> Result<(), IppError> {
+    loop {
+        match scanner.next_field(bytes)? {
+            Field::NeedMore(needed) => return Err(scanner.incomplete(bytes, needed)),
+            Field::Attribute {
+                field_offset,
+                value_tag,
+                name,
+                value,
+            } => {
+                if std::str::from_utf8(&bytes[name]).is_err() {
+                    return Err(IppError::InvalidUtf8(field_offset));
+                }
+                if !AttributeValue::is_valid(value_tag, &bytes[value]) {
+                    return Err(invalid_value(value_tag, field_offset));
+                }
+            }
+            Field::End(_) => return Ok(()),
+            Field::Header | Field::Delimiter => {}
+        }
+    }
+}
+
+/// the bytes declared by the 2-byte length at `offset`, an error for the field
+/// at `field_offset` if they run past `bytes`
+pub(crate) fn length_prefixed(
+    bytes: &[u8],
+    offset: usize,
+    field_offset: usize,
+) -> Result<&[u8], IppError> {
+    ByteReader::for_field(bytes, offset, field_offset).read_length_prefixed()
+}
+
+/// the most specific error for a value failing [`AttributeValue::is_valid`]
+fn invalid_value(value_tag: ValueTag, field_offset: usize) -> IppError {
+    match value_tag {
+        ValueTag::DateTime => IppError::InvalidDateTime(field_offset),
+        ValueTag::TextWithLanguage
+        | ValueTag::NameWithLanguage
+        | ValueTag::Resolution
+        | ValueTag::RangeOfInteger => IppError::InvalidValue(field_offset),
+        tag if tag.is_integer_type() => IppError::InvalidValue(field_offset),
+        // every other value is a character-string, which can only fail on UTF-8
+        _ => IppError::InvalidUtf8(field_offset),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{assert_ipp_len_matches, OperationParser, ParseProgress};
+    use crate::spec::value::OrientationRequested;
+
+    fn header() -> Vec<u8> {
+        vec![1, 1, 0x00, 0x0b, 0, 0, 0, 1]
+    }
+
+    fn push_field(bytes: &mut Vec<u8>, tag: u8, name: &str, value: &[u8]) {
+        bytes.push(tag);
+        bytes.extend((name.len() as u16).to_be_bytes());
+        bytes.extend(name.as_bytes());
+        bytes.extend((value.len() as u16).to_be_bytes());
+        bytes.extend(value);
+    }
+
+    /// a request whose 'media-col' nests `depth` collections
+    fn nested_collections(depth: usize) -> Vec<u8> {
+        let mut bytes = header();
+        bytes.push(DelimiterTag::JobAttributes as u8);
+        push_field(&mut bytes, 0x34, "media-col", &[]);
+        for _ in 1..depth {
+            push_field(&mut bytes, 0x4a, "", b"media-col");
+            push_field(&mut bytes, 0x34, "", &[]);
+        }
+        for _ in 0..depth {
+            push_field(&mut bytes, 0x37, "", &[]);
+        }
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        bytes
+    }
+
+    fn exceeded(result: Result<Operation, IppError>) -> Option<(&'static str, usize)> {
+        match result {
+            Err(IppError::LimitExceeded { limit, max }) => Some((limit, max)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn deep_collections_are_refused_before_recursing() {
+        let limits = DecodeLimits::default();
+        assert!(Operation::try_from_ipp(&nested_collections(limits.max_collection_depth)).is_ok());
+
+        // deep enough to overflow the stack if it were decoded
+        let bomb = nested_collections(200_000);
+        let expected = Some(("max_collection_depth", limits.max_collection_depth));
+        assert_eq!(exceeded(Operation::try_from_ipp(&bomb)), expected);
+        assert_eq!(exceeded(Operation::from_reader(&mut &bomb[..])), expected);
+        assert!(matches!(
+            Operation::try_parse(&bomb),
+            Err(IppError::LimitExceeded { .. })
+        ));
+
+        let shallow = DecodeLimits {
+            max_collection_depth: 2,
+            ..limits
+        };
+        assert_eq!(
+            exceeded(Operation::try_from_ipp_with_limits(
+                &nested_collections(3),
+                &shallow
+            )),
+            Some(("max_collection_depth", 2))
+        );
+    }
+
+    #[test]
+    fn attributes_and_groups_are_limited() {
+        let limits = DecodeLimits {
+            max_attributes_per_group: 2,
+            max_groups: 2,
+            ..DecodeLimits::default()
+        };
+
+        let mut bytes = header();
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        // additional values are not attributes of their own
+        push_field(&mut bytes, 0x48, "", b"fr");
+        let mut two_attributes = bytes.clone();
+        two_attributes.push(DelimiterTag::EndOfAttributes as u8);
+        assert!(Operation::try_from_ipp_with_limits(&two_attributes, &limits).is_ok());
+
+        push_field(&mut bytes, 0x45, "printer-uri", b"ipp://localhost");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        assert_eq!(
+            exceeded(Operation::try_from_ipp_with_limits(&bytes, &limits)),
+            Some(("max_attributes_per_group", 2))
+        );
+
+        let mut groups = header();
+        groups.extend([DelimiterTag::OperationAttributes as u8; 3]);
+        groups.push(DelimiterTag::EndOfAttributes as u8);
+        assert_eq!(
+            exceeded(Operation::try_from_ipp_with_limits(&groups, &limits)),
+            Some(("max_groups", 2))
+        );
+    }
+
+    #[test]
+    fn additional_value_first_in_a_group_is_refused() {
+        let mut bytes = header();
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        bytes.push(DelimiterTag::JobAttributes as u8);
+        let orphan = bytes.len();
+        push_field(&mut bytes, 0x44, "", b"iso_a4_210x297mm");
+        push_field(&mut bytes, 0x44, "media", b"na_letter_8.5x11in");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let refused = |result: Result<(), IppError>| matches!(result, Err(IppError::AdditionalValueWithoutAttribute(offset)) if offset == orphan);
+        assert!(refused(Operation::try_from_ipp(&bytes).map(drop)));
+        assert!(refused(Operation::try_parse(&bytes).map(drop)));
+        assert!(refused(Operation::from_reader(&mut &bytes[..]).map(drop)));
+        assert!(refused(OperationParser::new().push(&bytes).map(drop)));
+
+        // the same value after the attribute it belongs to
+        let mut bytes = header();
+        bytes.push(DelimiterTag::JobAttributes as u8);
+        push_field(&mut bytes, 0x44, "media", b"na_letter_8.5x11in");
+        push_field(&mut bytes, 0x44, "", b"iso_a4_210x297mm");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        let operation = Operation::try_from_ipp(&bytes).unwrap();
+        let media = operation
+            .attribute(DelimiterTag::JobAttributes, JobTemplateAttribute::Media)
+            .unwrap();
+        assert_eq!(media.values.len(), 2);
+        assert_eq!(
+            Operation::try_parse(&bytes).unwrap().into_owned(),
+            operation
+        );
+    }
+
+    /// endless begin-attribute-group-tags after a header
+    struct EndlessGroups {
+        header: Vec<u8>,
+    }
+
+    impl Read for EndlessGroups {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let header = self.header.len().min(buf.len());
+            buf[..header].copy_from_slice(&self.header[..header]);
+            self.header.drain(..header);
+            buf[header..].fill(DelimiterTag::OperationAttributes as u8);
+            Ok(buf.len())
+        }
+    }
+
+    #[test]
+    fn streams_stop_at_the_limit() {
+        let max_groups = DecodeLimits::default().max_groups;
+
+        let mut reader = EndlessGroups { header: header() };
+        assert_eq!(
+            exceeded(Operation::from_reader(&mut reader)),
+            Some(("max_groups", max_groups))
+        );
+
+        let mut parser = OperationParser::new();
+        let chunk = [DelimiterTag::OperationAttributes as u8; 1024];
+        assert!(matches!(
+            parser.push(&header()),
+            Ok(ParseProgress::NeedMore)
+        ));
+        let result = (0..).find_map(|_| parser.push(&chunk).err()).unwrap();
+        assert!(matches!(
+            result,
+            IppError::LimitExceeded {
+                limit: "max_groups",
+                ..
+            }
+        ));
+    }
+
+    /// request with `operation_id` and its operation-attributes
+    fn request(operation_id: u16) -> Vec<u8> {
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&operation_id.to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        push_field(
+            &mut bytes,
+            0x45,
+            "printer-uri",
+            b"ipp://localhost/ipp/print",
+        );
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        bytes
+    }
+
+    #[test]
+    fn multi_decodes_concatenated_get_printer_attributes() {
+        let first = request(0x000b);
+        let mut second = request(0x000b);
+        // any version check_version accepts starts a message
+        second[0] = 2;
+        let bytes = [first.clone(), second].concat();
+
+        let operations = Operation::from_ipp_multi(&bytes).unwrap();
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].0, 0..first.len());
+        assert_eq!(operations[1].0, first.len()..bytes.len());
+        for (_, operation) in &operations {
+            assert_eq!(
+                operation.operation_id(),
+                Some(OperationID::GetPrinterAttributes)
+            );
+            assert!(operation.data.is_empty());
+        }
+        assert_eq!(operations[1].1.version.major, 2);
+    }
+
+    #[test]
+    fn multi_takes_trailing_data_for_the_last_print_job() {
+        let get_printer_attributes = request(0x000b);
+        let print_job = request(0x0002);
+        let data = b"%PDF-1.7\n%%EOF\n";
+        let bytes = [&get_printer_attributes[..], &print_job, data].concat();
+
+        let operations = Operation::from_ipp_multi(&bytes).unwrap();
+        assert_eq!(operations.len(), 2);
+        let (range, operation) = &operations[1];
+        assert_eq!(*range, get_printer_attributes.len()..bytes.len());
+        assert_eq!(operation.operation_id(), Some(OperationID::PrintJob));
+        assert_eq!(operation.data, data);
+    }
+
+    #[test]
+    fn multi_keeps_binary_data_that_looks_like_a_message() {
+        let print_job = request(0x0002);
+        // a version, operation-id and request-id followed by an end-of-attributes tag
+        // decode as a message on their own
+        let data = b"%PDF-1.7\n\x01\x00\x00\x00\x00\x00\x00\x00\x03\n%%EOF\n";
+        assert!(Operation::from_reader(&mut &data[9..]).is_ok());
+        let bytes = [&print_job[..], data].concat();
+
+        let operations = Operation::from_ipp_multi(&bytes).unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].0, 0..bytes.len());
+        assert_eq!(operations[0].1.data, data);
+    }
+
+    #[test]
+    fn multi_refuses_bytes_after_a_message_without_data() {
+        let bytes = [&request(0x000b)[..], b"%PDF-1.7\n%%EOF\n"].concat();
+
+        assert!(Operation::from_ipp_multi(&bytes).is_err());
+    }
+
+    /// response with two job-attributes groups, holding every [`AttributeValue`] variant
+    fn every_value() -> Operation {
+        use crate::{
+            attributes,
+            encoder::{RangeOfInteger, Resolution},
+            spec::{
+                attribute::{JobAttribute, JobTemplateAttribute, OperationAttribute},
+                value::ResolutionUnits,
+            },
+        };
+        use chrono::{TimeZone, Utc};
+
+        let mut operation = Operation::new_response(IppVersion::V1_1, StatusCode::SuccessfulOk, 9);
+        operation.set_attributes_charset("utf-8");
+        operation.set_attributes_natural_language("en-US");
+        operation.operation_attributes_mut().insert(Attribute::uri(
+            OperationAttribute::PrinterUri,
+            "ipp://localhost/ipp/print",
+        ));
+
+        let media_size =
+            Attribute::integer(AttributeName::Unsupported("x-dimension".into()), 21000);
+        let media_col = Attribute {
+            tag: ValueTag::BegCollection,
+            name: AttributeName::Unsupported("media-col".into()),
+            values: vec![AttributeValue::Collection(vec![media_size])],
+        };
+
+        operation.attribute_groups.push(attributes!(
+            DelimiterTag::JobAttributes,
+            Attribute::integer(JobAttribute::JobId, 1),
+            Attribute::name_with_lang(JobAttribute::JobName, "fr", "rapport"),
+            Attribute::text(JobAttribute::JobStateMessage, "done"),
+            Attribute::keyword(JobAttribute::JobStateReasons, "none"),
+            Attribute::mime_media_type(OperationAttribute::DocumentFormat, "application/pdf"),
+            Attribute::boolean(AttributeName::Unsupported("x-flag".into()), true),
+            Attribute::resolution(
+                JobTemplateAttribute::PrinterResolution,
+                Resolution::new(600, 600, ResolutionUnits::DotsPerInch),
+            ),
+            Attribute::range_of_integer(
+                JobTemplateAttribute::PageRanges,
+                RangeOfInteger::new(1, 5)
+            ),
+            Attribute::datetime(
+                JobAttribute::DateTimeAtCreation,
+                Utc.timestamp_millis_opt(1_600_000_000_100).unwrap(),
+            ),
+            media_col,
+        ));
+        operation.attribute_groups.push(attributes!(
+            DelimiterTag::JobAttributes,
+            Attribute::integer(JobAttribute::JobId, 2),
+            Attribute::uri(JobAttribute::JobUri, "ipp://localhost/ipp/print/jobs/2"),
+        ));
+        operation
+    }
+
+    #[test]
+    fn ipp_len_matches_every_value() {
+        let mut operation = every_value();
+        assert_ipp_len_matches(&operation);
+        assert_ipp_len_matches(&operation.attribute_groups);
+
+        operation.data = b"%PDF-1.7".to_vec();
+        assert_ipp_len_matches(&operation);
+    }
+
+    #[test]
+    fn ipp_round_trips() {
+        let mut operation = every_value();
+        operation.data = b"%PDF-1.7".to_vec();
+        let bytes = operation.to_ipp();
+
+        // the decoded length stops before the data
+        assert_eq!(
+            Operation::from_ipp(&bytes, 0),
+            (bytes.len() - operation.data.len(), operation.clone())
+        );
+        assert_eq!(Operation::try_from_ipp(&bytes).unwrap(), operation);
+        assert_eq!(
+            OperationRef::from_ipp(&bytes).unwrap().into_owned(),
+            operation
+        );
+
+        // any difference counts, the data included
+        let mut other = operation.clone();
+        other.data.clear();
+        assert_ne!(Operation::try_from_ipp(&bytes).unwrap(), other);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let operation = every_value();
+        let json = operation.to_json().unwrap();
+        assert_eq!(Operation::from_json(&json).unwrap(), operation);
+        let pretty = operation.to_json_pretty().unwrap();
+        assert_eq!(Operation::from_json(&pretty).unwrap(), operation);
+    }
+
+    #[test]
+    fn operation_attributes_keep_the_wire_order() {
+        let mut charset_second = header();
+        charset_second.push(DelimiterTag::OperationAttributes as u8);
+        push_field(
+            &mut charset_second,
+            0x48,
+            "attributes-natural-language",
+            b"en",
+        );
+        push_field(&mut charset_second, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut charset_second, 0x45, "printer-uri", b"ipp://localhost");
+        charset_second.push(DelimiterTag::EndOfAttributes as u8);
+
+        let names = |operation: &Operation| {
+            operation
+                .operation_attributes_ordered()
+                .map(|attribute| attribute.name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let strict = CompatQuirks::default();
+        assert!(matches!(
+            Operation::try_from_ipp_with_quirks(&charset_second, &strict),
+            Err(IppError::CharsetNotFirst)
+        ));
+        let lenient = CompatQuirks {
+            charset_not_first: true,
+            ..strict
+        };
+        let (operation, _) =
+            Operation::try_from_ipp_with_quirks(&charset_second, &lenient).unwrap();
+        assert_eq!(
+            names(&operation),
+            [
+                "attributes-natural-language",
+                "attributes-charset",
+                "printer-uri"
+            ]
+        );
+
+        let charset_first = request(0x000b);
+        let (operation, report) =
+            Operation::try_from_ipp_with_quirks(&charset_first, &strict).unwrap();
+        assert!(report.warnings.is_empty());
+        assert_eq!(
+            names(&operation),
+            [
+                "attributes-charset",
+                "attributes-natural-language",
+                "printer-uri"
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_request_fallback_is_well_formed() {
+        let bytes = Operation::bad_request_fallback().to_ipp();
+        // version 1.1, client-error-bad-request, request-id 0
+        assert_eq!(bytes[..8], [1, 1, 0x04, 0x00, 0, 0, 0, 0]);
+        assert_eq!(bytes.last(), Some(&(DelimiterTag::EndOfAttributes as u8)));
+
+        let response = Operation::try_from_ipp(&bytes).unwrap();
+        assert_eq!(
+            response.status_code(),
+            Some(StatusCode::ClientErrorBadRequest)
+        );
+        assert_eq!(response.attribute_groups.len(), 1);
+        assert_eq!(
+            response
+                .operation_attributes_ordered()
+                .map(|attribute| attribute.name.to_string())
+                .collect::<Vec<_>>(),
+            ["attributes-charset", "attributes-natural-language"]
+        );
+        assert_eq!(response.attributes_charset(), Some("utf-8"));
+        assert_eq!(response.attributes_natural_language(), Some("en-US"));
+        assert!(response.data.is_empty());
+    }
+
+    #[test]
+    fn parse_header_offset_is_past_the_end_of_attributes() {
+        // no data, the offset is the end of the bytes
+        let bytes = request(0x0002);
+        let (header, offset) = Operation::parse_header(&bytes).unwrap();
+        assert_eq!(offset, bytes.len());
+        assert_eq!(header.operation_id_or_status_code, 0x0002);
+        assert_eq!(header.request_id, 1);
+        assert_eq!(
+            header.attribute_groups,
+            Operation::try_from_ipp(&bytes).unwrap().attribute_groups
+        );
+
+        // data starting with an end-of-attributes tag of its own is left whole
+        let data = b"\x03%!PS\nshowpage\n";
+        let mut with_data = bytes.clone();
+        with_data.extend_from_slice(data);
+        let (with_data_header, offset) = Operation::parse_header(&with_data).unwrap();
+        assert_eq!(offset, bytes.len());
+        assert_eq!(&with_data[offset..], data);
+        assert_eq!(with_data_header, header);
+
+        // without its end-of-attributes tag there is no offset to tell
+        assert!(Operation::parse_header(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn groups_without_end_of_attributes_are_refused() {
+        let bytes = request(0x0002);
+        // the attributes are whole, only the end-of-attributes tag is cut off
+        let truncated = &bytes[..bytes.len() - 1];
+        let header_only = &bytes[..8];
+
+        for bytes in [truncated, header_only] {
+            assert!(matches!(
+                Operation::try_from_ipp(bytes),
+                Err(IppError::MissingEndOfAttributes)
+            ));
+            assert!(matches!(
+                OperationRef::from_ipp(bytes),
+                Err(IppError::MissingEndOfAttributes)
+            ));
+        }
+
+        let mut with_data = bytes.clone();
+        with_data.extend_from_slice(b"%!PS");
+        assert_eq!(Operation::try_from_ipp(&with_data).unwrap().data, b"%!PS");
+    }
+
+    /// operation attributes in the order CUPS `lp` sends them with Print-Job
+    fn cups_print_job() -> Vec<u8> {
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&0x0002_u16.to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en-us");
+        push_field(
+            &mut bytes,
+            0x45,
+            "printer-uri",
+            b"ipp://localhost:631/printers/office",
+        );
+        push_field(&mut bytes, 0x42, "requesting-user-name", b"alice");
+        push_field(&mut bytes, 0x42, "job-name", b"report.pdf");
+        push_field(&mut bytes, 0x49, "document-format", b"application/pdf");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        bytes.extend_from_slice(b"%PDF-1.7");
+        bytes
+    }
+
+    /// Get-Job-Attributes as sent by `lpstat`, targeting the job by 'job-id'
+    fn lpstat_get_job_attributes() -> Vec<u8> {
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&0x0009_u16.to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en-us");
+        push_field(
+            &mut bytes,
+            0x45,
+            "printer-uri",
+            b"ipp://localhost:631/printers/office",
+        );
+        push_field(&mut bytes, 0x21, "job-id", &42_i32.to_be_bytes());
+        push_field(&mut bytes, 0x42, "requesting-user-name", b"alice");
+        push_field(&mut bytes, 0x44, "requested-attributes", b"job-state");
+        push_field(&mut bytes, 0x44, "", b"job-state-reasons");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        bytes
+    }
+
+    #[test]
+    fn operation_attribute_accessors() {
+        let print_job = Operation::try_from_ipp(&cups_print_job()).unwrap();
+        assert_eq!(print_job.attributes_charset(), Some("utf-8"));
+        assert_eq!(print_job.attributes_natural_language(), Some("en-us"));
+        assert_eq!(
+            print_job.printer_uri(),
+            Some("ipp://localhost:631/printers/office")
+        );
+        assert_eq!(print_job.requesting_user_name(), Some("alice"));
+        assert_eq!(print_job.job_name(), Some("report.pdf"));
+        assert_eq!(print_job.document_format(), Some("application/pdf"));
+        assert_eq!(print_job.job_id(), None);
+        assert!(print_job.requested_attributes().is_empty());
+
+        let get_job_attributes = Operation::try_from_ipp(&lpstat_get_job_attributes()).unwrap();
+        assert_eq!(get_job_attributes.job_id(), Some(42));
+        assert_eq!(
+            get_job_attributes.requested_attributes(),
+            ["job-state", "job-state-reasons"]
+        );
+        assert_eq!(get_job_attributes.job_name(), None);
+        assert_eq!(get_job_attributes.document_format(), None);
+    }
+
+    #[test]
+    fn subscription_groups_round_trip() {
+        // Create-Printer-Subscriptions response from CUPS for two subscriptions
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&0x0000_u16.to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        for id in [42_i32, 43] {
+            bytes.push(DelimiterTag::SubscriptionAttributes as u8);
+            push_field(
+                &mut bytes,
+                0x21,
+                "notify-subscription-id",
+                &id.to_be_bytes(),
+            );
+        }
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let response = Operation::try_from_ipp(&bytes).unwrap();
+        let subscriptions = response
+            .attribute_groups
+            .iter()
+            .filter(|group| group.tag == DelimiterTag::SubscriptionAttributes)
+            .map(|group| {
+                group
+                    .attributes
+                    .values()
+                    .next()
+                    .and_then(Attribute::first_i32)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(subscriptions, [Some(42), Some(43)]);
+
+        assert_eq!(response.to_ipp(), bytes);
+        assert_eq!(Operation::try_parse(&bytes).unwrap().into_owned(), response);
+    }
+
+    #[test]
+    fn error_response_decodes_with_status_and_charset() {
+        let mut request = Operation::new_request(IppVersion::V2_0, OperationID::PrintJob, 42);
+        request.set_attributes_charset("iso-8859-1");
+        request.set_attributes_natural_language("fr");
+
+        let response = Operation::error_response(
+            &request,
+            StatusCode::ClientErrorNotFound,
+            Some("no such job"),
+        );
+        let decoded = Operation::try_from_ipp(&response.to_ipp()).unwrap();
+        assert_eq!(decoded.status_code(), Some(StatusCode::ClientErrorNotFound));
+        assert_eq!(decoded.version, IppVersion::V2_0);
+        assert_eq!(decoded.request_id, 42);
+        assert_eq!(decoded.attributes_charset(), Some("iso-8859-1"));
+        assert_eq!(decoded.attributes_natural_language(), Some("fr"));
+        assert_eq!(decoded.status_message(), Some("no such job"));
+        assert!(decoded.validate().is_empty());
+
+        // the requested charset is the one that is not supported
+        let response =
+            Operation::error_response(&request, StatusCode::ClientErrorCharsetNotSupported, None);
+        let decoded = Operation::try_from_ipp(&response.to_ipp()).unwrap();
+        assert_eq!(
+            decoded.status_code(),
+            Some(StatusCode::ClientErrorCharsetNotSupported)
+        );
+        assert_eq!(decoded.attributes_charset(), Some("utf-8"));
+        assert_eq!(decoded.status_message(), None);
+    }
+
+    #[test]
+    fn vendor_status_codes_are_classified_by_range() {
+        let mut response = Operation::new_response(IppVersion::V1_1, StatusCode::SuccessfulOk, 1);
+        assert!(response.is_successful_response());
+
+        response.operation_id_or_status_code = 0x0080;
+        assert_eq!(response.status_code(), None);
+        assert!(response.is_successful_response());
+
+        response.operation_id_or_status_code = 0x0480;
+        assert_eq!(response.status_code(), None);
+        assert!(!response.is_successful_response());
+    }
+
+    /// Get-Notifications response with two event-notification groups and a reserved 0x09 group
+    fn get_notifications_response() -> Vec<u8> {
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&0x0000_u16.to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        push_field(
+            &mut bytes,
+            0x21,
+            "notify-get-interval",
+            &30_i32.to_be_bytes(),
+        );
+        for sequence_number in [1_i32, 2] {
+            bytes.push(DelimiterTag::EventNotificationAttributes as u8);
+            push_field(
+                &mut bytes,
+                0x21,
+                "notify-subscription-id",
+                &7_i32.to_be_bytes(),
+            );
+            push_field(
+                &mut bytes,
+                0x21,
+                "notify-sequence-number",
+                &sequence_number.to_be_bytes(),
+            );
+            push_field(
+                &mut bytes,
+                0x44,
+                "notify-subscribed-event",
+                b"job-completed",
+            );
+            push_field(&mut bytes, 0x41, "notify-text", b"Job done");
+        }
+        bytes.push(0x09);
+        push_field(&mut bytes, 0x44, "future-thing", b"x");
+        push_field(&mut bytes, 0x44, "", b"y");
+        bytes.push(DelimiterTag::PrinterAttributes as u8);
+        push_field(&mut bytes, 0x42, "printer-name", b"p");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        bytes
+    }
+
+    #[test]
+    fn event_notification_and_reserved_delimiters_decode() {
+        let bytes = get_notifications_response();
+        let response = Operation::try_from_ipp(&bytes).unwrap();
+        let tags = response
+            .attribute_groups
+            .iter()
+            .map(|group| group.tag)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            [
+                DelimiterTag::OperationAttributes,
+                DelimiterTag::EventNotificationAttributes,
+                DelimiterTag::EventNotificationAttributes,
+                DelimiterTag::PrinterAttributes,
+            ]
+        );
+        assert_eq!(response.attribute_groups[1].attributes.len(), 4);
+        assert_eq!(response.attribute_groups[3].attributes.len(), 1);
+
+        assert_eq!(Operation::try_parse(&bytes).unwrap().into_owned(), response);
+        assert_eq!(<Operation as IppEncode>::from_ipp(&bytes, 0).1, response);
+        assert_eq!(Operation::from_reader(&mut &bytes[..]).unwrap(), response);
+        let mut parser = OperationParser::new();
+        for byte in bytes[..bytes.len() - 1].chunks(1) {
+            assert!(matches!(
+                parser.push(byte).unwrap(),
+                ParseProgress::NeedMore
+            ));
+        }
+        assert!(matches!(
+            parser.push(&bytes[bytes.len() - 1..]).unwrap(),
+            ParseProgress::HeaderComplete(..)
+        ));
+
+        // the registered groups are encoded again
+        assert_eq!(
+            Operation::try_from_ipp(&response.to_ipp()).unwrap(),
+            response
+        );
+
+        // a reserved group first in the message
+        let mut bytes = header();
+        bytes.push(0x0f);
+        push_field(&mut bytes, 0x44, "a", b"x");
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        let request = Operation::try_from_ipp(&bytes).unwrap();
+        assert_eq!(request.attribute_groups.len(), 1);
+        assert_eq!(request.attribute_groups[0].attributes.len(), 2);
+    }
+
+    #[test]
+    fn attributes_are_found_across_groups() {
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&(OperationID::PrintJob as u16).to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        push_field(&mut bytes, 0x45, "printer-uri", b"ipp://localhost/");
+        bytes.push(DelimiterTag::JobAttributes as u8);
+        push_field(&mut bytes, 0x21, "copies", &2_i32.to_be_bytes());
+        push_field(&mut bytes, 0x44, "sides", b"one-sided");
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+        let request = Operation::try_from_ipp(&bytes).unwrap();
+
+        let attributes = request.attributes().collect::<Vec<_>>();
+        assert_eq!(attributes.len(), 5);
+        assert!(attributes[..3]
+            .iter()
+            .all(|(tag, _)| *tag == DelimiterTag::OperationAttributes));
+        assert!(attributes[3..]
+            .iter()
+            .all(|(tag, _)| *tag == DelimiterTag::JobAttributes));
+
+        let (tag, sides) = request.find_attribute(JobTemplateAttribute::Sides).unwrap();
+        assert_eq!(tag, DelimiterTag::JobAttributes);
+        assert_eq!(sides.first_str(), Some("one-sided"));
+        let (tag, _) = request
+            .find_attribute(OperationAttribute::PrinterUri)
+            .unwrap();
+        assert_eq!(tag, DelimiterTag::OperationAttributes);
+        assert!(request
+            .find_attribute(crate::spec::attribute::PrinterAttribute::PrinterState)
+            .is_none());
+    }
+
+    #[test]
+    fn value_starting_with_a_delimiter_byte_stays_in_its_group() {
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&(OperationID::PrintJob as u16).to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en");
+        bytes.push(DelimiterTag::JobAttributes as u8);
+        push_field(&mut bytes, 0x30, "job-name", &[0x02, 0x03, 0x01]);
+        push_field(&mut bytes, 0x30, "", &[0x02]);
+        push_field(&mut bytes, 0x21, "copies", &3_i32.to_be_bytes());
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let request = Operation::try_from_ipp(&bytes).unwrap();
+        assert_eq!(request.attribute_groups.len(), 2);
+        let job_attributes = request
+            .attribute_group(DelimiterTag::JobAttributes)
+            .unwrap();
+        assert_eq!(job_attributes.attributes.len(), 2);
+        let job_name = &job_attributes.attributes[&AttributeName::from(JobAttribute::JobName)];
+        assert_eq!(job_name.values.len(), 2);
+
+        assert_eq!(Operation::try_parse(&bytes).unwrap().into_owned(), request);
+        assert_eq!(<Operation as IppEncode>::from_ipp(&bytes, 0).1, request);
+        assert_eq!(request.to_ipp(), bytes);
+    }
+
+    #[test]
+    fn status_message_round_trips() {
+        let request = Operation::new_request(IppVersion::V1_1, OperationID::PrintJob, 5);
+        let response = Operation::error_response(
+            &request,
+            StatusCode::ClientErrorNotFound,
+            Some("Tâche 5 introuvable"),
+        );
+        let decoded = Operation::try_from_ipp(&response.to_ipp()).unwrap();
+        assert_eq!(decoded.status_message(), Some("Tâche 5 introuvable"));
+        let (_, status_message) = decoded
+            .find_attribute(OperationAttribute::StatusMessage)
+            .unwrap();
+        assert_eq!(status_message.tag, ValueTag::TextWithoutLanguage);
+
+        let mut response = Operation::response_to(&request, StatusCode::SuccessfulOk);
+        assert_eq!(response.status_message(), None);
+        response.set_status_message("queued");
+        response.set_status_message("printing");
+        let decoded = Operation::try_from_ipp(&response.to_ipp()).unwrap();
+        assert_eq!(decoded.status_message(), Some("printing"));
+        assert_eq!(
+            decoded
+                .find_attribute(OperationAttribute::StatusMessage)
+                .map(|(_, attribute)| attribute.values.len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn orientation_requested_of_a_macos_print_job() {
+        // Print-Job sent by macOS for a landscape document
+        let mut bytes = header();
+        bytes[2..4].copy_from_slice(&(OperationID::PrintJob as u16).to_be_bytes());
+        bytes.push(DelimiterTag::OperationAttributes as u8);
+        push_field(&mut bytes, 0x47, "attributes-charset", b"utf-8");
+        push_field(&mut bytes, 0x48, "attributes-natural-language", b"en-us");
+        push_field(
+            &mut bytes,
+            0x45,
+            "printer-uri",
+            b"ipp://printer.local:631/ipp/print",
+        );
+        push_field(&mut bytes, 0x42, "requesting-user-name", b"alice");
+        push_field(&mut bytes, 0x42, "job-name", b"Untitled");
+        push_field(&mut bytes, 0x49, "document-format", b"application/pdf");
+        bytes.push(DelimiterTag::JobAttributes as u8);
+        push_field(
+            &mut bytes,
+            0x23,
+            "orientation-requested",
+            &4_i32.to_be_bytes(),
+        );
+        bytes.push(DelimiterTag::EndOfAttributes as u8);
+
+        let request = Operation::try_from_ipp(&bytes).unwrap();
+        let orientation_requested = request
+            .attribute(
+                DelimiterTag::JobAttributes,
+                JobTemplateAttribute::OrientationRequested,
+            )
+            .unwrap();
+        assert_eq!(
+            orientation_requested
+                .as_enums::<OrientationRequested>()
+                .collect::<Vec<_>>(),
+            [Ok(OrientationRequested::Landscape)]
+        );
+        assert_eq!(
+            orientation_requested.values[0].as_orientation_requested(),
+            Some(OrientationRequested::Landscape)
+        );
     }
 }
@@ -0,0 +1,165 @@
+use super::{
+    frame::{Field, FrameScanner},
+    DecodeLimits, IppError, Operation, ParsedHeader,
+};
+
+/// Outcome of feeding a chunk to [`OperationParser::push`]
+#[derive(Debug, Clone)]
+pub enum ParseProgress<'a> {
+    /// the end-of-attributes tag has not been reached yet
+    NeedMore,
+    /// header and attribute groups are complete,
+    /// along with the document bytes that followed them in the same chunk
+    HeaderComplete(ParsedHeader, &'a [u8]),
+    /// document bytes, the whole chunk
+    Data(&'a [u8]),
+}
+
+/// Push-based parser for operations arriving in chunks (e.g. chunked HTTP bodies)
+///
+/// Only the header and attribute groups are buffered, fields split across chunks
+/// are picked up once the rest arrives. Document data is handed back per chunk
+/// without being buffered.
+#[derive(Debug, Clone)]
+pub struct OperationParser {
+    /// bytes of header and attribute groups received so far
+    buffer: Vec<u8>,
+    /// framing of the fields in `buffer` received so far
+    scanner: FrameScanner,
+    /// true once the end-of-attributes tag has been seen
+    header_complete: bool,
+}
+
+impl Default for OperationParser {
+    fn default() -> Self {
+        Self {
+            buffer: Vec::new(),
+            scanner: FrameScanner::new(0, &DecodeLimits::default()),
+            header_complete: false,
+        }
+    }
+}
+
+impl OperationParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<'a>(&mut self, chunk: &'a [u8]) -> Result<ParseProgress<'a>, IppError> {
+        if self.header_complete {
+            return Ok(ParseProgress::Data(chunk));
+        }
+
+        let chunk_start = self.buffer.len();
+        self.buffer.extend_from_slice(chunk);
+
+        match self.scan()? {
+            None => Ok(ParseProgress::NeedMore),
+            Some(end) => {
+                let (header, _) = Operation::parse_header(&self.buffer[..end])?;
+
+                self.header_complete = true;
+                self.buffer = Vec::new();
+
+                Ok(ParseProgress::HeaderComplete(
+                    header,
+                    &chunk[end - chunk_start..],
+                ))
+            }
+        }
+    }
+
+    /// advance over complete fields, returning the offset just past
+    /// the end-of-attributes tag once it is in `buffer`
+    fn scan(&mut self) -> Result<Option<usize>, IppError> {
+        loop {
+            match self.scanner.next_field(&self.buffer)? {
+                Field::NeedMore(_) => return Ok(None),
+                Field::End(end) => return Ok(Some(end)),
+                Field::Header | Field::Delimiter | Field::Attribute { .. } => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{Attribute, AttributeName, AttributeValue, IppEncode, IppVersion};
+    use crate::spec::{
+        attribute::{JobTemplateAttribute, OperationAttribute},
+        operation::OperationID,
+        tag::{DelimiterTag, ValueTag},
+    };
+
+    /// Print-Job with a 'media-col' collection and document data, as a client sends it
+    fn print_job() -> Vec<u8> {
+        let media_col = Attribute {
+            tag: ValueTag::BegCollection,
+            name: AttributeName::Unsupported(String::from("media-col")),
+            values: vec![AttributeValue::Collection(vec![
+                Attribute::keyword(JobTemplateAttribute::MediaSource, "main"),
+                Attribute::keyword(JobTemplateAttribute::MediaType, "stationery"),
+            ])],
+        };
+
+        let mut operation = Operation::new_request(IppVersion::V1_1, OperationID::PrintJob, 7);
+        operation.set_attributes_charset("utf-8");
+        operation.set_attributes_natural_language("en-US");
+        operation.insert_attribute(
+            DelimiterTag::OperationAttributes,
+            Attribute::uri(OperationAttribute::PrinterUri, "ipp://localhost/ipp/print"),
+        );
+        operation.insert_attribute(
+            DelimiterTag::JobAttributes,
+            Attribute::integer(JobTemplateAttribute::Copies, 2),
+        );
+        operation.insert_attribute(DelimiterTag::JobAttributes, media_col);
+        operation.data = b"%!PS-Adobe-3.0\nshowpage\n%%EOF\n".to_vec();
+
+        operation.to_ipp()
+    }
+
+    /// feed `chunks` to a parser, returning the header and the document data handed back
+    fn parse(chunks: &[&[u8]]) -> (ParsedHeader, Vec<u8>) {
+        let mut parser = OperationParser::new();
+        let mut header = None;
+        let mut data = Vec::new();
+
+        for chunk in chunks {
+            match parser.push(chunk).unwrap() {
+                ParseProgress::NeedMore => assert!(header.is_none()),
+                ParseProgress::HeaderComplete(parsed, rest) => {
+                    assert!(header.replace(parsed).is_none());
+                    data.extend(rest);
+                }
+                ParseProgress::Data(chunk) => data.extend(chunk),
+            }
+        }
+
+        (header.unwrap(), data)
+    }
+
+    #[test]
+    fn split_at_every_offset_decodes_like_from_ipp() {
+        let bytes = print_job();
+        let (_, expected) = Operation::from_ipp(&bytes, 0);
+        let expected_header = ParsedHeader {
+            version: expected.version,
+            operation_id_or_status_code: expected.operation_id_or_status_code,
+            request_id: expected.request_id,
+            attribute_groups: expected.attribute_groups.clone(),
+        };
+
+        // every chunk boundary falls within a field at some offset, including
+        // the members of 'media-col' and the document data
+        for offset in 0..=bytes.len() {
+            let (header, data) = parse(&[&bytes[..offset], &bytes[offset..]]);
+            assert_eq!(header, expected_header, "split at {}", offset);
+            assert_eq!(data, expected.data, "split at {}", offset);
+        }
+
+        let single_bytes: Vec<&[u8]> = bytes.chunks(1).collect();
+        assert_eq!(parse(&single_bytes), (expected_header, expected.data));
+    }
+}
@@ -1,4 +1,9 @@
 use super::IppEncode;
+use crate::io::{self, Write};
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+use super::{reader::ByteReader, IppError};
 
 impl IppEncode for i32 {
     fn ipp_bytes() -> usize {
@@ -17,12 +22,17 @@ impl IppEncode for i32 {
     }
 
     fn to_ipp(&self) -> Vec<u8> {
-        let value_bytes = self.to_be_bytes().to_vec();
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
 
-        let value_length = value_bytes.len() as u16;
-        let value_length_bytes = value_length.to_be_bytes().to_vec();
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let value_length = Self::ipp_bytes() as u16;
+        writer.write_all(&value_length.to_be_bytes())?;
+        writer.write_all(&self.to_be_bytes())?;
 
-        [value_length_bytes, value_bytes].concat()
+        Ok(self.ipp_len())
     }
 }
 
@@ -42,16 +52,21 @@ impl IppEncode for String {
     }
 
     fn to_ipp(&self) -> Vec<u8> {
-        let value_bytes = self.as_bytes().to_vec();
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
 
-        let value_length = value_bytes.len() as u16;
-        let value_length_bytes = value_length.to_be_bytes().to_vec();
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let value_length = io::length_u16(self.len())?;
+        writer.write_all(&value_length.to_be_bytes())?;
+        writer.write_all(self.as_bytes())?;
 
-        [value_length_bytes, value_bytes].concat()
+        Ok(self.ipp_len())
     }
 
     fn ipp_len(&self) -> usize {
-        self.as_bytes().len() + Self::ipp_value_length_bytes()
+        self.len() + Self::ipp_value_length_bytes()
     }
 }
 
@@ -77,11 +92,69 @@ impl IppEncode for bool {
     }
 
     fn to_ipp(&self) -> Vec<u8> {
-        let value_bytes = (*self as i8).to_be_bytes().to_vec();
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
+
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let value_length = Self::ipp_bytes() as u16;
+        writer.write_all(&value_length.to_be_bytes())?;
+        writer.write_all(&(*self as i8).to_be_bytes())?;
+
+        Ok(self.ipp_len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl super::IppDecode for i32 {
+    fn min_len() -> usize {
+        2 + 4
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let mut reader = ByteReader::new(bytes, offset);
+        if reader.read_u16_be()? != 4 {
+            return Err(IppError::InvalidValue(offset));
+        }
+
+        Ok((Self::min_len(), reader.read_i32_be()?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl super::IppDecode for String {
+    fn min_len() -> usize {
+        2
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let mut reader = ByteReader::new(bytes, offset);
+        let value = reader.read_length_prefixed()?;
+        let value = core::str::from_utf8(value).map_err(|_| IppError::InvalidUtf8(offset))?;
+
+        Ok((reader.position() - offset, String::from(value)))
+    }
+}
 
-        let value_length = value_bytes.len() as u16;
-        let value_length_bytes = value_length.to_be_bytes().to_vec();
+#[cfg(feature = "std")]
+impl super::IppDecode for bool {
+    fn min_len() -> usize {
+        2 + 1
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let mut reader = ByteReader::new(bytes, offset);
+        if reader.read_u16_be()? != 1 {
+            return Err(IppError::InvalidValue(offset));
+        }
+
+        let value = match reader.read_u8()? {
+            0x00 => false,
+            0x01 => true,
+            _ => return Err(IppError::InvalidValue(offset)),
+        };
 
-        [value_length_bytes, value_bytes].concat()
+        Ok((Self::min_len(), value))
     }
 }
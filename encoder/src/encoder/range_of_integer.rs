@@ -0,0 +1,165 @@
+use super::{error::ValueParseError, IppEncode};
+#[cfg(feature = "std")]
+use super::{reader::ByteReader, IppError};
+use crate::io::{self, Write};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// 'rangeOfInteger' value, both bounds included
+///
+/// Written, parsed and serialized as `<lower>-<upper>`, e.g. `1-5`.
+///
+/// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.9)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangeOfInteger {
+    pub lower: i32,
+    pub upper: i32,
+}
+
+impl RangeOfInteger {
+    pub fn new(lower: i32, upper: i32) -> Self {
+        Self { lower, upper }
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        (self.lower..=self.upper).contains(&value)
+    }
+}
+
+impl IppEncode for RangeOfInteger {
+    fn ipp_bytes() -> usize {
+        // lower bound (4 bytes), upper bound (4 bytes)
+        8
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
+        let start = offset + Self::ipp_value_length_bytes();
+        let value = &bytes[start..start + Self::ipp_bytes()];
+
+        let range = Self {
+            lower: i32::from_be_bytes(value[0..4].try_into().unwrap()),
+            upper: i32::from_be_bytes(value[4..8].try_into().unwrap()),
+        };
+
+        (range.ipp_len(), range)
+    }
+
+    fn to_ipp(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
+
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let value_length = Self::ipp_bytes() as u16;
+        writer.write_all(&value_length.to_be_bytes())?;
+        writer.write_all(&self.lower.to_be_bytes())?;
+        writer.write_all(&self.upper.to_be_bytes())?;
+
+        Ok(self.ipp_len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl super::IppDecode for RangeOfInteger {
+    fn min_len() -> usize {
+        2 + 8
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let mut reader = ByteReader::new(bytes, offset);
+        if reader.read_u16_be()? != 8 {
+            return Err(IppError::InvalidValue(offset));
+        }
+
+        let lower = reader.read_i32_be()?;
+        let upper = reader.read_i32_be()?;
+
+        Ok((Self::min_len(), Self::new(lower, upper)))
+    }
+}
+
+impl fmt::Display for RangeOfInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.lower, self.upper)
+    }
+}
+
+impl FromStr for RangeOfInteger {
+    type Err = ValueParseError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let error = || ValueParseError::new("<lower>-<upper>", str);
+
+        // the lower bound may itself start with a minus sign
+        let separator = str
+            .get(1..)
+            .and_then(|rest| rest.find('-'))
+            .ok_or_else(error)?
+            + 1;
+
+        let range = Self {
+            lower: str[..separator].parse().map_err(|_| error())?,
+            upper: str[separator + 1..].parse().map_err(|_| error())?,
+        };
+        if range.lower > range.upper {
+            return Err(error());
+        }
+
+        Ok(range)
+    }
+}
+
+impl Serialize for RangeOfInteger {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RangeOfInteger {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let range = String::deserialize(deserializer)?;
+        Self::from_str(&range).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::assert_ipp_len_matches;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse_then_encode() {
+        let range = RangeOfInteger::from_str("2-10").unwrap();
+        assert_eq!(range, RangeOfInteger::new(2, 10));
+        let bytes = range.to_ipp();
+        assert_eq!(bytes, [0, 8, 0, 0, 0, 2, 0, 0, 0, 10]);
+        assert_eq!(
+            <RangeOfInteger as IppEncode>::from_ipp(&bytes, 0),
+            (10, range)
+        );
+        assert_eq!(range.to_string(), "2-10");
+        assert_ipp_len_matches(&range);
+
+        assert_eq!(
+            RangeOfInteger::from_str("-5--1").unwrap(),
+            RangeOfInteger::new(-5, -1)
+        );
+        for invalid in ["5-1", "1", "-", "a-b", "1-"] {
+            assert!(RangeOfInteger::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn serialized_as_text() {
+        let range = RangeOfInteger::new(1, 5);
+        assert_eq!(serde_json::to_string(&range).unwrap(), r#""1-5""#);
+        assert_eq!(
+            serde_json::from_str::<RangeOfInteger>(r#""1-5""#).unwrap(),
+            range
+        );
+    }
+}
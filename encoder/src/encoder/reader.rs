@@ -0,0 +1,191 @@
+use super::IppError;
+
+/// Cursor over a message for the fallible decoders
+///
+/// Every read checks that its bytes are there and fails with [`IppError::Truncated`]
+/// otherwise, counting `needed` and `available` from where the reader started.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    start: usize,
+    position: usize,
+    /// offset reported in errors, the start of the field being read
+    field_offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    /// reader at `offset`, for a field starting there
+    pub fn new(bytes: &'a [u8], offset: usize) -> Self {
+        Self::for_field(bytes, offset, offset)
+    }
+
+    /// reader at `offset` for a field starting at `field_offset`,
+    /// e.g. the name of an attribute whose errors point at its value-tag
+    pub fn for_field(bytes: &'a [u8], offset: usize, field_offset: usize) -> Self {
+        Self {
+            bytes,
+            start: offset,
+            position: offset,
+            field_offset,
+        }
+    }
+
+    /// offset of the next byte to read
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, IppError> {
+        let [byte] = self.read_array()?;
+        Ok(byte)
+    }
+
+    pub fn read_u16_be(&mut self) -> Result<u16, IppError> {
+        self.read_array().map(u16::from_be_bytes)
+    }
+
+    pub fn read_u32_be(&mut self) -> Result<u32, IppError> {
+        self.read_array().map(u32::from_be_bytes)
+    }
+
+    pub fn read_i32_be(&mut self) -> Result<i32, IppError> {
+        self.read_array().map(i32::from_be_bytes)
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], IppError> {
+        let bytes = self
+            .bytes
+            .get(self.position..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or_else(|| self.truncated(len))?;
+        self.position += len;
+
+        Ok(bytes)
+    }
+
+    /// a 2-byte length and the bytes it declares, as in name-length & name
+    /// and value-length & value
+    pub fn read_length_prefixed(&mut self) -> Result<&'a [u8], IppError> {
+        let len = self.read_u16_be()?;
+        self.read_bytes(len as usize)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], IppError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+
+        Ok(array)
+    }
+
+    fn truncated(&self, len: usize) -> IppError {
+        IppError::Truncated {
+            offset: self.field_offset,
+            needed: self.position - self.start + len,
+            available: self.bytes.len().saturating_sub(self.start),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// offset, needed and available of a truncation
+    fn truncated(err: IppError) -> (usize, usize, usize) {
+        match err {
+            IppError::Truncated {
+                offset,
+                needed,
+                available,
+            } => (offset, needed, available),
+            err => panic!("expected Truncated, got {}", err),
+        }
+    }
+
+    const BYTES: [u8; 5] = [0x01, 0x02, 0x03, 0xff, 0xfe];
+
+    #[test]
+    fn read_u8() {
+        let mut reader = ByteReader::new(&BYTES, 4);
+        assert_eq!(reader.read_u8().unwrap(), 0xfe);
+        assert_eq!(reader.position(), 5);
+        assert_eq!(truncated(reader.read_u8().unwrap_err()), (4, 2, 1));
+        assert_eq!(reader.position(), 5);
+    }
+
+    #[test]
+    fn read_u16_be() {
+        assert_eq!(ByteReader::new(&BYTES, 3).read_u16_be().unwrap(), 0xfffe);
+        assert_eq!(
+            truncated(ByteReader::new(&BYTES, 4).read_u16_be().unwrap_err()),
+            (4, 2, 1)
+        );
+    }
+
+    #[test]
+    fn read_u32_be() {
+        assert_eq!(
+            ByteReader::new(&BYTES, 1).read_u32_be().unwrap(),
+            0x0203_fffe
+        );
+        assert_eq!(
+            truncated(ByteReader::new(&BYTES, 2).read_u32_be().unwrap_err()),
+            (2, 4, 3)
+        );
+    }
+
+    #[test]
+    fn read_i32_be() {
+        assert_eq!(
+            ByteReader::new(&[0xff, 0xff, 0xff, 0xfe], 0)
+                .read_i32_be()
+                .unwrap(),
+            -2
+        );
+        // an offset past the end has nothing available
+        assert_eq!(
+            truncated(ByteReader::new(&BYTES, 9).read_i32_be().unwrap_err()),
+            (9, 4, 0)
+        );
+    }
+
+    #[test]
+    fn read_bytes() {
+        let mut reader = ByteReader::new(&BYTES, 0);
+        assert_eq!(reader.read_bytes(5).unwrap(), BYTES);
+        assert_eq!(reader.read_bytes(0).unwrap(), [0u8; 0]);
+        assert_eq!(
+            truncated(ByteReader::new(&BYTES, 0).read_bytes(6).unwrap_err()),
+            (0, 6, 5)
+        );
+    }
+
+    #[test]
+    fn read_length_prefixed() {
+        let bytes = [0x00, 0x02, 0x09, 0x08, 0x00, 0x03, 0x01];
+
+        // errors are for the field the reader was made for, counted from its start
+        let mut reader = ByteReader::for_field(&bytes, 0, 7);
+        assert_eq!(reader.read_length_prefixed().unwrap(), [0x09, 0x08]);
+        assert_eq!(
+            truncated(reader.read_length_prefixed().unwrap_err()),
+            (7, 9, 7)
+        );
+
+        assert_eq!(
+            truncated(
+                ByteReader::new(&bytes, 4)
+                    .read_length_prefixed()
+                    .unwrap_err()
+            ),
+            (4, 5, 3)
+        );
+        assert_eq!(
+            truncated(
+                ByteReader::new(&bytes, 6)
+                    .read_length_prefixed()
+                    .unwrap_err()
+            ),
+            (6, 2, 1)
+        );
+    }
+}
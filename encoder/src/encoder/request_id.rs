@@ -0,0 +1,65 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Thread-safe source of increasing request-ids for clients
+///
+/// Ids run from 1 up to 2^31 - 1, then wrap around to 1; 0 is never returned.
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.2)
+#[derive(Debug)]
+pub struct RequestIdGenerator {
+    next: AtomicU32,
+}
+
+impl RequestIdGenerator {
+    /// largest request-id allowed, ids wrap around to 1 after it
+    pub const MAX: u32 = i32::MAX as u32;
+
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// `first` is clamped into 1..=[`RequestIdGenerator::MAX`]
+    pub fn starting_at(first: u32) -> Self {
+        Self {
+            next: AtomicU32::new(first.clamp(1, Self::MAX)),
+        }
+    }
+
+    pub fn next_id(&self) -> u32 {
+        // fetch_update only fails if the closure returns None
+        self.next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| {
+                Some(if id >= Self::MAX { 1 } else { id + 1 })
+            })
+            .unwrap()
+    }
+}
+
+impl Default for RequestIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_around_to_one() {
+        let generator = RequestIdGenerator::starting_at(RequestIdGenerator::MAX - 1);
+        assert_eq!(generator.next_id(), RequestIdGenerator::MAX - 1);
+        assert_eq!(generator.next_id(), RequestIdGenerator::MAX);
+        assert_eq!(generator.next_id(), 1);
+        assert_eq!(generator.next_id(), 2);
+
+        // u32::MAX is past what a request-id may be, it is clamped first
+        let generator = RequestIdGenerator::starting_at(u32::MAX);
+        assert_eq!(generator.next_id(), RequestIdGenerator::MAX);
+        assert_eq!(generator.next_id(), 1);
+
+        // 0 is reserved
+        assert_eq!(RequestIdGenerator::starting_at(0).next_id(), 1);
+        assert_eq!(RequestIdGenerator::new().next_id(), 1);
+    }
+}
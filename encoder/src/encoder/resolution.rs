@@ -0,0 +1,184 @@
+use super::{error::ValueParseError, IppEncode};
+#[cfg(feature = "std")]
+use super::{reader::ByteReader, IppError};
+use crate::io::{self, Write};
+use crate::spec::value::ResolutionUnits;
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// 'resolution' value, dots per unit in the cross-feed and feed directions
+///
+/// Written, parsed and serialized as `<cross-feed>x<feed><units>`, e.g. `600x600dpi`;
+/// `600dpi` also parses, for the same resolution in both directions.
+///
+/// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.9)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Resolution {
+    pub cross_feed: i32,
+    pub feed: i32,
+    pub units: ResolutionUnits,
+}
+
+impl Resolution {
+    pub fn new(cross_feed: i32, feed: i32, units: ResolutionUnits) -> Self {
+        Self {
+            cross_feed,
+            feed,
+            units,
+        }
+    }
+}
+
+impl IppEncode for Resolution {
+    fn ipp_bytes() -> usize {
+        // cross-feed (4 bytes), feed (4 bytes), units (1 byte)
+        9
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
+        let start = offset + Self::ipp_value_length_bytes();
+        let value = &bytes[start..start + Self::ipp_bytes()];
+
+        let resolution = Self {
+            cross_feed: i32::from_be_bytes(value[0..4].try_into().unwrap()),
+            feed: i32::from_be_bytes(value[4..8].try_into().unwrap()),
+            units: ResolutionUnits::from_repr(value[8] as usize).unwrap(),
+        };
+
+        (resolution.ipp_len(), resolution)
+    }
+
+    fn to_ipp(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
+
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let value_length = Self::ipp_bytes() as u16;
+        writer.write_all(&value_length.to_be_bytes())?;
+        writer.write_all(&self.cross_feed.to_be_bytes())?;
+        writer.write_all(&self.feed.to_be_bytes())?;
+        writer.write_all(&[self.units as u8])?;
+
+        Ok(self.ipp_len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl super::IppDecode for Resolution {
+    fn min_len() -> usize {
+        2 + 9
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let mut reader = ByteReader::new(bytes, offset);
+        if reader.read_u16_be()? != 9 {
+            return Err(IppError::InvalidValue(offset));
+        }
+
+        let cross_feed = reader.read_i32_be()?;
+        let feed = reader.read_i32_be()?;
+        let units = ResolutionUnits::from_repr(reader.read_u8()? as usize)
+            .ok_or(IppError::InvalidValue(offset))?;
+
+        Ok((Self::min_len(), Self::new(cross_feed, feed, units)))
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}{}", self.cross_feed, self.feed, self.units)
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = ValueParseError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let error = || ValueParseError::new("<cross-feed>x<feed>dpi or dpcm", str);
+
+        let dots_end = str.rfind(|c: char| c.is_ascii_digit()).ok_or_else(error)? + 1;
+        let (dots, units) = str.split_at(dots_end);
+        let units = ResolutionUnits::from_str(units).map_err(|_| error())?;
+
+        let (cross_feed, feed) = dots.split_once('x').unwrap_or((dots, dots));
+
+        Ok(Self {
+            cross_feed: cross_feed.parse().map_err(|_| error())?,
+            feed: feed.parse().map_err(|_| error())?,
+            units,
+        })
+    }
+}
+
+impl Serialize for Resolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Resolution {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let resolution = String::deserialize(deserializer)?;
+        Self::from_str(&resolution).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::assert_ipp_len_matches;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse_then_encode() {
+        let resolution = Resolution::from_str("300x600dpcm").unwrap();
+        assert_eq!(
+            resolution,
+            Resolution::new(300, 600, ResolutionUnits::DotsPerCentimeter)
+        );
+        let bytes = resolution.to_ipp();
+        assert_eq!(bytes, [0, 9, 0, 0, 1, 44, 0, 0, 2, 88, 4]);
+        assert_eq!(
+            <Resolution as IppEncode>::from_ipp(&bytes, 0),
+            (11, resolution)
+        );
+        assert_eq!(resolution.to_string(), "300x600dpcm");
+        assert_ipp_len_matches(&resolution);
+
+        assert_eq!(
+            Resolution::from_str("600dpi").unwrap().to_string(),
+            "600x600dpi"
+        );
+        for invalid in ["600", "600x600", "x600dpi", "600x600dpm", ""] {
+            assert!(Resolution::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn units_must_be_registered() {
+        let mut bytes = Resolution::new(600, 600, ResolutionUnits::DotsPerInch).to_ipp();
+        bytes[10] = 9;
+        assert!(matches!(
+            <Resolution as crate::encoder::IppDecode>::from_ipp(&bytes, 0),
+            Err(IppError::InvalidValue(0))
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn serialized_as_text() {
+        let resolution = Resolution::new(600, 600, ResolutionUnits::DotsPerInch);
+        assert_eq!(
+            serde_json::to_string(&resolution).unwrap(),
+            r#""600x600dpi""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Resolution>(r#""600x600dpi""#).unwrap(),
+            resolution
+        );
+    }
+}
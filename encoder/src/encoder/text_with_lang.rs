@@ -1,4 +1,10 @@
 use super::IppEncode;
+#[cfg(feature = "std")]
+use super::{operation::length_prefixed, AttributeValue, IppError};
+use crate::io::{self, Write};
+#[cfg(feature = "std")]
+use crate::spec::tag::ValueTag;
+use alloc::{string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
 
 /// Wrapper for 'textWithoutLanguage' attribute value type
@@ -11,6 +17,15 @@ pub struct TextWithLang {
     pub text: String,
 }
 
+impl TextWithLang {
+    pub fn new(lang: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            lang: lang.into(),
+            text: text.into(),
+        }
+    }
+}
+
 impl IppEncode for TextWithLang {
     fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self) {
         let lang_offset = offset + Self::ipp_value_length_bytes();
@@ -26,16 +41,39 @@ impl IppEncode for TextWithLang {
     }
 
     fn to_ipp(&self) -> Vec<u8> {
-        let lang_bytes = self.lang.to_ipp();
-        let text_bytes = self.text.to_ipp();
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_ipp_into(&mut bytes);
+        bytes
+    }
+
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let total_len = io::length_u16(self.lang.ipp_len() + self.text.ipp_len())?;
+        writer.write_all(&total_len.to_be_bytes())?;
 
-        let total_len = lang_bytes.len() as u16 + text_bytes.len() as u16;
-        let total_len_bytes = total_len.to_be_bytes().to_vec();
+        self.lang.to_writer(writer)?;
+        self.text.to_writer(writer)?;
 
-        [total_len_bytes, lang_bytes, text_bytes].concat()
+        Ok(self.ipp_len())
     }
 
     fn ipp_len(&self) -> usize {
         Self::ipp_value_length_bytes() + self.lang.ipp_len() + self.text.ipp_len()
     }
 }
+
+#[cfg(feature = "std")]
+impl super::IppDecode for TextWithLang {
+    fn min_len() -> usize {
+        // value-length, lang-length and text-length
+        2 + 2 + 2
+    }
+
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError> {
+        let value = length_prefixed(bytes, offset, offset)?;
+        if !AttributeValue::is_valid(ValueTag::TextWithLanguage, value) {
+            return Err(IppError::InvalidValue(offset));
+        }
+
+        Ok(<Self as IppEncode>::from_ipp(bytes, offset))
+    }
+}
@@ -1,3 +1,9 @@
+use crate::io::{self, Write};
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use super::IppError;
+
 /// Skeleton for implementing encoder / decoder logics
 pub trait IppEncode {
     fn ipp_value_length_bytes() -> usize {
@@ -6,9 +12,70 @@ pub trait IppEncode {
     fn ipp_bytes() -> usize {
         panic!("No implementation for ipp_bytes is provided for this type");
     }
+    /// Panics on malformed input
+    ///
+    /// Superseded by [`IppDecode::from_ipp`], which reports malformed input as an error;
+    /// decoding is due to leave this trait in the next major release.
     fn from_ipp(bytes: &[u8], offset: usize) -> (usize, Self);
+    /// Panics on a name or value longer than 65535 bytes, which its 2-byte length
+    /// cannot hold, see [`IppEncode::try_to_ipp`]
     fn to_ipp(&self) -> Vec<u8>;
+    /// [`IppEncode::to_ipp`], failing with [`io::Error`] on a name or value
+    /// longer than 65535 bytes
+    fn try_to_ipp(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(self.ipp_len());
+        self.to_writer(&mut bytes)?;
+        Ok(bytes)
+    }
+    /// append encoded bytes to `buf`
+    fn to_ipp_into(&self, buf: &mut Vec<u8>) {
+        // writing into a Vec only fails on a length that does not fit in 2 bytes
+        self.to_writer(buf).expect(io::TOO_LONG);
+    }
+    /// write encoded bytes into `writer`, returning the number of bytes written
+    ///
+    /// A name or value longer than 65535 bytes fails rather than wrapping its length around.
+    fn to_writer<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let bytes = self.to_ipp();
+        writer.write_all(&bytes)?;
+        Ok(bytes.len())
+    }
     fn ipp_len(&self) -> usize {
         Self::ipp_bytes() + Self::ipp_value_length_bytes()
     }
 }
+
+/// Fallible decoding, never panics on malformed input
+///
+/// Implemented by every [`IppEncode`] type; its decoding counterpart is
+/// [`IppEncode::from_ipp`], which panics instead.
+#[cfg(feature = "std")]
+pub trait IppDecode: Sized {
+    /// fewest bytes an encoded value can take
+    fn min_len() -> usize;
+    /// decode from `offset`, returning the number of bytes read and the value
+    fn from_ipp(bytes: &[u8], offset: usize) -> Result<(usize, Self), IppError>;
+}
+
+/// Panic unless `to_ipp`, `to_writer` and `ipp_len` agree on the encoded length of `value`
+///
+/// Meant for tests and fuzzing of [`IppEncode`] implementations, where `ipp_len`
+/// is computed separately from the encoding it describes; requires the `test-support` feature
+#[cfg(any(test, feature = "test-support"))]
+pub fn assert_ipp_len_matches<T: IppEncode>(value: &T) {
+    let bytes = value.to_ipp();
+    assert_eq!(
+        bytes.len(),
+        value.ipp_len(),
+        "ipp_len disagrees with the length of to_ipp"
+    );
+
+    let mut written = Vec::with_capacity(bytes.len());
+    let len = value.to_writer(&mut written).unwrap();
+    assert_eq!(
+        len,
+        written.len(),
+        "to_writer reported a different number of bytes than it wrote"
+    );
+    assert_eq!(written, bytes, "to_writer and to_ipp wrote different bytes");
+}
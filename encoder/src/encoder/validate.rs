@@ -0,0 +1,359 @@
+use super::{
+    Attribute, AttributeName, IppVersion, Operation, Severity, ValidationIssue, ValidationIssueKind,
+};
+use crate::spec::{
+    attribute::{JobAttribute, OperationAttribute, PrinterAttribute},
+    operation::OperationID,
+    tag::DelimiterTag,
+};
+
+impl Operation {
+    /// Check the structural requirements of RFC 8010/8011, all issues found are returned
+    ///
+    /// - request-id 1 or more and a version of [`IppVersion::KNOWN`]
+    /// - exactly one operation-attributes group, before any other group
+    /// - 'attributes-charset' and 'attributes-natural-language' in it, which encoding
+    ///   always writes first and second
+    /// - every attribute with at least one value, and only one for single-valued attributes
+    /// - value-tags within the syntax of registered attributes, out-of-band values and
+    ///   the unsupported-attributes group aside
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1)
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if self.request_id == 0 {
+            issues.push(issue(Severity::Error, ValidationIssueKind::ZeroRequestId));
+        }
+
+        if !self.version.is_supported(&IppVersion::KNOWN) {
+            issues.push(issue(
+                Severity::Error,
+                ValidationIssueKind::UnsupportedVersion(self.version),
+            ));
+        }
+
+        let operation_groups = self
+            .attribute_groups
+            .iter()
+            .filter(|group| group.tag == DelimiterTag::OperationAttributes)
+            .count();
+        if operation_groups == 0 {
+            issues.push(issue(
+                Severity::Error,
+                ValidationIssueKind::MissingOperationAttributes,
+            ));
+        } else {
+            if operation_groups > 1 {
+                issues.push(issue(
+                    Severity::Error,
+                    ValidationIssueKind::DuplicateOperationAttributes,
+                ));
+            }
+            if self.attribute_groups[0].tag != DelimiterTag::OperationAttributes {
+                issues.push(issue(
+                    Severity::Error,
+                    ValidationIssueKind::OperationAttributesNotFirst,
+                ));
+            }
+
+            for name in [
+                OperationAttribute::AttributesCharset,
+                OperationAttribute::AttributesNaturalLanguage,
+            ] {
+                self.require(&mut issues, Severity::Error, name);
+            }
+        }
+
+        for group in &self.attribute_groups {
+            let mut attributes: Vec<&Attribute> = group.attributes.values().collect();
+            attributes.sort_by_cached_key(|attribute| attribute.name.to_string());
+
+            for attribute in attributes {
+                let at = |severity, kind| ValidationIssue {
+                    severity,
+                    group: Some(group.tag),
+                    attribute: Some(attribute.name.clone()),
+                    kind,
+                };
+
+                if attribute.values.is_empty() {
+                    issues.push(at(Severity::Error, ValidationIssueKind::EmptyValues));
+                } else if attribute.values.len() > 1 && is_single_valued(&attribute.name) {
+                    issues.push(at(Severity::Error, ValidationIssueKind::MultipleValues));
+                }
+
+                // unsupported attributes are echoed back as the client sent them
+                if group.tag == DelimiterTag::UnsupportedAttributes
+                    || attribute.tag.is_out_of_band()
+                {
+                    continue;
+                }
+                if let Some(value_tags) = attribute.name.value_tags() {
+                    if !value_tags.contains(&attribute.tag) {
+                        issues.push(at(
+                            Severity::Error,
+                            ValidationIssueKind::UnexpectedValueTag(attribute.tag),
+                        ));
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// [`Operation::validate`], then the groups and operation attributes a request
+    /// for `operation_id` takes
+    ///
+    /// - 'printer-uri', or for job operations 'job-uri' or 'printer-uri' and 'job-id',
+    ///   none for CUPS operations on the server itself (e.g. CUPS-Get-Printers)
+    /// - 'last-document' for Send-Document
+    /// - 'requesting-user-name', as a warning since clients only SHOULD send it
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.5)
+    pub fn validate_as_request(&self, operation_id: OperationID) -> Vec<ValidationIssue> {
+        let mut issues = self.validate();
+
+        for group in &self.attribute_groups {
+            if !request_takes_group(operation_id, group.tag) {
+                issues.push(ValidationIssue {
+                    severity: Severity::Warning,
+                    group: Some(group.tag),
+                    attribute: None,
+                    kind: ValidationIssueKind::UnexpectedGroup,
+                });
+            }
+        }
+
+        if targets_job(operation_id) {
+            if self
+                .attribute(DelimiterTag::OperationAttributes, JobAttribute::JobUri)
+                .is_none()
+            {
+                self.require(&mut issues, Severity::Error, OperationAttribute::PrinterUri);
+                self.require(&mut issues, Severity::Error, JobAttribute::JobId);
+            }
+        } else if !targets_server(operation_id) {
+            self.require(&mut issues, Severity::Error, OperationAttribute::PrinterUri);
+        }
+
+        if operation_id == OperationID::SendDocument {
+            self.require(
+                &mut issues,
+                Severity::Error,
+                OperationAttribute::LastDocument,
+            );
+        }
+
+        self.require(
+            &mut issues,
+            Severity::Warning,
+            OperationAttribute::RequestingUserName,
+        );
+
+        issues
+    }
+
+    /// push a [`ValidationIssueKind::MissingRequiredAttribute`] unless the
+    /// operation-attributes group has `name`
+    fn require(
+        &self,
+        issues: &mut Vec<ValidationIssue>,
+        severity: Severity,
+        name: impl Into<AttributeName>,
+    ) {
+        let name = name.into();
+        if self
+            .attribute(DelimiterTag::OperationAttributes, name.clone())
+            .is_none()
+        {
+            issues.push(ValidationIssue {
+                severity,
+                group: Some(DelimiterTag::OperationAttributes),
+                attribute: Some(name),
+                kind: ValidationIssueKind::MissingRequiredAttribute,
+            });
+        }
+    }
+}
+
+/// issue about the operation as a whole
+fn issue(severity: Severity, kind: ValidationIssueKind) -> ValidationIssue {
+    ValidationIssue {
+        severity,
+        group: None,
+        attribute: None,
+        kind,
+    }
+}
+
+/// operations on a job, identified by 'job-uri' or by 'printer-uri' and 'job-id'
+fn targets_job(operation_id: OperationID) -> bool {
+    matches!(
+        operation_id,
+        OperationID::SendDocument
+            | OperationID::SendUri
+            | OperationID::CancelJob
+            | OperationID::GetJobAttributes
+            | OperationID::HoldJob
+            | OperationID::ReleaseJob
+            | OperationID::RestartJob
+            | OperationID::CreateJobSubscriptions
+            | OperationID::SetJobAttributes
+            | OperationID::ReprocessJob
+            | OperationID::ResumeJob
+            | OperationID::PromoteJob
+            | OperationID::ScheduleJobAfter
+            | OperationID::CancelDocument
+            | OperationID::GetDocumentAttributes
+            | OperationID::GetDocuments
+            | OperationID::DeleteDocument
+            | OperationID::SetDocumentAttributes
+            | OperationID::ResubmitJob
+            | OperationID::CloseJob
+            | OperationID::CupsMoveJob
+            | OperationID::CupsAuthenticateJob
+            | OperationID::CupsGetDocument
+    )
+}
+
+/// CUPS operations on the server's list of printers, classes, devices or PPDs
+fn targets_server(operation_id: OperationID) -> bool {
+    matches!(
+        operation_id,
+        OperationID::CupsGetDefault
+            | OperationID::CupsGetPrinters
+            | OperationID::CupsGetClasses
+            | OperationID::CupsGetDevices
+            | OperationID::CupsGetPpds
+    )
+}
+
+/// job creation takes job template attributes, and with RFC 3995 subscriptions;
+/// the RFC 3380 set operations take the attributes they set
+fn request_takes_group(operation_id: OperationID, tag: DelimiterTag) -> bool {
+    let creates_job = matches!(
+        operation_id,
+        OperationID::PrintJob | OperationID::PrintUri | OperationID::CreateJob
+    );
+
+    match tag {
+        DelimiterTag::OperationAttributes => true,
+        DelimiterTag::JobAttributes => {
+            creates_job
+                || matches!(
+                    operation_id,
+                    OperationID::ValidateJob
+                        | OperationID::SetJobAttributes
+                        | OperationID::ResubmitJob
+                )
+        }
+        DelimiterTag::PrinterAttributes => operation_id == OperationID::SetPrinterAttributes,
+        DelimiterTag::SubscriptionAttributes => {
+            creates_job
+                || matches!(
+                    operation_id,
+                    OperationID::CreatePrinterSubscriptions | OperationID::CreateJobSubscriptions
+                )
+        }
+        _ => false,
+    }
+}
+
+/// attributes of this crate's spec that never take a 1setOf value
+fn is_single_valued(name: &AttributeName) -> bool {
+    match name {
+        AttributeName::Operation(OperationAttribute::RequestedAttributes) => false,
+        AttributeName::Operation(_) => true,
+        AttributeName::Job(attribute) => matches!(
+            attribute,
+            JobAttribute::JobUri
+                | JobAttribute::JobId
+                | JobAttribute::JobPrinterUri
+                | JobAttribute::JobName
+                | JobAttribute::JobState
+        ),
+        AttributeName::Printer(attribute) => matches!(
+            attribute,
+            PrinterAttribute::PrinterName
+                | PrinterAttribute::PrinterState
+                | PrinterAttribute::PrinterIsAcceptingJobs
+                | PrinterAttribute::PrinterUpTime
+                | PrinterAttribute::ColorSupported
+                | PrinterAttribute::PagesPerMinute
+                | PrinterAttribute::PagesPerMinuteColor
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{tag::ValueTag, value::WhichJobsKeyword};
+
+    fn get_jobs() -> Operation {
+        let mut operation = Operation::new_request(IppVersion::V1_1, OperationID::GetJobs, 1);
+        operation.set_attributes_charset("utf-8");
+        operation.set_attributes_natural_language("en");
+        operation
+    }
+
+    #[test]
+    fn valid_operation_has_no_issues() {
+        let mut operation = get_jobs();
+        operation
+            .operation_attributes_mut()
+            .insert(Attribute::keyword(
+                OperationAttribute::WhichJobs,
+                WhichJobsKeyword::Completed.to_string(),
+            ));
+        assert_eq!(operation.validate(), []);
+    }
+
+    #[test]
+    fn missing_charset_is_an_error() {
+        let mut operation = get_jobs();
+        operation
+            .operation_attributes_mut()
+            .attributes
+            .shift_remove(&AttributeName::from(OperationAttribute::AttributesCharset));
+
+        let issues = operation.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(
+            issues[0].kind,
+            ValidationIssueKind::MissingRequiredAttribute
+        );
+        assert_eq!(
+            issues[0].attribute,
+            Some(AttributeName::from(OperationAttribute::AttributesCharset))
+        );
+    }
+
+    #[test]
+    fn every_issue_is_reported() {
+        let mut operation = get_jobs();
+        operation.request_id = 0;
+        operation.insert_attribute(
+            DelimiterTag::JobAttributes,
+            Attribute::keyword(JobAttribute::JobId, "1"),
+        );
+        operation.attribute_groups.swap(0, 1);
+
+        let kinds = operation
+            .validate()
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect::<Vec<_>>();
+        for kind in [
+            ValidationIssueKind::ZeroRequestId,
+            ValidationIssueKind::OperationAttributesNotFirst,
+            ValidationIssueKind::UnexpectedValueTag(ValueTag::Keyword),
+        ] {
+            assert!(kinds.contains(&kind), "{:?} not in {:?}", kind, kinds);
+        }
+    }
+}
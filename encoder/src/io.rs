@@ -0,0 +1,54 @@
+//! # ipp_encoder::io
+//!
+//! `std::io` with the `std` feature. Without it, a minimal `Write`
+//! implemented for `Vec<u8>` so that [`IppEncode::to_writer`](crate::encoder::IppEncode::to_writer)
+//! is available on `no_std` targets.
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, Result, Write};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::{Error, Result, Write};
+
+/// why writing into a `Vec<u8>` can fail at all
+pub(crate) const TOO_LONG: &str = "name or value longer than 65535 bytes";
+
+/// `len` as a 2-byte name-length or value-length, an error past 65535 bytes
+/// rather than a length that wraps around
+pub(crate) fn length_u16(len: usize) -> Result<u16> {
+    #[cfg(feature = "std")]
+    let too_long = || Error::new(std::io::ErrorKind::InvalidInput, TOO_LONG);
+    #[cfg(not(feature = "std"))]
+    let too_long = || Error;
+
+    u16::try_from(len).map_err(|_| too_long())
+}
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use alloc::vec::Vec;
+
+    /// write failure, a name or value too long for its 2-byte length
+    #[derive(Debug)]
+    pub struct Error;
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Subset of `std::io::Write` used by the encoder
+    pub trait Write {
+        fn write_all(&mut self, buf: &[u8]) -> Result<()>;
+    }
+
+    impl Write for Vec<u8> {
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            self.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            (**self).write_all(buf)
+        }
+    }
+}
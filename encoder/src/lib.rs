@@ -6,49 +6,64 @@
 //! - [`spec`](spec/index.html): RFC specification type mapping
 //! - [`encoder`](encoder/index.html): core implementation for encoding & decoding IPP operation
 //!
+//! The most commonly used types of both are re-exported in [`prelude`](prelude/index.html).
+//!
+//! ## Features
+//!
+//! - `std` (default): operations, attribute groups, 'dateTime' values and JSON serialization.
+//!   Without it the crate is `no_std` and only needs `alloc`
+//! - `tokio`: async reading & writing of operations, implies `std`
+//! - `base64`: include `Operation::data` in serialization as base64, implies `std`
+//! - `compression`: `Operation::decompressed_data` for gzip and deflate documents, implies `std`
+//! - `test-support`: `encoder::assert_ipp_len_matches`, for tests and fuzzing of encoders
+//!
 //! ## Examples
 //!
 //! See [ipp/server](https://github.com/vnphanquang/ipp/blob/main/server/src/main.rs) for full IPP server example
 //!
-//! ```rust
-//! use ipp_encoder::encoder::Operation;
+//! ```rust,no_run
+//! use ipp_encoder::prelude::*;
 //!
 //! let request: Vec<u8> = Vec::new();
 //!
 //! // ... get raw bytes from ipp server
 //! // request = ...
 //!
-//! let (_, request) = Operation::from(&request, 0);
+//! let (_, request) = Operation::from_ipp(&request, 0);
 //!
-//! println!("Request: {}", request.to_json()); // operation can be serialized
+//! println!("Request: {}", request.to_json().unwrap()); // operation can be serialized
 //!
 //! // from spec same byte can be operation_id (request) or status_code (response)
-//! println!"OperationID: {}", request.operation_id().unwrap() as i32);
+//! println!("OperationID: {}", request.operation_id().unwrap() as i32);
 //!
-//! for (_, attribute_group) in request.attribute_groups {
-//!     for (_, attribute) in attribute_group.attributes {
-//!         // do something
-//!     }
+//! for (group, attribute) in request.attributes() {
+//!     // do something
 //! }
 //!
 //! // request.data contain trailing bytes (for example: postscript file)
 //!
 //! // later ...
 //!
-//! let mut response = Operation {
-//!     version: IppVersion { major: 1, minor: 1 },
-//!     request_id: request.request_id,
-//!     operation_id_or_status_code: IppStatusCode::SuccessfulOk as u16,
-//!     attribute_groups: HashMap::new(),
-//!     data: Vec::new(),
-//! };
+//! let mut response = Operation::new_response(
+//!     IppVersion::V1_1,
+//!     StatusCode::SuccessfulOk,
+//!     request.request_id,
+//! );
+//! response.set_attributes_charset("utf-8");
+//! response.set_attributes_natural_language("en-US");
 //!
-//! println!("Response: {}", response.to_json()) // operation can be deserialized
+//! println!("Response: {}", response.to_json().unwrap()); // operation can be deserialized
 //!
 //! // response.to_ipp() for sending back response with IPP server
 //! ```
 //!
 //!
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod encoder;
+pub mod io;
+pub mod prelude;
 pub mod spec;
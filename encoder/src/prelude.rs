@@ -0,0 +1,21 @@
+//! # ipp_encoder::prelude
+//!
+//! Types a typical IPP server or client needs, in one import:
+//!
+//! ```rust
+//! use ipp_encoder::prelude::*;
+//! ```
+
+pub use crate::encoder::{
+    Attribute, AttributeName, AttributeValue, IppEncode, IppVersion, RangeOfInteger, Resolution,
+    TextWithLang,
+};
+#[cfg(feature = "std")]
+pub use crate::encoder::{
+    AttributeGroup, IppError, IppRequest, IppResponse, Operation, OperationBuilder, ResponseBuilder,
+};
+pub use crate::spec::{
+    attribute::{JobAttribute, JobTemplateAttribute, OperationAttribute, PrinterAttribute},
+    operation::{JobState, OperationID, PrinterState, StatusCode},
+    tag::{DelimiterTag, ValueTag},
+};
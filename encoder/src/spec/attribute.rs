@@ -1,11 +1,39 @@
 use serde::{Deserialize, Serialize};
-use strum_macros::EnumString;
+use strum_macros::{EnumIter, EnumString};
+
+use super::{
+    tag::ValueTag,
+    value::{MediaSourceKeyword, MediaTypeKeyword, MultipleDocumentHandlingKeyword, SidesKeyword},
+    KeywordAttribute,
+};
+
+// attribute syntaxes, 'text' and 'name' take the value-tag with or without language
+const TEXT: &[ValueTag] = &[ValueTag::TextWithoutLanguage, ValueTag::TextWithLanguage];
+const NAME: &[ValueTag] = &[ValueTag::NameWithoutLanguage, ValueTag::NameWithLanguage];
+const KEYWORD: &[ValueTag] = &[ValueTag::Keyword];
+const KEYWORD_OR_NAME: &[ValueTag] = &[
+    ValueTag::Keyword,
+    ValueTag::NameWithoutLanguage,
+    ValueTag::NameWithLanguage,
+];
+const URI: &[ValueTag] = &[ValueTag::Uri];
+const URI_SCHEME: &[ValueTag] = &[ValueTag::UriScheme];
+const CHARSET: &[ValueTag] = &[ValueTag::Charset];
+const NATURAL_LANGUAGE: &[ValueTag] = &[ValueTag::NaturalLanguage];
+const MIME_MEDIA_TYPE: &[ValueTag] = &[ValueTag::MimeMediaType];
+const INTEGER: &[ValueTag] = &[ValueTag::Integer];
+const BOOLEAN: &[ValueTag] = &[ValueTag::Boolean];
+const ENUM: &[ValueTag] = &[ValueTag::Enum];
+const DATE_TIME: &[ValueTag] = &[ValueTag::DateTime];
+const RANGE_OF_INTEGER: &[ValueTag] = &[ValueTag::RangeOfInteger];
+const RESOLUTION: &[ValueTag] = &[ValueTag::Resolution];
 
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4)
 #[derive(
     Serialize,
     Deserialize,
     EnumString,
+    EnumIter,
     strum_macros::Display,
     Debug,
     PartialEq,
@@ -89,6 +117,94 @@ pub enum PrinterAttribute {
     PagesPerMinute,
     #[strum(serialize = "pages-per-minute-color")]
     PagesPerMinuteColor,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.11)
+    #[strum(serialize = "media-supported")]
+    MediaSupported,
+    /// ref: [pwg5100.7](https://ftp.pwg.org/pub/pwg/candidates/cs-ippjobext21-20230210-5100.7.pdf)
+    #[strum(serialize = "media-source-supported")]
+    MediaSourceSupported,
+    /// ref: [pwg5100.7](https://ftp.pwg.org/pub/pwg/candidates/cs-ippjobext21-20230210-5100.7.pdf)
+    #[strum(serialize = "media-type-supported")]
+    MediaTypeSupported,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.8)
+    #[strum(serialize = "sides-supported")]
+    SidesSupported,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.8)
+    #[strum(serialize = "sides-default")]
+    SidesDefault,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.5)
+    #[strum(serialize = "copies-supported")]
+    CopiesSupported,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.5)
+    #[strum(serialize = "copies-default")]
+    CopiesDefault,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.4)
+    #[strum(serialize = "multiple-document-handling-supported")]
+    MultipleDocumentHandlingSupported,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.4)
+    #[strum(serialize = "multiple-document-handling-default")]
+    MultipleDocumentHandlingDefault,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.10)
+    #[strum(serialize = "orientation-requested-supported")]
+    OrientationRequestedSupported,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.13)
+    #[strum(serialize = "print-quality-default")]
+    PrintQualityDefault,
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.13)
+    #[strum(serialize = "print-quality-supported")]
+    PrintQualitySupported,
+}
+
+impl PrinterAttribute {
+    /// value-tags of the attribute syntax, out-of-band values aside
+    pub fn value_tags(self) -> &'static [ValueTag] {
+        match self {
+            Self::PrinterUriSupported => URI,
+            Self::UriSecuritySupported | Self::UriAuthenticationSupported => KEYWORD,
+            Self::PrinterName => NAME,
+            Self::PrinterLocation | Self::PrinterInfo | Self::PrinterMakeAndModel => TEXT,
+            Self::PrinterMoreInfo
+            | Self::PrinterDriverInstaller
+            | Self::PrinterMoreInfoManufacturer => URI,
+            Self::PrinterState => ENUM,
+            Self::PrinterStateReasons => KEYWORD,
+            Self::PrinterStateMessage => TEXT,
+            Self::IppVersionsSupported => KEYWORD,
+            Self::OperationsSupported => ENUM,
+            Self::MultipleDocumentJobsSupported => BOOLEAN,
+            Self::CharsetConfigured | Self::CharsetSupported => CHARSET,
+            Self::NaturalLanguageConfigured | Self::GeneratedNaturalLanguageSupported => {
+                NATURAL_LANGUAGE
+            }
+            Self::DocumentFormatDefault | Self::DocumentFormatSupported => MIME_MEDIA_TYPE,
+            Self::PrinterIsAcceptingJobs => BOOLEAN,
+            Self::QueuedJobCount => INTEGER,
+            Self::PrinterMessageFromOperator => TEXT,
+            Self::ColorSupported => BOOLEAN,
+            Self::ReferenceUriSchemesSupported => URI_SCHEME,
+            Self::PdlOverrideSupported => KEYWORD,
+            Self::PrinterUpTime => INTEGER,
+            Self::PrinterCurrentTime => DATE_TIME,
+            Self::MultipleOperationTimeOut => INTEGER,
+            Self::CompressionSupported => KEYWORD,
+            Self::JobKOctetsSupported
+            | Self::JobImpressionsSupported
+            | Self::JobMediaSheetsSupported => RANGE_OF_INTEGER,
+            Self::PagesPerMinute | Self::PagesPerMinuteColor => INTEGER,
+            Self::MediaSupported | Self::MediaSourceSupported | Self::MediaTypeSupported => {
+                KEYWORD_OR_NAME
+            }
+            Self::SidesSupported | Self::SidesDefault => KEYWORD,
+            Self::CopiesSupported => RANGE_OF_INTEGER,
+            Self::CopiesDefault => INTEGER,
+            Self::MultipleDocumentHandlingSupported | Self::MultipleDocumentHandlingDefault => {
+                KEYWORD
+            }
+            Self::OrientationRequestedSupported
+            | Self::PrintQualityDefault
+            | Self::PrintQualitySupported => ENUM,
+        }
+    }
 }
 
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2)
@@ -96,6 +212,7 @@ pub enum PrinterAttribute {
     Serialize,
     Deserialize,
     EnumString,
+    EnumIter,
     strum_macros::Display,
     Debug,
     PartialEq,
@@ -131,6 +248,50 @@ pub enum JobTemplateAttribute {
     PrinterResolution,
     #[strum(serialize = "print-quality")]
     PrintQuality,
+    /// ref: [pwg5100.7](https://ftp.pwg.org/pub/pwg/candidates/cs-ippjobext21-20230210-5100.7.pdf)
+    #[strum(serialize = "media-source")]
+    MediaSource,
+    /// ref: [pwg5100.7](https://ftp.pwg.org/pub/pwg/candidates/cs-ippjobext21-20230210-5100.7.pdf)
+    #[strum(serialize = "media-type")]
+    MediaType,
+}
+
+impl JobTemplateAttribute {
+    /// value-tags of the attribute syntax, out-of-band values aside
+    pub fn value_tags(self) -> &'static [ValueTag] {
+        match self {
+            Self::JobPriority => INTEGER,
+            Self::JobHoldUntil | Self::JobSheets => KEYWORD_OR_NAME,
+            Self::MultipleDocumentHandling => KEYWORD,
+            Self::Copies => INTEGER,
+            Self::Finishings => ENUM,
+            Self::PageRanges => RANGE_OF_INTEGER,
+            Self::Sides => KEYWORD,
+            Self::NumberUp => INTEGER,
+            Self::OrientationRequested => ENUM,
+            Self::Media => KEYWORD_OR_NAME,
+            Self::PrinterResolution => RESOLUTION,
+            Self::PrintQuality => ENUM,
+            Self::MediaSource | Self::MediaType => KEYWORD_OR_NAME,
+        }
+    }
+
+    /// Whether `value` is a keyword of the spec enum for this attribute,
+    /// None for attributes this crate has no such enum for
+    ///
+    /// 'media-source' and 'media-type' also take names, only 'keyword' values
+    /// should be checked.
+    pub fn is_valid_keyword(self, value: &str) -> Option<bool> {
+        match self {
+            Self::Sides => Some(SidesKeyword::validate(value)),
+            Self::MultipleDocumentHandling => {
+                Some(MultipleDocumentHandlingKeyword::validate(value))
+            }
+            Self::MediaSource => Some(MediaSourceKeyword::validate(value)),
+            Self::MediaType => Some(MediaTypeKeyword::validate(value)),
+            _ => None,
+        }
+    }
 }
 
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.3)
@@ -138,6 +299,7 @@ pub enum JobTemplateAttribute {
     Serialize,
     Deserialize,
     EnumString,
+    EnumIter,
     strum_macros::Display,
     Debug,
     PartialEq,
@@ -205,12 +367,46 @@ pub enum JobAttribute {
     JobMediaSheetsCompleted,
 }
 
+impl JobAttribute {
+    /// value-tags of the attribute syntax, out-of-band values aside
+    pub fn value_tags(self) -> &'static [ValueTag] {
+        match self {
+            Self::JobUri | Self::JobPrinterUri | Self::JobMoreInfo => URI,
+            Self::JobId => INTEGER,
+            Self::JobName | Self::JobOriginatingUserName => NAME,
+            Self::JobState => ENUM,
+            Self::JobStateReasons => KEYWORD,
+            Self::JobStateMessage
+            | Self::JobDetailedStatusMessages
+            | Self::JobDocumentAccessErrors => TEXT,
+            Self::NumberOfDocuments => INTEGER,
+            Self::OutputDeviceAssigned => NAME,
+            Self::TimeAtCreation
+            | Self::TimeAtProcessing
+            | Self::TimeAtCompleted
+            | Self::JobPrinterUpTime => INTEGER,
+            Self::DateTimeAtCreation | Self::DateTimeAtProcessing | Self::DateTimeAtCompleted => {
+                DATE_TIME
+            }
+            Self::NumberOfInterveningJobs => INTEGER,
+            Self::JobMessageFromOperator => TEXT,
+            Self::JobKOctets
+            | Self::JobImpressions
+            | Self::JobMediaSheets
+            | Self::JobKOctetsProcessed
+            | Self::JobImpressionsCompleted
+            | Self::JobMediaSheetsCompleted => INTEGER,
+        }
+    }
+}
+
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2)
 
 #[derive(
     Serialize,
     Deserialize,
     EnumString,
+    EnumIter,
     strum_macros::Display,
     Debug,
     PartialEq,
@@ -230,4 +426,46 @@ pub enum OperationAttribute {
     /// https://datatracker.ietf.org/doc/html/rfc8011#section-5.3.20
     #[strum(serialize = "attributes-natural-language")]
     AttributesNaturalLanguage,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.6
+    #[strum(serialize = "requesting-user-name")]
+    RequestingUserName,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.6.1
+    #[strum(serialize = "limit")]
+    Limit,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.6.1
+    #[strum(serialize = "which-jobs")]
+    WhichJobs,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.1.1
+    #[strum(serialize = "compression")]
+    Compression,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.1.1
+    #[strum(serialize = "document-format")]
+    DocumentFormat,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.3.1.1
+    #[strum(serialize = "last-document")]
+    LastDocument,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.6.2
+    #[strum(serialize = "status-message")]
+    StatusMessage,
+    /// https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.6.3
+    #[strum(serialize = "detailed-status-message")]
+    DetailedStatusMessage,
+}
+
+impl OperationAttribute {
+    /// value-tags of the attribute syntax, out-of-band values aside
+    pub fn value_tags(self) -> &'static [ValueTag] {
+        match self {
+            Self::RequestedAttributes => KEYWORD,
+            Self::PrinterUri => URI,
+            Self::AttributesCharset => CHARSET,
+            Self::AttributesNaturalLanguage => NATURAL_LANGUAGE,
+            Self::RequestingUserName => NAME,
+            Self::Limit => INTEGER,
+            Self::WhichJobs | Self::Compression => KEYWORD,
+            Self::DocumentFormat => MIME_MEDIA_TYPE,
+            Self::LastDocument => BOOLEAN,
+            Self::StatusMessage | Self::DetailedStatusMessage => TEXT,
+        }
+    }
 }
@@ -7,3 +7,63 @@ pub mod attribute;
 pub mod operation;
 pub mod tag;
 pub mod value;
+
+use core::str::FromStr;
+
+/// 'enum' values of the spec, looked up by their integer value
+pub trait IppEnum: Sized {
+    /// None for values the spec enum does not list (e.g. vendor extensions)
+    fn from_value(value: i32) -> Option<Self>;
+}
+
+macro_rules! impl_ipp_enum {
+    ($($enum:ty),*) => {
+        $(
+            impl IppEnum for $enum {
+                fn from_value(value: i32) -> Option<Self> {
+                    Self::from_repr(usize::try_from(value).ok()?)
+                }
+            }
+        )*
+    };
+}
+
+impl_ipp_enum!(
+    operation::PrinterState,
+    operation::JobState,
+    operation::OperationID,
+    value::OrientationRequested,
+    value::PrintQuality
+);
+
+/// 'keyword' values of the spec, looked up by their name
+pub trait KeywordAttribute: FromStr {
+    /// whether `value` is one of the keywords the spec enum lists
+    fn validate(value: &str) -> bool {
+        Self::from_str(value).is_ok()
+    }
+}
+
+macro_rules! impl_keyword_attribute {
+    ($($enum:ty),*) => {
+        $(
+            impl KeywordAttribute for $enum {}
+        )*
+    };
+}
+
+impl_keyword_attribute!(
+    value::UriSecuritySupportedKeyword,
+    value::UriAuthenticationSupportedKeyword,
+    value::PrinterStateReasonKeyword,
+    value::PrinterStateReason,
+    value::JobStateReasonKeyword,
+    value::PdlOverrideSupportedKeyword,
+    value::CompressionSupportedKeyword,
+    value::WhichJobsKeyword,
+    value::SidesKeyword,
+    value::MultipleDocumentHandlingKeyword,
+    value::MediaSourceKeyword,
+    value::MediaTypeKeyword,
+    value::MediaKeyword
+);
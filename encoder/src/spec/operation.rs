@@ -1,4 +1,12 @@
-use strum_macros::FromRepr;
+use alloc::{string::String, vec, vec::Vec};
+use core::{ops::RangeInclusive, str::FromStr};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use strum_macros::{EnumIter, EnumString, FromRepr, IntoStaticStr};
+
+use super::{
+    attribute::{JobAttribute, OperationAttribute},
+    tag::DelimiterTag,
+};
 
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.11)
 #[derive(FromRepr, Debug, PartialEq, Eq, Clone, Copy)]
@@ -20,61 +28,518 @@ pub enum JobState {
     Completed = 9,
 }
 
-/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.15)
-#[derive(FromRepr, Debug, PartialEq, Eq, Clone, Copy)]
+/// Displayed, parsed and serialized as its registry name, e.g. "Get-Printer-Attributes"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.15),
+/// later operations as assigned in the
+/// [IANA registry](https://www.iana.org/assignments/ipp-registrations/ipp-registrations.xhtml)
+#[derive(
+    FromRepr,
+    EnumString,
+    EnumIter,
+    IntoStaticStr,
+    strum_macros::Display,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+)]
 pub enum OperationID {
+    #[strum(serialize = "Print-Job")]
     PrintJob = 0x0002,
+    #[strum(serialize = "Print-URI")]
     PrintUri = 0x0003,
+    #[strum(serialize = "Validate-Job")]
     ValidateJob = 0x0004,
+    #[strum(serialize = "Create-Job")]
     CreateJob = 0x0005,
+    #[strum(serialize = "Send-Document")]
     SendDocument = 0x0006,
+    #[strum(serialize = "Send-URI")]
     SendUri = 0x0007,
+    #[strum(serialize = "Cancel-Job")]
     CancelJob = 0x0008,
+    #[strum(serialize = "Get-Job-Attributes")]
     GetJobAttributes = 0x0009,
+    #[strum(serialize = "Get-Jobs")]
     GetJobs = 0x000A,
+    #[strum(serialize = "Get-Printer-Attributes")]
     GetPrinterAttributes = 0x000B,
+    #[strum(serialize = "Hold-Job")]
     HoldJob = 0x000C,
+    #[strum(serialize = "Release-Job")]
     ReleaseJob = 0x000D,
+    #[strum(serialize = "Restart-Job")]
     RestartJob = 0x000E,
+    #[strum(serialize = "Pause-Printer")]
     PausePrinter = 0x0010,
+    #[strum(serialize = "Resume-Printer")]
     ResumePrinter = 0x0011,
+    #[strum(serialize = "Purge-Jobs")]
     PurgeJobs = 0x0012,
+    /// 0x0013, ref: [rfc3380](https://datatracker.ietf.org/doc/html/rfc3380#section-4)
+    #[strum(serialize = "Set-Printer-Attributes")]
+    SetPrinterAttributes = 0x0013,
+    /// 0x0014, ref: [rfc3380](https://datatracker.ietf.org/doc/html/rfc3380#section-4)
+    #[strum(serialize = "Set-Job-Attributes")]
+    SetJobAttributes = 0x0014,
+    /// 0x0015, ref: [rfc3380](https://datatracker.ietf.org/doc/html/rfc3380#section-4)
+    #[strum(serialize = "Get-Printer-Supported-Values")]
+    GetPrinterSupportedValues = 0x0015,
+    /// 0x0016, ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    #[strum(serialize = "Create-Printer-Subscriptions")]
+    CreatePrinterSubscriptions = 0x0016,
+    /// 0x0017, ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    #[strum(serialize = "Create-Job-Subscriptions")]
+    CreateJobSubscriptions = 0x0017,
+    /// 0x0018, ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    #[strum(serialize = "Get-Subscription-Attributes")]
+    GetSubscriptionAttributes = 0x0018,
+    /// 0x0019, ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    #[strum(serialize = "Get-Subscriptions")]
+    GetSubscriptions = 0x0019,
+    /// 0x001A, ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    #[strum(serialize = "Renew-Subscription")]
+    RenewSubscription = 0x001A,
+    /// 0x001B, ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    #[strum(serialize = "Cancel-Subscription")]
+    CancelSubscription = 0x001B,
+    /// 0x001C, ref: [rfc3996](https://datatracker.ietf.org/doc/html/rfc3996#section-5)
+    #[strum(serialize = "Get-Notifications")]
+    GetNotifications = 0x001C,
+    /// 0x0022, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Enable-Printer")]
+    EnablePrinter = 0x0022,
+    /// 0x0023, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Disable-Printer")]
+    DisablePrinter = 0x0023,
+    /// 0x0024, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Pause-Printer-After-Current-Job")]
+    PausePrinterAfterCurrentJob = 0x0024,
+    /// 0x0025, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Hold-New-Jobs")]
+    HoldNewJobs = 0x0025,
+    /// 0x0026, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Release-Held-New-Jobs")]
+    ReleaseHeldNewJobs = 0x0026,
+    /// 0x0027, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Deactivate-Printer")]
+    DeactivatePrinter = 0x0027,
+    /// 0x0028, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Activate-Printer")]
+    ActivatePrinter = 0x0028,
+    /// 0x0029, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Restart-Printer")]
+    RestartPrinter = 0x0029,
+    /// 0x002A, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Shutdown-Printer")]
+    ShutdownPrinter = 0x002A,
+    /// 0x002B, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Startup-Printer")]
+    StartupPrinter = 0x002B,
+    /// 0x002C, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Reprocess-Job")]
+    ReprocessJob = 0x002C,
+    /// 0x002D, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Cancel-Current-Job")]
+    CancelCurrentJob = 0x002D,
+    /// 0x002E, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Suspend-Current-Job")]
+    SuspendCurrentJob = 0x002E,
+    /// 0x002F, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Resume-Job")]
+    ResumeJob = 0x002F,
+    /// 0x0030, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Promote-Job")]
+    PromoteJob = 0x0030,
+    /// 0x0031, ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-3)
+    #[strum(serialize = "Schedule-Job-After")]
+    ScheduleJobAfter = 0x0031,
+    /// 0x0033, PWG 5100.5
+    #[strum(serialize = "Cancel-Document")]
+    CancelDocument = 0x0033,
+    /// 0x0034, PWG 5100.5
+    #[strum(serialize = "Get-Document-Attributes")]
+    GetDocumentAttributes = 0x0034,
+    /// 0x0035, PWG 5100.5
+    #[strum(serialize = "Get-Documents")]
+    GetDocuments = 0x0035,
+    /// 0x0036, PWG 5100.5
+    #[strum(serialize = "Delete-Document")]
+    DeleteDocument = 0x0036,
+    /// 0x0037, PWG 5100.5
+    #[strum(serialize = "Set-Document-Attributes")]
+    SetDocumentAttributes = 0x0037,
+    /// 0x0038, PWG 5100.11
+    #[strum(serialize = "Cancel-Jobs")]
+    CancelJobs = 0x0038,
+    /// 0x0039, PWG 5100.11
+    #[strum(serialize = "Cancel-My-Jobs")]
+    CancelMyJobs = 0x0039,
+    /// 0x003A, PWG 5100.11
+    #[strum(serialize = "Resubmit-Job")]
+    ResubmitJob = 0x003A,
+    /// 0x003B, PWG 5100.11
+    #[strum(serialize = "Close-Job")]
+    CloseJob = 0x003B,
+    /// 0x003C, PWG 5100.13
+    #[strum(serialize = "Identify-Printer")]
+    IdentifyPrinter = 0x003C,
+    /// 0x003D, PWG 5100.13
+    #[strum(serialize = "Validate-Document")]
+    ValidateDocument = 0x003D,
+
+    // CUPS vendor operations, 0x4000 to 0x7FFF is the vendor range
+    // ref: [cups](https://www.cups.org/doc/spec-ipp.html#OPERATIONS)
+    #[strum(serialize = "CUPS-Get-Default")]
+    CupsGetDefault = 0x4001,
+    #[strum(serialize = "CUPS-Get-Printers")]
+    CupsGetPrinters = 0x4002,
+    #[strum(serialize = "CUPS-Add-Modify-Printer")]
+    CupsAddModifyPrinter = 0x4003,
+    #[strum(serialize = "CUPS-Delete-Printer")]
+    CupsDeletePrinter = 0x4004,
+    #[strum(serialize = "CUPS-Get-Classes")]
+    CupsGetClasses = 0x4005,
+    #[strum(serialize = "CUPS-Add-Modify-Class")]
+    CupsAddModifyClass = 0x4006,
+    #[strum(serialize = "CUPS-Delete-Class")]
+    CupsDeleteClass = 0x4007,
+    #[strum(serialize = "CUPS-Accept-Jobs")]
+    CupsAcceptJobs = 0x4008,
+    #[strum(serialize = "CUPS-Reject-Jobs")]
+    CupsRejectJobs = 0x4009,
+    #[strum(serialize = "CUPS-Set-Default")]
+    CupsSetDefault = 0x400A,
+    #[strum(serialize = "CUPS-Get-Devices")]
+    CupsGetDevices = 0x400B,
+    #[strum(serialize = "CUPS-Get-PPDs")]
+    CupsGetPpds = 0x400C,
+    #[strum(serialize = "CUPS-Move-Job")]
+    CupsMoveJob = 0x400D,
+    #[strum(serialize = "CUPS-Authenticate-Job")]
+    CupsAuthenticateJob = 0x400E,
+    #[strum(serialize = "CUPS-Get-PPD")]
+    CupsGetPpd = 0x400F,
+    #[strum(serialize = "CUPS-Get-Document")]
+    CupsGetDocument = 0x4027,
+    #[strum(serialize = "CUPS-Create-Local-Printer")]
+    CupsCreateLocalPrinter = 0x4028,
+}
+
+impl OperationID {
+    /// registry name, e.g. "Print-Job"
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+
+    /// vendor operation (0x4000 to 0x7FFF), e.g. the CUPS ones
+    pub fn is_vendor_extension(self) -> bool {
+        (0x4000..=0x7FFF).contains(&(self as u16))
+    }
+
+    /// Whether a request for this operation is followed by document data
+    pub fn carries_document_data(self) -> bool {
+        matches!(self, Self::PrintJob | Self::SendDocument)
+    }
+
+    /// Attributes a printer must always return in a successful response to this operation
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.2)
+    pub fn required_response_attributes(self) -> Vec<ResponseAttribute> {
+        let mut required = vec![
+            ResponseAttribute::Operation(OperationAttribute::AttributesCharset),
+            ResponseAttribute::Operation(OperationAttribute::AttributesNaturalLanguage),
+        ];
+
+        match self {
+            Self::PrintJob
+            | Self::PrintUri
+            | Self::CreateJob
+            | Self::SendDocument
+            | Self::SendUri => {
+                required.extend(
+                    [
+                        JobAttribute::JobUri,
+                        JobAttribute::JobId,
+                        JobAttribute::JobState,
+                        JobAttribute::JobStateReasons,
+                    ]
+                    .map(ResponseAttribute::Job),
+                );
+            }
+            _ => {}
+        }
+
+        required
+    }
 }
 
+/// Attribute of a response, by the group it is returned in,
+/// see [`OperationID::required_response_attributes`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ResponseAttribute {
+    Operation(OperationAttribute),
+    Job(JobAttribute),
+}
+
+impl ResponseAttribute {
+    pub fn group(self) -> DelimiterTag {
+        match self {
+            Self::Operation(_) => DelimiterTag::OperationAttributes,
+            Self::Job(_) => DelimiterTag::JobAttributes,
+        }
+    }
+}
+
+/// Displayed, parsed and serialized as its registry keyword, e.g. "client-error-not-found"
+///
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#appendix-B.1.2.1)
-#[derive(FromRepr, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(
+    FromRepr, EnumString, EnumIter, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy,
+)]
 pub enum StatusCode {
+    #[strum(serialize = "successful-ok")]
     SuccessfulOk = 0x0000,
+    #[strum(serialize = "successful-ok-ignored-or-substituted-attributes")]
     SuccessfulOkIgnoredOrSubstitutedAttributes = 0x0001,
+    #[strum(serialize = "successful-ok-conflicting-attributes")]
     SuccessfulOkConflictingAttributes = 0x0002,
+    /// ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995#section-13)
+    #[strum(serialize = "successful-ok-ignored-subscriptions")]
+    SuccessfulOkIgnoredSubscriptions = 0x0003,
+    /// ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995#section-13)
+    #[strum(serialize = "successful-ok-too-many-events")]
+    SuccessfulOkTooManyEvents = 0x0005,
+    /// ref: [rfc3996](https://datatracker.ietf.org/doc/html/rfc3996#section-10)
+    #[strum(serialize = "successful-ok-events-complete")]
+    SuccessfulOkEventsComplete = 0x0007,
+    #[strum(serialize = "client-error-bad-request")]
     ClientErrorBadRequest = 0x0400,
+    #[strum(serialize = "client-error-forbidden")]
     ClientErrorForbidden = 0x0401,
+    #[strum(serialize = "client-error-not-authenticated")]
     ClientErrorNotAuthenticated = 0x0402,
+    #[strum(serialize = "client-error-not-authorized")]
     ClientErrorNotAuthorized = 0x0403,
+    #[strum(serialize = "client-error-not-possible")]
     ClientErrorNotPossible = 0x0404,
+    #[strum(serialize = "client-error-timeout")]
     ClientErrorTimeout = 0x0405,
+    #[strum(serialize = "client-error-not-found")]
     ClientErrorNotFound = 0x0406,
+    #[strum(serialize = "client-error-gone")]
     ClientErrorGone = 0x0407,
+    #[strum(serialize = "client-error-request-entity-too-large")]
     ClientErrorRequestEntityTooLarge = 0x0408,
+    #[strum(serialize = "client-error-request-value-too-long")]
     ClientErrorRequestValueTooLong = 0x0409,
+    #[strum(serialize = "client-error-document-format-not-supported")]
     ClientErrorDocumentFormatNotSupported = 0x040A,
+    #[strum(serialize = "client-error-attributes-or-values-not-supported")]
     ClientErrorAttributesOrValuesNotSupported = 0x040B,
+    #[strum(serialize = "client-error-uri-scheme-not-supported")]
     ClientErrorUriSchemeNotSupported = 0x040C,
+    #[strum(serialize = "client-error-charset-not-supported")]
     ClientErrorCharsetNotSupported = 0x040D,
+    #[strum(serialize = "client-error-conflicting-attributes")]
     ClientErrorConflictingAttributes = 0x040E,
+    #[strum(serialize = "client-error-compression-not-supported")]
     ClientErrorCompressionNotSupported = 0x040F,
+    #[strum(serialize = "client-error-compression-error")]
     ClientErrorCompressionError = 0x0410,
+    #[strum(serialize = "client-error-document-format-error")]
     ClientErrorDocumentFormatError = 0x0411,
+    #[strum(serialize = "client-error-document-access-error")]
     ClientErrorDocumentAccessError = 0x0412,
+    /// ref: [rfc3380](https://datatracker.ietf.org/doc/html/rfc3380#section-7)
+    #[strum(serialize = "client-error-attributes-not-settable")]
+    ClientErrorAttributesNotSettable = 0x0413,
+    /// ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995#section-13)
+    #[strum(serialize = "client-error-ignored-all-subscriptions")]
+    ClientErrorIgnoredAllSubscriptions = 0x0414,
+    /// ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995#section-13)
+    #[strum(serialize = "client-error-too-many-subscriptions")]
+    ClientErrorTooManySubscriptions = 0x0415,
+    /// PWG 5100.13
+    #[strum(serialize = "client-error-document-password-error")]
+    ClientErrorDocumentPasswordError = 0x0418,
+    /// PWG 5100.13
+    #[strum(serialize = "client-error-document-permission-error")]
+    ClientErrorDocumentPermissionError = 0x0419,
+    /// PWG 5100.13
+    #[strum(serialize = "client-error-document-security-error")]
+    ClientErrorDocumentSecurityError = 0x041A,
+    /// PWG 5100.13
+    #[strum(serialize = "client-error-document-unprintable-error")]
+    ClientErrorDocumentUnprintableError = 0x041B,
+    /// PWG 5100.16
+    #[strum(serialize = "client-error-account-info-needed")]
+    ClientErrorAccountInfoNeeded = 0x041C,
+    /// PWG 5100.16
+    #[strum(serialize = "client-error-account-closed")]
+    ClientErrorAccountClosed = 0x041D,
+    /// PWG 5100.16
+    #[strum(serialize = "client-error-account-limit-reached")]
+    ClientErrorAccountLimitReached = 0x041E,
+    /// PWG 5100.16
+    #[strum(serialize = "client-error-account-authorization-failed")]
+    ClientErrorAccountAuthorizationFailed = 0x041F,
+    /// PWG 5100.18
+    #[strum(serialize = "client-error-not-fetchable")]
+    ClientErrorNotFetchable = 0x0420,
+    #[strum(serialize = "server-error-internal-error")]
     ServerErrorInternalError = 0x0500,
+    #[strum(serialize = "server-error-operation-not-supported")]
     ServerErrorOperationNotSupported = 0x0501,
+    #[strum(serialize = "server-error-service-unavailable")]
     ServerErrorServiceUnavailable = 0x0502,
+    #[strum(serialize = "server-error-version-not-supported")]
     ServerErrorVersionNotSupported = 0x0503,
+    #[strum(serialize = "server-error-device-error")]
     ServerErrorDeviceError = 0x0504,
+    #[strum(serialize = "server-error-temporary-error")]
     ServerErrorTemporaryError = 0x0505,
+    #[strum(serialize = "server-error-not-accepting-jobs")]
     ServerErrorNotAcceptingJobs = 0x0506,
+    #[strum(serialize = "server-error-busy")]
     ServerErrorBusy = 0x0507,
+    #[strum(serialize = "server-error-job-canceled")]
     ServerErrorJobCanceled = 0x0508,
+    #[strum(serialize = "server-error-multiple-document-jobs-not-supported")]
     ServerErrorMultipleDocumentJobsNotSupported = 0x0509,
+    /// ref: [rfc3998](https://datatracker.ietf.org/doc/html/rfc3998#section-6)
+    #[strum(serialize = "server-error-printer-is-deactivated")]
+    ServerErrorPrinterIsDeactivated = 0x050A,
+    /// PWG 5100.7
+    #[strum(serialize = "server-error-too-many-jobs")]
+    ServerErrorTooManyJobs = 0x050B,
+    /// PWG 5100.7
+    #[strum(serialize = "server-error-too-many-documents")]
+    ServerErrorTooManyDocuments = 0x050C,
+    /// catch-all, not a registered status-code
+    #[strum(serialize = "unknown-status-code")]
     UnknownStatusCode = 0xffff,
 }
+
+/// Classes of status-codes by range, which also covers codes outside [`StatusCode`]
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#appendix-B.1)
+impl StatusCode {
+    pub const SUCCESSFUL: RangeInclusive<u16> = 0x0000..=0x00FF;
+    pub const INFORMATIONAL: RangeInclusive<u16> = 0x0100..=0x01FF;
+    pub const REDIRECTION: RangeInclusive<u16> = 0x0200..=0x02FF;
+    pub const CLIENT_ERROR: RangeInclusive<u16> = 0x0400..=0x04FF;
+    pub const SERVER_ERROR: RangeInclusive<u16> = 0x0500..=0x05FF;
+
+    pub fn is_successful(self) -> bool {
+        Self::SUCCESSFUL.contains(&(self as u16))
+    }
+
+    pub fn is_informational(self) -> bool {
+        Self::INFORMATIONAL.contains(&(self as u16))
+    }
+
+    pub fn is_redirection(self) -> bool {
+        Self::REDIRECTION.contains(&(self as u16))
+    }
+
+    pub fn is_client_error(self) -> bool {
+        Self::CLIENT_ERROR.contains(&(self as u16))
+    }
+
+    pub fn is_server_error(self) -> bool {
+        Self::SERVER_ERROR.contains(&(self as u16))
+    }
+}
+
+impl Serialize for OperationID {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for OperationID {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::from_str(&name)
+            .map_err(|_| D::Error::custom(format_args!("unknown operation-id: {}", name)))
+    }
+}
+
+impl Serialize for StatusCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword)
+            .map_err(|_| D::Error::custom(format_args!("unknown status-code: {}", keyword)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn status_code_keywords_round_trip() {
+        assert_eq!(
+            StatusCode::ClientErrorDocumentFormatNotSupported.to_string(),
+            "client-error-document-format-not-supported"
+        );
+
+        for status_code in StatusCode::iter() {
+            let keyword = status_code.to_string();
+            assert!(
+                keyword.chars().all(|c| c.is_ascii_lowercase() || c == '-'),
+                "{}",
+                keyword
+            );
+            assert_eq!(StatusCode::from_str(&keyword), Ok(status_code));
+            assert_eq!(
+                StatusCode::from_repr(status_code as usize),
+                Some(status_code)
+            );
+        }
+        assert!(StatusCode::from_str("ClientErrorNotFound").is_err());
+    }
+
+    #[test]
+    fn status_code_classes() {
+        assert!(StatusCode::SuccessfulOk.is_successful());
+        assert!(StatusCode::SuccessfulOkConflictingAttributes.is_successful());
+        assert!(StatusCode::ClientErrorNotFound.is_client_error());
+        assert!(!StatusCode::ClientErrorNotFound.is_server_error());
+        assert!(StatusCode::ServerErrorBusy.is_server_error());
+        assert!(!StatusCode::UnknownStatusCode.is_successful());
+        assert!(!StatusCode::UnknownStatusCode.is_client_error());
+
+        // vendor codes outside the enum are classified by range alone
+        assert!(StatusCode::from_repr(0x0080).is_none());
+        assert!(StatusCode::SUCCESSFUL.contains(&0x0080));
+        assert!(StatusCode::CLIENT_ERROR.contains(&0x0480));
+        assert!(StatusCode::SERVER_ERROR.contains(&0x05ff));
+        assert!(!StatusCode::SUCCESSFUL.contains(&0x0300));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn status_code_serde_round_trips() {
+        for status_code in StatusCode::iter() {
+            let json = serde_json::to_string(&status_code).unwrap();
+            assert_eq!(json, format!("\"{}\"", status_code));
+            assert_eq!(
+                serde_json::from_str::<StatusCode>(&json).unwrap(),
+                status_code
+            );
+        }
+        assert!(serde_json::from_str::<StatusCode>(r#""client-error-bogus""#).is_err());
+    }
+}
@@ -1,5 +1,8 @@
-use serde::{Deserialize, Serialize};
-use strum_macros::{EnumString, FromRepr};
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString, FromRepr, IntoStaticStr};
 
 /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.5.1)
 #[derive(
@@ -21,39 +24,334 @@ pub enum DelimiterTag {
     EndOfAttributes = 0x03,
     PrinterAttributes = 0x04,
     UnsupportedAttributes = 0x05,
+    /// ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    SubscriptionAttributes = 0x06,
+    /// ref: [rfc3995](https://datatracker.ietf.org/doc/html/rfc3995)
+    EventNotificationAttributes = 0x07,
+}
+
+impl DelimiterTag {
+    /// Whether `raw` is a delimiter tag, registered or reserved (0x00 to 0x0F)
+    ///
+    /// A reserved tag begins a group this crate has no variant for. Decoding drops its
+    /// attributes up to the next delimiter tag rather than reading the tag as a value-tag.
+    ///
+    /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.5.1)
+    pub fn is_delimiter(raw: u8) -> bool {
+        raw <= 0x0F
+    }
 }
 
 /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.5.2)
-#[derive(Serialize, Deserialize, FromRepr, Debug, PartialEq, Eq, Clone, Copy)]
+///
+/// Serialized as its registry name, e.g. `"nameWithoutLanguage"`; deserialization also
+/// accepts the tag number and the variant name of earlier releases.
+/// [`ValueTag::CharacterString`] is serialized as its tag number.
+#[derive(EnumString, EnumIter, FromRepr, IntoStaticStr, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
 pub enum ValueTag {
     // "out-of-band" values - "Out-of-Band Attribute Value Tags" registry
+    #[strum(serialize = "unsupported")]
     Unsupported = 0x10,
+    #[strum(serialize = "unknown")]
     Unknown = 0x12,
+    #[strum(serialize = "no-value")]
     NoValue = 0x13,
 
     // integer values - "Attribute Syntaxes" registry
+    #[strum(serialize = "integer")]
     Integer = 0x21,
+    #[strum(serialize = "boolean")]
     Boolean = 0x22,
+    #[strum(serialize = "enum")]
     Enum = 0x23,
 
     // octetString values - "Attribute Syntaxes" registry
+    #[strum(serialize = "octetString")]
     OctetStringUnspecified = 0x30,
+    #[strum(serialize = "dateTime")]
     DateTime = 0x31,
+    #[strum(serialize = "resolution")]
     Resolution = 0x32,
+    #[strum(serialize = "rangeOfInteger")]
     RangeOfInteger = 0x33,
+    #[strum(serialize = "begCollection")]
     BegCollection = 0x34,
+    #[strum(serialize = "textWithLanguage")]
     TextWithLanguage = 0x35,
+    #[strum(serialize = "nameWithLanguage")]
     NameWithLanguage = 0x36,
+    #[strum(serialize = "endCollection")]
     EndCollection = 0x37,
 
     // character-string values - "Attribute Syntaxes" registry
+    #[strum(serialize = "textWithoutLanguage")]
     TextWithoutLanguage = 0x41,
+    #[strum(serialize = "nameWithoutLanguage")]
     NameWithoutLanguage = 0x42,
+    #[strum(serialize = "keyword")]
     Keyword = 0x44,
+    #[strum(serialize = "uri")]
     Uri = 0x45,
+    #[strum(serialize = "uriScheme")]
     UriScheme = 0x46,
+    #[strum(serialize = "charset")]
     Charset = 0x47,
+    #[strum(serialize = "naturalLanguage")]
     NaturalLanguage = 0x48,
+    #[strum(serialize = "mimeMediaType")]
     MimeMediaType = 0x49,
+    #[strum(serialize = "memberAttrName")]
     MemberAttrName = 0x4a,
+
+    /// character-string tag (0x40 to 0x5F) registered after this crate, with its raw value
+    ///
+    /// Its values are read and written as strings, so they round-trip with their tag.
+    /// Only produced by [`ValueTag::from_wire`], it is left out of `from_repr` and `iter`.
+    #[strum(disabled)]
+    CharacterString(u8),
+}
+
+impl ValueTag {
+    /// Value-tag of a raw tag byte, None for delimiter and unknown tags
+    ///
+    /// A tag not in this enum but within the character-string range (0x40 to 0x5F)
+    /// is a character-string type registered after this crate, it is kept as
+    /// [`ValueTag::CharacterString`] so its values are not lost and it is written back as is.
+    ///
+    /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.5.2)
+    pub fn from_wire(raw: u8) -> Option<Self> {
+        Self::from_repr(raw)
+            .or_else(|| is_character_string_range(raw).then_some(Self::CharacterString(raw)))
+    }
+
+    /// Tag byte on the wire, inverse of [`ValueTag::from_wire`]
+    pub fn raw(self) -> u8 {
+        match self {
+            Self::Unsupported => 0x10,
+            Self::Unknown => 0x12,
+            Self::NoValue => 0x13,
+            Self::Integer => 0x21,
+            Self::Boolean => 0x22,
+            Self::Enum => 0x23,
+            Self::OctetStringUnspecified => 0x30,
+            Self::DateTime => 0x31,
+            Self::Resolution => 0x32,
+            Self::RangeOfInteger => 0x33,
+            Self::BegCollection => 0x34,
+            Self::TextWithLanguage => 0x35,
+            Self::NameWithLanguage => 0x36,
+            Self::EndCollection => 0x37,
+            Self::TextWithoutLanguage => 0x41,
+            Self::NameWithoutLanguage => 0x42,
+            Self::Keyword => 0x44,
+            Self::Uri => 0x45,
+            Self::UriScheme => 0x46,
+            Self::Charset => 0x47,
+            Self::NaturalLanguage => 0x48,
+            Self::MimeMediaType => 0x49,
+            Self::MemberAttrName => 0x4a,
+            Self::CharacterString(raw) => raw,
+        }
+    }
+
+    /// "out-of-band" tags carry no value, their value-length is always 0x0000
+    ///
+    /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.8)
+    pub fn is_out_of_band(self) -> bool {
+        matches!(self, Self::Unsupported | Self::Unknown | Self::NoValue)
+    }
+
+    /// 'integer', 'boolean' and 'enum', tags 0x20 to 0x2F
+    pub fn is_integer_type(self) -> bool {
+        (0x20..=0x2F).contains(&self.raw())
+    }
+
+    /// 'octetString' and the types built on it (e.g. 'dateTime', 'textWithLanguage',
+    /// the collection tags), tags 0x30 to 0x3F
+    pub fn is_octet_string_type(self) -> bool {
+        (0x30..=0x3F).contains(&self.raw())
+    }
+
+    /// 'textWithoutLanguage', 'keyword', 'uri' and the like, tags 0x40 to 0x5F
+    pub fn is_character_string_type(self) -> bool {
+        is_character_string_range(self.raw())
+    }
+
+    /// Attribute syntax of values with this tag, which tells the type they decode to
+    /// (see [`AttributeValue`](crate::encoder::AttributeValue))
+    ///
+    /// 'memberAttrName' values are the names of collection members, so 'keyword';
+    /// 'endCollection' closes a collection and maps to [`ValueSyntax::Collection`] too.
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.1)
+    pub fn syntax(self) -> ValueSyntax {
+        match self {
+            Self::Unsupported | Self::Unknown | Self::NoValue => ValueSyntax::OutOfBand,
+            Self::Integer => ValueSyntax::Integer,
+            Self::Boolean => ValueSyntax::Boolean,
+            Self::Enum => ValueSyntax::Enum,
+            Self::OctetStringUnspecified => ValueSyntax::OctetString,
+            Self::DateTime => ValueSyntax::DateTime,
+            Self::Resolution => ValueSyntax::Resolution,
+            Self::RangeOfInteger => ValueSyntax::RangeOfInteger,
+            Self::BegCollection | Self::EndCollection => ValueSyntax::Collection,
+            Self::TextWithLanguage | Self::TextWithoutLanguage | Self::CharacterString(_) => {
+                ValueSyntax::Text
+            }
+            Self::NameWithLanguage | Self::NameWithoutLanguage => ValueSyntax::Name,
+            Self::Keyword | Self::MemberAttrName => ValueSyntax::Keyword,
+            Self::Uri => ValueSyntax::Uri,
+            Self::UriScheme => ValueSyntax::UriScheme,
+            Self::Charset => ValueSyntax::Charset,
+            Self::NaturalLanguage => ValueSyntax::NaturalLanguage,
+            Self::MimeMediaType => ValueSyntax::MimeMediaType,
+        }
+    }
+
+    /// value-length every value of this tag has, None for variable-length values
+    ///
+    /// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.9)
+    pub fn expected_fixed_len(self) -> Option<usize> {
+        match self {
+            Self::Integer | Self::Enum => Some(4),
+            Self::Boolean => Some(1),
+            Self::DateTime => Some(11),
+            Self::Resolution => Some(9),
+            Self::RangeOfInteger => Some(8),
+            Self::Unsupported
+            | Self::Unknown
+            | Self::NoValue
+            | Self::BegCollection
+            | Self::EndCollection => Some(0),
+            _ => None,
+        }
+    }
+}
+
+/// Attribute syntaxes, what [`ValueTag::syntax`] maps each value-tag to
+///
+/// 'text' and 'name' cover both their with- and without-language forms.
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.1)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ValueSyntax {
+    Integer,
+    Boolean,
+    Enum,
+    Text,
+    Name,
+    Keyword,
+    Uri,
+    UriScheme,
+    Charset,
+    NaturalLanguage,
+    MimeMediaType,
+    DateTime,
+    Resolution,
+    RangeOfInteger,
+    Collection,
+    OctetString,
+    /// 'unsupported', 'unknown' and 'no-value', see [`ValueTag::is_out_of_band`]
+    OutOfBand,
+}
+
+/// registry name, the tag number in hex for [`ValueTag::CharacterString`]
+impl fmt::Display for ValueTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CharacterString(raw) => write!(f, "0x{:02x}", raw),
+            tag => f.write_str(tag.into()),
+        }
+    }
+}
+
+impl Serialize for ValueTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::CharacterString(raw) => serializer.serialize_u8(*raw),
+            tag => serializer.collect_str(tag),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ValueTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawValueTag::deserialize(deserializer)? {
+            RawValueTag::Number(number) => Self::from_wire(number)
+                .ok_or_else(|| D::Error::custom(format_args!("unknown value tag: {}", number))),
+            RawValueTag::Name(name) => Self::from_str(&name)
+                .ok()
+                .or_else(|| Self::iter().find(|tag| format!("{:?}", tag) == name))
+                .ok_or_else(|| D::Error::custom(format_args!("unknown value tag: {}", name))),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValueTag {
+    Number(u8),
+    Name(String),
+}
+
+fn is_character_string_range(raw: u8) -> bool {
+    (0x40..=0x5F).contains(&raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_is_the_discriminant() {
+        for tag in ValueTag::iter() {
+            assert_eq!(ValueTag::from_wire(tag.raw()), Some(tag));
+        }
+    }
+
+    #[test]
+    fn unregistered_character_string_tag_is_kept() {
+        let tag = ValueTag::from_wire(0x4f).unwrap();
+        assert_eq!(tag, ValueTag::CharacterString(0x4f));
+        assert_eq!(tag.raw(), 0x4f);
+        assert!(tag.is_character_string_type());
+        assert_eq!(tag.syntax(), ValueSyntax::Text);
+        assert_eq!(ValueTag::from_wire(0x60), None);
+    }
+
+    #[test]
+    fn syntax_of_each_tag() {
+        let syntaxes = [
+            (ValueTag::Unsupported, ValueSyntax::OutOfBand),
+            (ValueTag::Unknown, ValueSyntax::OutOfBand),
+            (ValueTag::NoValue, ValueSyntax::OutOfBand),
+            (ValueTag::Integer, ValueSyntax::Integer),
+            (ValueTag::Boolean, ValueSyntax::Boolean),
+            (ValueTag::Enum, ValueSyntax::Enum),
+            (ValueTag::OctetStringUnspecified, ValueSyntax::OctetString),
+            (ValueTag::DateTime, ValueSyntax::DateTime),
+            (ValueTag::Resolution, ValueSyntax::Resolution),
+            (ValueTag::RangeOfInteger, ValueSyntax::RangeOfInteger),
+            (ValueTag::BegCollection, ValueSyntax::Collection),
+            (ValueTag::TextWithLanguage, ValueSyntax::Text),
+            (ValueTag::NameWithLanguage, ValueSyntax::Name),
+            (ValueTag::EndCollection, ValueSyntax::Collection),
+            (ValueTag::TextWithoutLanguage, ValueSyntax::Text),
+            (ValueTag::NameWithoutLanguage, ValueSyntax::Name),
+            (ValueTag::Keyword, ValueSyntax::Keyword),
+            (ValueTag::Uri, ValueSyntax::Uri),
+            (ValueTag::UriScheme, ValueSyntax::UriScheme),
+            (ValueTag::Charset, ValueSyntax::Charset),
+            (ValueTag::NaturalLanguage, ValueSyntax::NaturalLanguage),
+            (ValueTag::MimeMediaType, ValueSyntax::MimeMediaType),
+            (ValueTag::MemberAttrName, ValueSyntax::Keyword),
+            (ValueTag::CharacterString(0x5f), ValueSyntax::Text),
+        ];
+        assert_eq!(syntaxes.len(), ValueTag::iter().count() + 1);
+        for (tag, syntax) in syntaxes {
+            assert_eq!(tag.syntax(), syntax, "{}", tag);
+            assert_eq!(tag.is_out_of_band(), syntax == ValueSyntax::OutOfBand);
+        }
+    }
 }
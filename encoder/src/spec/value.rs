@@ -1,4 +1,10 @@
-use strum_macros::EnumString;
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use strum_macros::{EnumString, FromRepr};
+
+use super::attribute::PrinterAttribute;
+use crate::encoder::Attribute;
 
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.3)
 #[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
@@ -24,11 +30,229 @@ pub enum UriAuthenticationSupportedKeyword {
     Certificate,
 }
 
+/// Base keywords of 'printer-state-reasons', without the severity suffix
+/// a value may carry, see [`PrinterStateReason`]
+///
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.12)
 #[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum PrinterStateReasonKeyword {
     #[strum(serialize = "none")]
     None,
+    #[strum(serialize = "other")]
+    Other,
+    #[strum(serialize = "media-needed")]
+    MediaNeeded,
+    #[strum(serialize = "media-jam")]
+    MediaJam,
+    #[strum(serialize = "moving-to-paused")]
+    MovingToPaused,
+    #[strum(serialize = "paused")]
+    Paused,
+    #[strum(serialize = "shutdown")]
+    Shutdown,
+    #[strum(serialize = "connecting-to-device")]
+    ConnectingToDevice,
+    #[strum(serialize = "timed-out")]
+    TimedOut,
+    #[strum(serialize = "stopping")]
+    Stopping,
+    #[strum(serialize = "stopped-partly")]
+    StoppedPartly,
+    #[strum(serialize = "toner-low")]
+    TonerLow,
+    #[strum(serialize = "toner-empty")]
+    TonerEmpty,
+    #[strum(serialize = "spool-area-full")]
+    SpoolAreaFull,
+    #[strum(serialize = "cover-open")]
+    CoverOpen,
+    #[strum(serialize = "interlock-open")]
+    InterlockOpen,
+    #[strum(serialize = "door-open")]
+    DoorOpen,
+    #[strum(serialize = "input-tray-missing")]
+    InputTrayMissing,
+    #[strum(serialize = "media-low")]
+    MediaLow,
+    #[strum(serialize = "media-empty")]
+    MediaEmpty,
+    #[strum(serialize = "output-tray-missing")]
+    OutputTrayMissing,
+    #[strum(serialize = "output-area-almost-full")]
+    OutputAreaAlmostFull,
+    #[strum(serialize = "output-area-full")]
+    OutputAreaFull,
+    #[strum(serialize = "marker-supply-low")]
+    MarkerSupplyLow,
+    #[strum(serialize = "marker-supply-empty")]
+    MarkerSupplyEmpty,
+    #[strum(serialize = "marker-waste-almost-full")]
+    MarkerWasteAlmostFull,
+    #[strum(serialize = "marker-waste-full")]
+    MarkerWasteFull,
+    #[strum(serialize = "fuser-over-temp")]
+    FuserOverTemp,
+    #[strum(serialize = "fuser-under-temp")]
+    FuserUnderTemp,
+    #[strum(serialize = "opc-near-eol")]
+    OpcNearEol,
+    #[strum(serialize = "opc-life-over")]
+    OpcLifeOver,
+    #[strum(serialize = "developer-low")]
+    DeveloperLow,
+    #[strum(serialize = "developer-empty")]
+    DeveloperEmpty,
+    #[strum(serialize = "interpreter-resource-unavailable")]
+    InterpreterResourceUnavailable,
+}
+
+/// Severity suffix of a 'printer-state-reasons' value, e.g. "-warning" in "media-low-warning"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.12)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReasonSeverity {
+    #[strum(serialize = "report")]
+    Report,
+    #[strum(serialize = "warning")]
+    Warning,
+    #[strum(serialize = "error")]
+    Error,
+}
+
+/// 'printer-state-reasons' value: a keyword and its optional severity suffix
+///
+/// Parsed from and displayed as the keyword as sent, e.g. "toner-low-report" or "paused"
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PrinterStateReason {
+    pub keyword: PrinterStateReasonKeyword,
+    pub severity: Option<ReasonSeverity>,
+}
+
+impl PrinterStateReason {
+    pub fn new(keyword: PrinterStateReasonKeyword, severity: Option<ReasonSeverity>) -> Self {
+        Self { keyword, severity }
+    }
+}
+
+impl FromStr for PrinterStateReason {
+    type Err = strum::ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(keyword) = PrinterStateReasonKeyword::from_str(value) {
+            return Ok(Self::new(keyword, None));
+        }
+
+        let (keyword, severity) = value
+            .rsplit_once('-')
+            .ok_or(strum::ParseError::VariantNotFound)?;
+        Ok(Self::new(
+            PrinterStateReasonKeyword::from_str(keyword)?,
+            Some(ReasonSeverity::from_str(severity)?),
+        ))
+    }
+}
+
+impl core::fmt::Display for PrinterStateReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.severity {
+            Some(severity) => write!(f, "{}-{}", self.keyword, severity),
+            None => write!(f, "{}", self.keyword),
+        }
+    }
+}
+
+impl Serialize for PrinterStateReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PrinterStateReason {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword).map_err(|_| {
+            D::Error::custom(format_args!("unknown printer-state-reasons: {}", keyword))
+        })
+    }
+}
+
+/// Displayed, parsed and serialized as its keyword, e.g. "job-completed-successfully"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.3.8)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JobStateReasonKeyword {
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "job-incoming")]
+    JobIncoming,
+    #[strum(serialize = "job-data-insufficient")]
+    JobDataInsufficient,
+    #[strum(serialize = "document-access-error")]
+    DocumentAccessError,
+    #[strum(serialize = "submission-interrupted")]
+    SubmissionInterrupted,
+    #[strum(serialize = "job-outgoing")]
+    JobOutgoing,
+    #[strum(serialize = "job-hold-until-specified")]
+    JobHoldUntilSpecified,
+    #[strum(serialize = "resources-are-not-ready")]
+    ResourcesAreNotReady,
+    #[strum(serialize = "printer-stopped-partly")]
+    PrinterStoppedPartly,
+    #[strum(serialize = "printer-stopped")]
+    PrinterStopped,
+    #[strum(serialize = "job-interpreting")]
+    JobInterpreting,
+    #[strum(serialize = "job-queued")]
+    JobQueued,
+    #[strum(serialize = "job-transforming")]
+    JobTransforming,
+    #[strum(serialize = "job-queued-for-marker")]
+    JobQueuedForMarker,
+    #[strum(serialize = "job-printing")]
+    JobPrinting,
+    #[strum(serialize = "job-canceled-by-user")]
+    JobCanceledByUser,
+    #[strum(serialize = "job-canceled-by-operator")]
+    JobCanceledByOperator,
+    #[strum(serialize = "job-canceled-at-device")]
+    JobCanceledAtDevice,
+    #[strum(serialize = "aborted-by-system")]
+    AbortedBySystem,
+    #[strum(serialize = "unsupported-compression")]
+    UnsupportedCompression,
+    #[strum(serialize = "compression-error")]
+    CompressionError,
+    #[strum(serialize = "unsupported-document-format")]
+    UnsupportedDocumentFormat,
+    #[strum(serialize = "document-format-error")]
+    DocumentFormatError,
+    #[strum(serialize = "processing-to-stop-point")]
+    ProcessingToStopPoint,
+    #[strum(serialize = "job-completed-successfully")]
+    JobCompletedSuccessfully,
+    #[strum(serialize = "job-completed-with-warnings")]
+    JobCompletedWithWarnings,
+    #[strum(serialize = "job-completed-with-errors")]
+    JobCompletedWithErrors,
+    #[strum(serialize = "job-restartable")]
+    JobRestartable,
+    #[strum(serialize = "queued-in-device")]
+    QueuedInDevice,
+}
+
+impl Serialize for JobStateReasonKeyword {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for JobStateReasonKeyword {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword)
+            .map_err(|_| D::Error::custom(format_args!("unknown job-state-reasons: {}", keyword)))
+    }
 }
 
 /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.32)
@@ -52,3 +276,581 @@ pub enum CompressionSupportedKeyword {
     #[strum(serialize = "compress")]
     Compress,
 }
+
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.6.1)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WhichJobsKeyword {
+    #[strum(serialize = "completed")]
+    Completed,
+    #[strum(serialize = "not-completed")]
+    NotCompleted,
+}
+
+/// Displayed, parsed and serialized as its keyword, e.g. "two-sided-long-edge"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.8)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SidesKeyword {
+    #[strum(serialize = "one-sided")]
+    OneSided,
+    #[strum(serialize = "two-sided-long-edge")]
+    TwoSidedLongEdge,
+    #[strum(serialize = "two-sided-short-edge")]
+    TwoSidedShortEdge,
+}
+
+impl SidesKeyword {
+    /// 'sides-supported' printer attribute with the given keywords
+    pub fn supported_attribute(supported: impl IntoIterator<Item = Self>) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::SidesSupported,
+            supported.into_iter().map(|sides| sides.to_string()),
+        )
+    }
+
+    /// 'sides-default' printer attribute with this keyword
+    pub fn default_attribute(self) -> Attribute {
+        Attribute::keyword(PrinterAttribute::SidesDefault, self.to_string())
+    }
+}
+
+impl Serialize for SidesKeyword {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SidesKeyword {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword)
+            .map_err(|_| D::Error::custom(format_args!("unknown sides: {}", keyword)))
+    }
+}
+
+/// Displayed, parsed and serialized as its keyword, e.g. "separate-documents-collated-copies"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.4)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MultipleDocumentHandlingKeyword {
+    #[strum(serialize = "single-document")]
+    SingleDocument,
+    #[strum(serialize = "separate-documents-uncollated-copies")]
+    SeparateDocumentsUncollatedCopies,
+    #[strum(serialize = "separate-documents-collated-copies")]
+    SeparateDocumentsCollatedCopies,
+    #[strum(serialize = "single-document-new-sheet")]
+    SingleDocumentNewSheet,
+}
+
+impl MultipleDocumentHandlingKeyword {
+    /// 'multiple-document-handling-supported' printer attribute with the given keywords
+    pub fn supported_attribute(supported: impl IntoIterator<Item = Self>) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::MultipleDocumentHandlingSupported,
+            supported.into_iter().map(|handling| handling.to_string()),
+        )
+    }
+
+    /// 'multiple-document-handling-default' printer attribute with this keyword
+    pub fn default_attribute(self) -> Attribute {
+        Attribute::keyword(
+            PrinterAttribute::MultipleDocumentHandlingDefault,
+            self.to_string(),
+        )
+    }
+}
+
+impl Serialize for MultipleDocumentHandlingKeyword {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MultipleDocumentHandlingKeyword {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword).map_err(|_| {
+            D::Error::custom(format_args!(
+                "unknown multiple-document-handling: {}",
+                keyword
+            ))
+        })
+    }
+}
+
+/// ref: [pwg5100.7](https://ftp.pwg.org/pub/pwg/candidates/cs-ippjobext21-20230210-5100.7.pdf)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MediaSourceKeyword {
+    #[strum(serialize = "auto")]
+    Auto,
+    #[strum(serialize = "main")]
+    Main,
+    #[strum(serialize = "alternate")]
+    Alternate,
+    #[strum(serialize = "large-capacity")]
+    LargeCapacity,
+    #[strum(serialize = "manual")]
+    Manual,
+    #[strum(serialize = "envelope")]
+    Envelope,
+    #[strum(serialize = "disc")]
+    Disc,
+    #[strum(serialize = "photo")]
+    Photo,
+    #[strum(serialize = "hagaki")]
+    Hagaki,
+    #[strum(serialize = "main-roll")]
+    MainRoll,
+    #[strum(serialize = "alternate-roll")]
+    AlternateRoll,
+    #[strum(serialize = "top")]
+    Top,
+    #[strum(serialize = "middle")]
+    Middle,
+    #[strum(serialize = "bottom")]
+    Bottom,
+    #[strum(serialize = "side")]
+    Side,
+    #[strum(serialize = "left")]
+    Left,
+    #[strum(serialize = "right")]
+    Right,
+    #[strum(serialize = "center")]
+    Center,
+    #[strum(serialize = "rear")]
+    Rear,
+    #[strum(serialize = "by-pass-tray")]
+    ByPassTray,
+    #[strum(serialize = "tray-1")]
+    Tray1,
+    #[strum(serialize = "tray-2")]
+    Tray2,
+    #[strum(serialize = "tray-3")]
+    Tray3,
+    #[strum(serialize = "tray-4")]
+    Tray4,
+    #[strum(serialize = "tray-5")]
+    Tray5,
+    #[strum(serialize = "tray-6")]
+    Tray6,
+    #[strum(serialize = "tray-7")]
+    Tray7,
+    #[strum(serialize = "tray-8")]
+    Tray8,
+    #[strum(serialize = "tray-9")]
+    Tray9,
+    #[strum(serialize = "tray-10")]
+    Tray10,
+    #[strum(serialize = "tray-11")]
+    Tray11,
+    #[strum(serialize = "tray-12")]
+    Tray12,
+    #[strum(serialize = "tray-13")]
+    Tray13,
+    #[strum(serialize = "tray-14")]
+    Tray14,
+    #[strum(serialize = "tray-15")]
+    Tray15,
+    #[strum(serialize = "tray-16")]
+    Tray16,
+    #[strum(serialize = "tray-17")]
+    Tray17,
+    #[strum(serialize = "tray-18")]
+    Tray18,
+    #[strum(serialize = "tray-19")]
+    Tray19,
+    #[strum(serialize = "tray-20")]
+    Tray20,
+    #[strum(serialize = "roll-1")]
+    Roll1,
+    #[strum(serialize = "roll-2")]
+    Roll2,
+    #[strum(serialize = "roll-3")]
+    Roll3,
+    #[strum(serialize = "roll-4")]
+    Roll4,
+    #[strum(serialize = "roll-5")]
+    Roll5,
+    #[strum(serialize = "roll-6")]
+    Roll6,
+    #[strum(serialize = "roll-7")]
+    Roll7,
+    #[strum(serialize = "roll-8")]
+    Roll8,
+    #[strum(serialize = "roll-9")]
+    Roll9,
+    #[strum(serialize = "roll-10")]
+    Roll10,
+}
+
+/// ref: [pwg5100.7](https://ftp.pwg.org/pub/pwg/candidates/cs-ippjobext21-20230210-5100.7.pdf)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MediaTypeKeyword {
+    #[strum(serialize = "auto")]
+    Auto,
+    #[strum(serialize = "stationery")]
+    Stationery,
+    #[strum(serialize = "stationery-coated")]
+    StationeryCoated,
+    #[strum(serialize = "stationery-inkjet")]
+    StationeryInkjet,
+    #[strum(serialize = "stationery-preprinted")]
+    StationeryPreprinted,
+    #[strum(serialize = "stationery-letterhead")]
+    StationeryLetterhead,
+    #[strum(serialize = "stationery-prepunched")]
+    StationeryPrepunched,
+    #[strum(serialize = "stationery-fine")]
+    StationeryFine,
+    #[strum(serialize = "stationery-heavyweight")]
+    StationeryHeavyweight,
+    #[strum(serialize = "stationery-lightweight")]
+    StationeryLightweight,
+    #[strum(serialize = "transparency")]
+    Transparency,
+    #[strum(serialize = "envelope")]
+    Envelope,
+    #[strum(serialize = "envelope-plain")]
+    EnvelopePlain,
+    #[strum(serialize = "envelope-window")]
+    EnvelopeWindow,
+    #[strum(serialize = "continuous")]
+    Continuous,
+    #[strum(serialize = "continuous-long")]
+    ContinuousLong,
+    #[strum(serialize = "continuous-short")]
+    ContinuousShort,
+    #[strum(serialize = "tab-stock")]
+    TabStock,
+    #[strum(serialize = "pre-cut-tabs")]
+    PreCutTabs,
+    #[strum(serialize = "full-cut-tabs")]
+    FullCutTabs,
+    #[strum(serialize = "multi-part-form")]
+    MultiPartForm,
+    #[strum(serialize = "labels")]
+    Labels,
+    #[strum(serialize = "multi-layer")]
+    MultiLayer,
+    #[strum(serialize = "screen")]
+    Screen,
+    #[strum(serialize = "screen-paged")]
+    ScreenPaged,
+    #[strum(serialize = "photographic")]
+    Photographic,
+    #[strum(serialize = "photographic-glossy")]
+    PhotographicGlossy,
+    #[strum(serialize = "photographic-high-gloss")]
+    PhotographicHighGloss,
+    #[strum(serialize = "photographic-semi-gloss")]
+    PhotographicSemiGloss,
+    #[strum(serialize = "photographic-satin")]
+    PhotographicSatin,
+    #[strum(serialize = "photographic-matte")]
+    PhotographicMatte,
+    #[strum(serialize = "photographic-film")]
+    PhotographicFilm,
+    #[strum(serialize = "back-print-film")]
+    BackPrintFilm,
+    #[strum(serialize = "cardstock")]
+    Cardstock,
+    #[strum(serialize = "roll")]
+    Roll,
+    #[strum(serialize = "disc")]
+    Disc,
+    #[strum(serialize = "other")]
+    Other,
+}
+
+/// Common self-describing media size names
+///
+/// ref: [pwg5101.1](https://ftp.pwg.org/pub/pwg/candidates/cs-pwgmsn20-20130328-5101.1.pdf)
+#[derive(EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MediaKeyword {
+    #[strum(serialize = "iso_a3_297x420mm")]
+    IsoA3,
+    #[strum(serialize = "iso_a4_210x297mm")]
+    IsoA4,
+    #[strum(serialize = "iso_a5_148x210mm")]
+    IsoA5,
+    #[strum(serialize = "iso_b5_176x250mm")]
+    IsoB5,
+    #[strum(serialize = "iso_dl_110x220mm")]
+    IsoDl,
+    #[strum(serialize = "iso_c5_162x229mm")]
+    IsoC5,
+    #[strum(serialize = "na_letter_8.5x11in")]
+    NaLetter,
+    #[strum(serialize = "na_legal_8.5x14in")]
+    NaLegal,
+    #[strum(serialize = "na_ledger_11x17in")]
+    NaLedger,
+    #[strum(serialize = "na_executive_7.25x10.5in")]
+    NaExecutive,
+    #[strum(serialize = "na_number-10_4.125x9.5in")]
+    NaNumber10,
+    #[strum(serialize = "na_index-4x6_4x6in")]
+    NaIndex4x6,
+    #[strum(serialize = "jis_b5_182x257mm")]
+    JisB5,
+}
+
+/// units of a 'resolution' value, written after its dots
+///
+/// ref: [rfc8010](https://datatracker.ietf.org/doc/html/rfc8010#section-3.9)
+#[derive(FromRepr, EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ResolutionUnits {
+    #[strum(serialize = "dpi")]
+    DotsPerInch = 3,
+    #[strum(serialize = "dpcm")]
+    DotsPerCentimeter = 4,
+}
+
+/// Sent as its enum value, displayed, parsed and serialized as its keyword, e.g. "landscape"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.10)
+#[derive(FromRepr, EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OrientationRequested {
+    #[strum(serialize = "portrait")]
+    Portrait = 3,
+    #[strum(serialize = "landscape")]
+    Landscape = 4,
+    #[strum(serialize = "reverse-landscape")]
+    ReverseLandscape = 5,
+    #[strum(serialize = "reverse-portrait")]
+    ReversePortrait = 6,
+    /// added by PWG 5100.13
+    #[strum(serialize = "none")]
+    None = 7,
+}
+
+impl Serialize for OrientationRequested {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for OrientationRequested {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword).map_err(|_| {
+            D::Error::custom(format_args!("unknown orientation-requested: {}", keyword))
+        })
+    }
+}
+
+/// Sent as its enum value, displayed, parsed and serialized as its keyword, e.g. "draft"
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.2.13)
+#[derive(FromRepr, EnumString, strum_macros::Display, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PrintQuality {
+    #[strum(serialize = "draft")]
+    Draft = 3,
+    #[strum(serialize = "normal")]
+    Normal = 4,
+    #[strum(serialize = "high")]
+    High = 5,
+}
+
+impl PrintQuality {
+    /// 'print-quality-supported' printer attribute with the given qualities
+    pub fn supported_attribute(supported: impl IntoIterator<Item = Self>) -> Attribute {
+        Attribute::enum_value_set(
+            PrinterAttribute::PrintQualitySupported,
+            supported.into_iter().map(|quality| quality as i32),
+        )
+    }
+
+    /// 'print-quality-default' printer attribute with this quality
+    pub fn default_attribute(self) -> Attribute {
+        Attribute::enum_value(PrinterAttribute::PrintQualityDefault, self as i32)
+    }
+}
+
+impl Serialize for PrintQuality {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PrintQuality {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        Self::from_str(&keyword)
+            .map_err(|_| D::Error::custom(format_args!("unknown print-quality: {}", keyword)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{attribute::JobTemplateAttribute, tag::ValueTag, KeywordAttribute};
+
+    #[test]
+    fn media_source_and_media_type_keywords() {
+        assert_eq!(
+            MediaSourceKeyword::from_str("tray-1"),
+            Ok(MediaSourceKeyword::Tray1)
+        );
+        assert_eq!(MediaSourceKeyword::Main.to_string(), "main");
+        assert_eq!(
+            MediaTypeKeyword::from_str("photographic"),
+            Ok(MediaTypeKeyword::Photographic)
+        );
+        assert_eq!(MediaTypeKeyword::Transparency.to_string(), "transparency");
+
+        let media_source = JobTemplateAttribute::MediaSource;
+        assert_eq!(media_source.is_valid_keyword("alternate"), Some(true));
+        assert_eq!(media_source.is_valid_keyword("tray-0"), Some(false));
+        assert_eq!(
+            JobTemplateAttribute::MediaType.is_valid_keyword("envelope"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn printer_state_reasons_round_trip() {
+        for reason in [
+            "media-empty-warning",
+            "toner-low-report",
+            "cover-open-error",
+            "opc-near-eol-error",
+            "paused",
+            "none",
+            "interpreter-resource-unavailable",
+        ] {
+            assert_eq!(
+                PrinterStateReason::from_str(reason).unwrap().to_string(),
+                reason
+            );
+        }
+
+        assert_eq!(
+            PrinterStateReason::from_str("media-empty-warning"),
+            Ok(PrinterStateReason::new(
+                PrinterStateReasonKeyword::MediaEmpty,
+                Some(ReasonSeverity::Warning)
+            ))
+        );
+        assert_eq!(
+            PrinterStateReason::from_str("paused"),
+            Ok(PrinterStateReason::new(
+                PrinterStateReasonKeyword::Paused,
+                None
+            ))
+        );
+        for invalid in ["media-empty-fatal", "bogus", "-error", "error"] {
+            assert!(
+                PrinterStateReason::from_str(invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn printer_state_reasons_are_serialized_as_sent() {
+        let reason = PrinterStateReason::new(
+            PrinterStateReasonKeyword::MediaEmpty,
+            Some(ReasonSeverity::Warning),
+        );
+        assert_eq!(
+            serde_json::to_string(&reason).unwrap(),
+            r#""media-empty-warning""#
+        );
+        assert_eq!(
+            serde_json::from_str::<PrinterStateReason>(r#""media-empty-warning""#).unwrap(),
+            reason
+        );
+        assert!(serde_json::from_str::<PrinterStateReason>(r#""bogus-error""#).is_err());
+    }
+
+    #[test]
+    fn orientation_requested_keywords() {
+        assert_eq!(OrientationRequested::Landscape.to_string(), "landscape");
+        assert_eq!(
+            OrientationRequested::from_str("reverse-portrait"),
+            Ok(OrientationRequested::ReversePortrait)
+        );
+        assert_eq!(
+            OrientationRequested::from_repr(5),
+            Some(OrientationRequested::ReverseLandscape)
+        );
+        assert_eq!(OrientationRequested::from_repr(2), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn orientation_requested_is_serialized_as_its_keyword() {
+        assert_eq!(
+            serde_json::to_string(&OrientationRequested::Portrait).unwrap(),
+            r#""portrait""#
+        );
+        assert_eq!(
+            serde_json::from_str::<OrientationRequested>(r#""landscape""#).unwrap(),
+            OrientationRequested::Landscape
+        );
+    }
+
+    #[test]
+    fn multiple_document_handling_round_trips_through_an_attribute() {
+        let handlings = [
+            MultipleDocumentHandlingKeyword::SingleDocument,
+            MultipleDocumentHandlingKeyword::SeparateDocumentsUncollatedCopies,
+            MultipleDocumentHandlingKeyword::SeparateDocumentsCollatedCopies,
+            MultipleDocumentHandlingKeyword::SingleDocumentNewSheet,
+        ];
+        for handling in handlings {
+            let default = handling.default_attribute();
+            let (_, decoded) = Attribute::from_ipp(&default.to_ipp(), 0);
+            let decoded = decoded.unwrap();
+            assert_eq!(decoded.tag, ValueTag::Keyword);
+            assert_eq!(
+                decoded.name,
+                PrinterAttribute::MultipleDocumentHandlingDefault.into()
+            );
+            assert_eq!(
+                MultipleDocumentHandlingKeyword::from_str(decoded.first_str().unwrap()),
+                Ok(handling)
+            );
+            assert!(MultipleDocumentHandlingKeyword::validate(
+                &handling.to_string()
+            ));
+            assert_eq!(
+                JobTemplateAttribute::MultipleDocumentHandling
+                    .is_valid_keyword(&handling.to_string()),
+                Some(true)
+            );
+        }
+
+        let supported = MultipleDocumentHandlingKeyword::supported_attribute(handlings);
+        let (_, decoded) = Attribute::from_ipp(&supported.to_ipp(), 0);
+        let decoded = decoded.unwrap();
+        assert_eq!(
+            decoded
+                .as_strings()
+                .map(|keyword| MultipleDocumentHandlingKeyword::from_str(keyword).unwrap())
+                .collect::<alloc::vec::Vec<_>>(),
+            handlings
+        );
+        assert_eq!(
+            JobTemplateAttribute::MultipleDocumentHandling.is_valid_keyword("collated"),
+            Some(false)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn multiple_document_handling_is_serialized_as_its_keyword() {
+        let handling = MultipleDocumentHandlingKeyword::SeparateDocumentsCollatedCopies;
+        let json = serde_json::to_string(&handling).unwrap();
+        assert_eq!(json, r#""separate-documents-collated-copies""#);
+        assert_eq!(
+            serde_json::from_str::<MultipleDocumentHandlingKeyword>(&json).unwrap(),
+            handling
+        );
+        assert!(serde_json::from_str::<MultipleDocumentHandlingKeyword>(r#""collated""#).is_err());
+    }
+}
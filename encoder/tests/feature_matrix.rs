@@ -0,0 +1,40 @@
+//! Every combination of the crate features must build on its own,
+//! `cargo check` is run for each of them as `cargo hack --feature-powerset` would
+
+use std::process::Command;
+
+const FEATURES: [&str; 5] = ["std", "tokio", "base64", "compression", "test-support"];
+
+#[test]
+fn every_feature_combination_builds() {
+    let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+    // a target directory of its own, the one of `cargo test` is locked while it runs
+    let target = concat!(env!("CARGO_TARGET_TMPDIR"), "/feature-matrix");
+
+    let failed: Vec<String> = (0..1 << FEATURES.len())
+        .map(|set: usize| {
+            FEATURES
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| set & 1 << i != 0)
+                .map(|(_, feature)| *feature)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .filter(|features| {
+            let status = Command::new(env!("CARGO"))
+                .args(["check", "--quiet", "--lib", "--no-default-features"])
+                .args(["--features", features])
+                .args(["--manifest-path", manifest, "--target-dir", target])
+                .status()
+                .expect("cargo check could not be run");
+            !status.success()
+        })
+        .collect();
+
+    assert!(
+        failed.is_empty(),
+        "cargo check failed with features {:?}",
+        failed
+    );
+}
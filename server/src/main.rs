@@ -1,4 +1,5 @@
 use astro_dnssd::DNSServiceBuilder;
+use hyper::body::HttpBody;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Method, Request, Response, Server};
 use std::convert::Infallible;
@@ -7,7 +8,9 @@ use std::sync::Arc;
 
 mod printer;
 
-use printer::IppPrinter;
+use ipp_encoder::encoder::IppEncode;
+use ipp_encoder::spec::value::{UriAuthenticationSupportedKeyword, UriSecuritySupportedKeyword};
+use printer::{IppPrinter, PrinterUris};
 
 #[tokio::main]
 async fn main() {
@@ -19,11 +22,48 @@ async fn main() {
         .to_str()
         .unwrap_or("127.0.0.1")
         .to_string();
-    let uri = format!("ipp//{}:{}/", hostname, PORT);
+    let uri = format!("ipp://{}:{}/", hostname, PORT);
 
     const NAME: &str = "Rust IPP Printer";
+    // documents are held in memory, keep them to what the host can spare
+    const MAX_REQUEST_BYTES: usize = 32 * 1024 * 1024;
 
-    let printer = Arc::new(IppPrinter::new(&uri, NAME));
+    let uris = match PrinterUris::new(
+        &uri,
+        UriSecuritySupportedKeyword::None,
+        UriAuthenticationSupportedKeyword::None,
+    ) {
+        Ok(uris) => uris,
+        Err(e) => {
+            eprintln!("Invalid printer uri: {}", e);
+            return;
+        }
+    };
+
+    let admin_uri = format!("http://{}:{}/", hostname, PORT);
+    let mut printer = IppPrinter::new(uris, NAME)
+        .with_location(&hostname)
+        .with_info("Virtual printer of the ipp_encoder example server")
+        .with_make_and_model(concat!("Rust IPP Printer ", env!("CARGO_PKG_VERSION")))
+        .with_admin_uri(&admin_uri)
+        .with_max_request_bytes(MAX_REQUEST_BYTES);
+
+    // also list the address the server is bound to
+    let local_uri = format!("ipp://{}/", address);
+    if local_uri != uri {
+        printer = match printer.with_uri(
+            &local_uri,
+            UriSecuritySupportedKeyword::None,
+            UriAuthenticationSupportedKeyword::None,
+        ) {
+            Ok(printer) => printer,
+            Err(e) => {
+                eprintln!("Invalid printer uri: {}", e);
+                return;
+            }
+        };
+    }
+    let printer = Arc::new(printer);
 
     let make_svc = make_service_fn(move |_| {
         let inner_printer = printer.clone();
@@ -49,7 +89,6 @@ async fn main() {
             if let Err(e) = graceful.await {
                 eprintln!("server error: {}", e);
             } else {
-                println!("Dropping... {:?}", dns);
                 println!("gracefully shut down!");
             }
         }
@@ -65,33 +104,45 @@ async fn http_handler(
 ) -> Result<Response<Body>, Infallible> {
     let mut res = Response::new(Body::empty());
 
-    println!("============================");
-    println!("Requested in {}, {}", req.method(), req.uri().path());
-    println!(
-        "IPP Printer - printer_uri_supported: {:?}\n",
-        printer.printer_uri_supported()
-    );
-
     match (req.method(), req.uri().path()) {
         (&Method::GET, "/") => {
             *res.body_mut() = Body::from("IPP Server");
         }
         (&Method::POST, "/") => {
-            let bytes = hyper::body::to_bytes(req.into_body())
-                .await
-                .unwrap()
-                .to_vec();
+            let bytes = match read_body(req.into_body(), printer.max_request_bytes()).await {
+                Ok(bytes) => bytes,
+                Err(BodyError::TooLarge(head)) => {
+                    *res.status_mut() = hyper::StatusCode::PAYLOAD_TOO_LARGE;
+                    *res.body_mut() = printer.request_too_large(&head).into();
+                    return Ok(res);
+                }
+                Err(BodyError::Hyper(e)) => {
+                    eprintln!("Request body could not be read: {}", e);
+                    *res.status_mut() = hyper::StatusCode::BAD_REQUEST;
+                    return Ok(res);
+                }
+            };
 
-            let bytes = printer.handle(&bytes);
-
-            // let (_, operation) = Operation::from_ipp(&bytes, 0);
-            // println!("\nResponse Operation Counter: {}", operation.to_json());
+            let bytes = match printer.handle(&bytes) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    eprintln!("Request failed: {}", e);
+                    e.fallback_response().to_ipp()
+                }
+            };
 
             *res.status_mut() = hyper::StatusCode::OK;
             *res.body_mut() = bytes.into();
-
-            // println!("\nResponse Body: {:?}", *res.body());
-            println!("============================");
+        }
+        (&Method::GET, path) if path.starts_with("/jobs/") => {
+            match path["/jobs/".len()..]
+                .parse()
+                .ok()
+                .and_then(|id| printer.job_page(id))
+            {
+                Some(page) => *res.body_mut() = Body::from(page),
+                None => *res.status_mut() = hyper::StatusCode::NOT_FOUND,
+            }
         }
         _ => {
             *res.status_mut() = hyper::StatusCode::NOT_FOUND;
@@ -101,6 +152,39 @@ async fn http_handler(
     Ok(res)
 }
 
+/// why a request body was not read in full
+enum BodyError {
+    /// over the limit, with the bytes read until then (at most the IPP header)
+    TooLarge(Vec<u8>),
+    Hyper(hyper::Error),
+}
+
+/// read `body` chunk by chunk, giving up once it exceeds `max_bytes`
+async fn read_body(mut body: Body, max_bytes: usize) -> Result<Vec<u8>, BodyError> {
+    // version-number, operation-id and request-id, kept for the error response
+    const HEADER_LEN: usize = 8;
+
+    // refuse early when Content-Length already tells
+    if body.size_hint().lower() > max_bytes as u64 {
+        return Err(BodyError::TooLarge(Vec::new()));
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(BodyError::Hyper)?;
+
+        if bytes.len() + chunk.len() > max_bytes {
+            let missing = HEADER_LEN.saturating_sub(bytes.len());
+            bytes.extend(chunk.iter().take(missing));
+            bytes.truncate(HEADER_LEN);
+            return Err(BodyError::TooLarge(bytes));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
 async fn shutdown_signal() {
     // Wait for the CTRL+C signal
     tokio::signal::ctrl_c()
@@ -108,34 +192,77 @@ async fn shutdown_signal() {
         .expect("failed to install CTRL+C signal handler");
 }
 
-// fn test_encoding<T: IppEncode + std::fmt::Debug>(raw: T) {
-//     println!("raw: {:?}", raw);
-//     let encoded = raw.to_ipp();
-//     println!("encoded: {:?}", encoded);
-//     let decoded = T::from_ipp(&encoded, 0);
-//     println!("decoded: {:?}", decoded);
-// }
-
-// fn test() {
-//     // i32
-//     test_encoding(32 as i32);
-
-//     // String
-//     let text_wo_lang = String::from("Text Without Lang");
-//     test_encoding(text_wo_lang);
-
-//     // bool
-//     test_encoding(true);
-//     test_encoding(false);
-
-//     // TextWithLang
-//     let text_with_lang = TextWithLang {
-//         lang: String::from("en"),
-//         text: String::from("Text With Lang"),
-//     };
-//     test_encoding(text_with_lang);
-
-//     // DateTime
-//     let date = Utc::now();
-//     test_encoding(date);o
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ipp_encoder::{encoder::Operation, spec::operation::StatusCode};
+
+    fn printer(max_request_bytes: usize) -> Arc<IppPrinter> {
+        let uris = PrinterUris::new(
+            "ipp://localhost:6363/",
+            UriSecuritySupportedKeyword::None,
+            UriAuthenticationSupportedKeyword::None,
+        )
+        .unwrap();
+        Arc::new(IppPrinter::new(uris, "Test Printer").with_max_request_bytes(max_request_bytes))
+    }
+
+    /// POST `body` to the printer, the http status and the decoded IPP response
+    async fn post(printer: Arc<IppPrinter>, body: Body) -> (hyper::StatusCode, Operation) {
+        let request = Request::post("/").body(body).unwrap();
+        let response = http_handler(request, printer).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        (status, Operation::try_from_ipp(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn request_over_the_limit_is_refused() {
+        // header of a Print-Job with request-id 7, then more than the limit allows
+        let mut body = vec![1, 1, 0x00, 0x02, 0, 0, 0, 7];
+        body.resize(1024, 0);
+
+        // refused by its length before anything is read
+        let (status, response) = post(printer(512), Body::from(body.clone())).await;
+        assert_eq!(status, hyper::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            response.status_code(),
+            Some(StatusCode::ClientErrorRequestEntityTooLarge)
+        );
+
+        // refused once the chunks read go over, echoing the request-id
+        let chunks = body
+            .chunks(256)
+            .map(|chunk| Ok::<_, Infallible>(chunk.to_vec()))
+            .collect::<Vec<_>>();
+        let (status, response) = post(
+            printer(512),
+            Body::wrap_stream(futures::stream::iter(chunks)),
+        )
+        .await;
+        assert_eq!(status, hyper::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            response.status_code(),
+            Some(StatusCode::ClientErrorRequestEntityTooLarge)
+        );
+        assert_eq!(response.request_id, 7);
+    }
+
+    #[tokio::test]
+    async fn undecodable_request_gets_bad_request() {
+        // an IPP header without its attribute groups, then a plain http request
+        for body in [
+            &[1, 1, 0x00, 0x0b, 0, 0, 0, 1][..],
+            b"GET / HTTP/1.1\r\n\r\n",
+        ] {
+            let (status, response) = post(printer(1024), Body::from(body)).await;
+            assert_eq!(status, hyper::StatusCode::OK);
+            assert_eq!(
+                response.status_code(),
+                Some(StatusCode::ClientErrorBadRequest)
+            );
+            assert_eq!(response.attributes_charset(), Some("utf-8"));
+        }
+    }
+}
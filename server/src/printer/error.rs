@@ -0,0 +1,64 @@
+use ipp_encoder::{
+    encoder::{IppError, Operation},
+    spec::operation::StatusCode,
+};
+
+#[derive(Debug)]
+pub enum ServerError {
+    /// reading or writing the printer's own files
+    Io(std::io::Error),
+    /// the request body is not an IPP request
+    Decode(IppError),
+    /// the document could not be decompressed
+    Conversion(std::io::Error),
+}
+
+impl ServerError {
+    /// IPP status-code reported to the client for this error
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Io(_) => StatusCode::ServerErrorInternalError,
+            Self::Decode(_) => StatusCode::ClientErrorBadRequest,
+            Self::Conversion(_) => StatusCode::ClientErrorCompressionError,
+        }
+    }
+
+    /// Response for when there is no decoded request to echo,
+    /// see [`Operation::bad_request_fallback`]
+    pub fn fallback_response(&self) -> Operation {
+        let mut response = Operation::bad_request_fallback();
+        response.operation_id_or_status_code = self.status_code() as u16;
+        response
+    }
+}
+
+impl std::fmt::Display for ServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "io error: {}", err),
+            Self::Decode(err) => write!(f, "malformed request: {}", err),
+            Self::Conversion(err) => write!(f, "document could not be decompressed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) | Self::Conversion(err) => Some(err),
+            Self::Decode(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for ServerError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<IppError> for ServerError {
+    fn from(err: IppError) -> Self {
+        Self::Decode(err)
+    }
+}
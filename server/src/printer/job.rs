@@ -1 +1,132 @@
-pub struct IppJob;
+use ipp_encoder::{
+    attributes,
+    encoder::{Attribute, AttributeGroup},
+    spec::{
+        attribute::JobAttribute, operation::JobState, tag::DelimiterTag,
+        value::JobStateReasonKeyword,
+    },
+};
+
+pub struct IppJob {
+    pub id: i32,
+    pub uri: String,
+    pub printer_uri: String,
+    /// http page describing the job, only when the admin pages are enabled
+    pub more_info: Option<String>,
+    pub name: String,
+    /// from 'requesting-user-name' of the creating request
+    pub originating_user_name: String,
+    /// seconds since the printer started, same basis as 'printer-up-time'
+    pub time_at_creation: i32,
+    pub state: JobState,
+    /// document data in the order received, one per Print-Job or Send-Document
+    pub documents: Vec<Vec<u8>>,
+}
+
+impl IppJob {
+    pub fn new(
+        id: i32,
+        printer_uri: &str,
+        admin_uri: Option<&str>,
+        name: &str,
+        originating_user_name: &str,
+        time_at_creation: i32,
+    ) -> Self {
+        Self {
+            id,
+            uri: format!("{}/jobs/{}", printer_uri.trim_end_matches('/'), id),
+            printer_uri: String::from(printer_uri),
+            more_info: admin_uri
+                .map(|admin_uri| format!("{}/jobs/{}", admin_uri.trim_end_matches('/'), id)),
+            name: String::from(name),
+            originating_user_name: String::from(originating_user_name),
+            time_at_creation,
+            state: JobState::Pending,
+            documents: Vec::new(),
+        }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        matches!(
+            self.state,
+            JobState::Canceled | JobState::Aborted | JobState::Completed
+        )
+    }
+
+    /// job-attributes group with the job description attributes
+    pub fn job_attributes(&self) -> AttributeGroup {
+        let mut group = attributes!(
+            DelimiterTag::JobAttributes,
+            self.job_id(),
+            self.job_uri(),
+            self.job_printer_uri(),
+            self.job_state(),
+            self.job_state_reasons(),
+            self.job_name(),
+            self.job_originating_user_name(),
+            self.time_at_creation(),
+            self.number_of_documents(),
+        );
+        group.extend(self.job_more_info());
+
+        group
+    }
+}
+
+// job attribute constructor
+impl IppJob {
+    pub fn job_id(&self) -> Attribute {
+        Attribute::integer(JobAttribute::JobId, self.id)
+    }
+
+    pub fn job_uri(&self) -> Attribute {
+        Attribute::uri(JobAttribute::JobUri, &self.uri)
+    }
+
+    pub fn job_printer_uri(&self) -> Attribute {
+        Attribute::uri(JobAttribute::JobPrinterUri, &self.printer_uri)
+    }
+
+    pub fn job_more_info(&self) -> Option<Attribute> {
+        self.more_info
+            .as_ref()
+            .map(|more_info| Attribute::uri(JobAttribute::JobMoreInfo, more_info))
+    }
+
+    pub fn job_state(&self) -> Attribute {
+        Attribute::enum_value(JobAttribute::JobState, self.state as i32)
+    }
+
+    pub fn job_state_reasons(&self) -> Attribute {
+        let reason = match self.state {
+            JobState::Pending => JobStateReasonKeyword::None,
+            JobState::PendingHeld => JobStateReasonKeyword::JobHoldUntilSpecified,
+            JobState::Processing => JobStateReasonKeyword::JobPrinting,
+            JobState::ProcessingStopped => JobStateReasonKeyword::PrinterStopped,
+            JobState::Canceled => JobStateReasonKeyword::JobCanceledByUser,
+            JobState::Aborted => JobStateReasonKeyword::AbortedBySystem,
+            JobState::Completed => JobStateReasonKeyword::JobCompletedSuccessfully,
+        };
+
+        Attribute::keyword(JobAttribute::JobStateReasons, reason.to_string())
+    }
+
+    pub fn job_name(&self) -> Attribute {
+        Attribute::name(JobAttribute::JobName, self.name.clone())
+    }
+
+    pub fn job_originating_user_name(&self) -> Attribute {
+        Attribute::name(
+            JobAttribute::JobOriginatingUserName,
+            self.originating_user_name.clone(),
+        )
+    }
+
+    pub fn time_at_creation(&self) -> Attribute {
+        Attribute::integer(JobAttribute::TimeAtCreation, self.time_at_creation)
+    }
+
+    pub fn number_of_documents(&self) -> Attribute {
+        Attribute::integer(JobAttribute::NumberOfDocuments, self.documents.len() as i32)
+    }
+}
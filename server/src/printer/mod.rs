@@ -1,440 +1,1743 @@
 use chrono::{DateTime, Utc};
 use ipp_encoder::{
+    attributes,
     encoder::{
-        Attribute, AttributeGroup, AttributeName, AttributeValue, IppEncode, IppVersion, Operation,
-        TextWithLang,
+        Attribute, AttributeGroup, AttributeName, AttributeValue, CompatQuirks, IppEncode,
+        IppVersion, Operation, RangeOfInteger, Severity,
     },
     spec::{
-        attribute::{OperationAttribute, PrinterAttribute},
-        operation::{OperationID, PrinterState, StatusCode as IppStatusCode},
+        attribute::{JobAttribute, JobTemplateAttribute, OperationAttribute, PrinterAttribute},
+        operation::{JobState, OperationID, PrinterState, StatusCode as IppStatusCode},
         tag::{DelimiterTag, ValueTag},
         value::{
-            CompressionSupportedKeyword, PdlOverrideSupportedKeyword, PrinterStateReasonKeyword,
-            UriAuthenticationSupportedKeyword, UriSecuritySupportedKeyword,
+            CompressionSupportedKeyword, MediaKeyword, MediaSourceKeyword, MediaTypeKeyword,
+            OrientationRequested, PdlOverrideSupportedKeyword, PrintQuality,
+            PrinterStateReasonKeyword, SidesKeyword, UriAuthenticationSupportedKeyword,
+            UriSecuritySupportedKeyword, WhichJobsKeyword,
         },
     },
 };
-use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Mutex;
 
+mod error;
 mod job;
+mod uris;
+pub use error::ServerError;
 use job::IppJob;
+pub use uris::{PrinterUriError, PrinterUris};
+
+/// versions this printer accepts requests in, reported as 'ipp-versions-supported'
+const VERSIONS_SUPPORTED: [IppVersion; 2] = [IppVersion::V1_0, IppVersion::V1_1];
+
+/// nominal print speed, reported as 'pages-per-minute' and 'pages-per-minute-color'
+const PAGES_PER_MINUTE: i32 = 20;
+const PAGES_PER_MINUTE_COLOR: i32 = 12;
+
+/// largest 'copies' a job may ask for, reported as 'copies-supported'
+const MAX_COPIES: i32 = 99;
+
+/// default of [`IppPrinter::max_request_bytes`], document data included
+const MAX_REQUEST_BYTES: usize = 64 * 1024 * 1024;
 
 pub struct IppPrinter {
-    uri: String,
+    uris: PrinterUris,
+    /// base of the http admin pages, used for 'job-more-info'; disabled when None
+    admin_uri: Option<String>,
     name: String,
-    state: PrinterState,
+    /// 'printer-location', empty unless set with [`IppPrinter::with_location`]
+    location: String,
+    /// 'printer-info', the name unless set with [`IppPrinter::with_info`]
+    info: String,
+    /// 'printer-make-and-model'
+    make_and_model: String,
+    state: Mutex<PrinterState>,
     started_at: DateTime<Utc>,
-    jobs: Vec<IppJob>,
+    jobs: Mutex<Vec<IppJob>>,
+    /// printer attributes that do not change while running,
+    /// built once and cloned into Get-Printer-Attributes responses
+    printer_attributes: AttributeGroup,
+    /// largest request body accepted, see [`IppPrinter::request_too_large`]
+    max_request_bytes: usize,
 }
 
 impl IppPrinter {
-    pub fn new(uri: &str, name: &str) -> Self {
-        Self {
-            uri: String::from(uri),
+    pub fn new(uris: PrinterUris, name: &str) -> Self {
+        let mut printer = Self {
+            uris,
+            admin_uri: None,
             name: String::from(name),
-            state: PrinterState::Idle,
+            location: String::new(),
+            info: String::from(name),
+            make_and_model: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            state: Mutex::new(PrinterState::Idle),
             started_at: Utc::now(),
-            jobs: Vec::new(),
+            jobs: Mutex::new(Vec::new()),
+            printer_attributes: AttributeGroup::new(DelimiterTag::PrinterAttributes),
+            max_request_bytes: MAX_REQUEST_BYTES,
+        };
+        printer.printer_attributes = printer.static_printer_attributes();
+        printer
+    }
+
+    /// enable the http admin pages under `admin_uri`
+    pub fn with_admin_uri(mut self, admin_uri: &str) -> Self {
+        self.admin_uri = Some(String::from(admin_uri));
+        self.printer_attributes = self.static_printer_attributes();
+        self
+    }
+
+    /// also listen on `uri`, appended to the parallel 'printer-uri-supported',
+    /// 'uri-security-supported' and 'uri-authentication-supported' values
+    pub fn with_uri(
+        mut self,
+        uri: &str,
+        security: UriSecuritySupportedKeyword,
+        authentication: UriAuthenticationSupportedKeyword,
+    ) -> Result<Self, PrinterUriError> {
+        self.uris.push(uri, security, authentication)?;
+        for attribute in [
+            self.printer_uri_supported(),
+            self.uri_security_supported(),
+            self.uri_authentication_supported(),
+        ] {
+            self.printer_attributes.insert(attribute);
         }
+        Ok(self)
+    }
+
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.location = String::from(location);
+        self.printer_attributes.insert(self.printer_location());
+        self
+    }
+
+    pub fn with_info(mut self, info: &str) -> Self {
+        self.info = String::from(info);
+        self.printer_attributes.insert(self.printer_info());
+        self
     }
 
-    pub fn handle(&self, bytes: &[u8]) -> Vec<u8> {
-        let (_, request) = Operation::from_ipp(bytes, 0);
+    pub fn with_make_and_model(mut self, make_and_model: &str) -> Self {
+        self.make_and_model = String::from(make_and_model);
+        self.printer_attributes
+            .insert(self.printer_make_and_model());
+        self
+    }
+
+    pub fn with_max_request_bytes(mut self, max_request_bytes: usize) -> Self {
+        self.max_request_bytes = max_request_bytes;
+        self
+    }
+
+    pub fn max_request_bytes(&self) -> usize {
+        self.max_request_bytes
+    }
+
+    pub fn set_state(&self, state: PrinterState) {
+        *self.state.lock().unwrap() = state;
+    }
 
-        println!("\nRequest: {}", request.to_json());
-        println!("OperationID: {}\n", request.operation_id().unwrap() as i32);
+    /// admin page for the job, None when the admin pages are disabled or no such job exists
+    pub fn job_page(&self, id: i32) -> Option<String> {
+        self.admin_uri.as_ref()?;
 
-        let mut response = Operation {
-            version: IppVersion { major: 1, minor: 1 },
-            request_id: request.request_id,
-            operation_id_or_status_code: IppStatusCode::SuccessfulOk as u16,
-            attribute_groups: HashMap::new(),
-            data: Vec::new(),
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs.iter().find(|job| job.id == id)?;
+
+        Some(format!(
+            "Job {}: {}\nState: {:?}\nPrinter: {}\n",
+            job.id, job.name, job.state, job.printer_uri
+        ))
+    }
+
+    /// 'client-error-request-entity-too-large' for a request over `max_request_bytes`
+    ///
+    /// `head` is the start of the request, its request-id is echoed when it holds the header.
+    pub fn request_too_large(&self, head: &[u8]) -> Vec<u8> {
+        let request_id = head.get(4..8).map_or(0, |request_id| {
+            u32::from_be_bytes(request_id.try_into().unwrap())
+        });
+
+        let mut response = Operation::new_response(
+            IppVersion::V1_1,
+            IppStatusCode::ClientErrorRequestEntityTooLarge,
+            request_id,
+        );
+        response.set_attributes_charset("utf-8");
+        response.set_attributes_natural_language("en-US");
+        response.to_ipp()
+    }
+
+    /// Decode and carry out a request, Err only when `bytes` is not a request at all
+    pub fn handle(&self, bytes: &[u8]) -> Result<Vec<u8>, ServerError> {
+        let quirks = CompatQuirks {
+            case_insensitive_names: true,
+            charset_not_first: true,
+            repeated_names_as_values: true,
         };
+        let (request, report) = Operation::try_from_ipp_with_quirks(bytes, &quirks)?;
 
-        let operation_attribute_group = self.request_operation_attributes();
-        response
-            .attribute_groups
-            .insert(operation_attribute_group.tag, operation_attribute_group);
+        println!("\nRequest:\n{}", request);
+        for warning in report.warnings {
+            println!("Decode warning: {:?}", warning);
+        }
+
+        let mut response = match self.check_request(&request) {
+            Ok(()) => {
+                let mut response = Operation::response_to(&request, IppStatusCode::SuccessfulOk);
+                match self.respond(&request, &mut response) {
+                    Ok(()) => response,
+                    Err(e) => {
+                        eprintln!("Request failed: {}", e);
+                        Operation::error_response(&request, e.status_code(), Some(&e.to_string()))
+                    }
+                }
+            }
+            Err((status_code, message)) => {
+                Operation::error_response(&request, status_code, Some(&message))
+            }
+        };
+        response.version = request.version.clamp_to_supported(&VERSIONS_SUPPORTED);
+
+        let printer_uri = self.printer_uri();
+        response.operation_attributes_mut().insert(printer_uri);
+
+        if response.operation_id_or_status_code == IppStatusCode::SuccessfulOk as u16 {
+            if let Some(operation_id) = request.operation_id() {
+                debug_assert!(
+                    response
+                        .missing_response_attributes(operation_id)
+                        .is_empty(),
+                    "response to {:?} is missing required attributes",
+                    operation_id
+                );
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        for issue in response.validate() {
+            eprintln!("Invalid response: {}", issue);
+        }
+
+        println!("\nResponse:\n{}", response);
+
+        Ok(response.to_ipp())
+    }
+
+    /// carry out a request that passed [`IppPrinter::check_request`], filling in `response`
+    fn respond(&self, request: &Operation, response: &mut Operation) -> Result<(), ServerError> {
+        match request.operation_id().unwrap() {
+            OperationID::PrintJob => {
+                let data = request
+                    .decompressed_data()
+                    .map_err(ServerError::Conversion)?;
+                let path = "data.ps";
+                std::fs::write(path, &data)?;
+
+                let job_attribute_group =
+                    self.create_job(request, JobState::Pending, Some(data.into_owned()));
+                response.attribute_groups.push(job_attribute_group);
+            }
+            OperationID::CreateJob => {
+                // held until the last Send-Document
+                let job_attribute_group = self.create_job(request, JobState::PendingHeld, None);
+                response.attribute_groups.push(job_attribute_group);
+            }
+            OperationID::SendDocument => {
+                let data = request
+                    .decompressed_data()
+                    .map_err(ServerError::Conversion)?;
+                match self.send_document(request, data.into_owned()) {
+                    Ok(job_attribute_group) => {
+                        response.attribute_groups.push(job_attribute_group);
+                    }
+                    Err(status_code) => {
+                        response.operation_id_or_status_code = status_code as u16;
+                    }
+                }
+            }
+            OperationID::GetPrinterAttributes => {
+                let (supported, unsupported) = self.request_printer_attributes(request);
 
-        if request.version.major != 1 {
-            response.operation_id_or_status_code =
-                IppStatusCode::ServerErrorVersionNotSupported as u16;
-        } else if !self
-            .operation_supported()
-            .values
-            .contains(&AttributeValue::Number(
-                request.operation_id_or_status_code as i32,
-            ))
-        {
-            response.operation_id_or_status_code =
-                IppStatusCode::ServerErrorOperationNotSupported as u16;
-        } else {
-            if let Some((supported, unsupported)) = self.request_printer_attributes(&request) {
                 // insert unsupported-attributes group
-                let mut unsupported_group = AttributeGroup {
-                    tag: DelimiterTag::UnsupportedAttributes,
-                    attributes: HashMap::new(),
-                };
-                for value in unsupported {
-                    let attribute = Attribute {
-                        tag: ValueTag::Unsupported,
-                        name: AttributeName::Unsupported(value),
-                        values: vec![AttributeValue::TextWithoutLang(String::from("unsupported"))],
-                    };
-                    unsupported_group
-                        .attributes
-                        .insert(attribute.name.clone(), attribute);
+                if !unsupported.is_empty() {
+                    let unsupported_group =
+                        Self::unsupported_attributes_group(unsupported.into_iter().map(|name| {
+                            Self::unsupported_attribute(AttributeName::Unsupported(name))
+                        }));
+                    response.attribute_groups.push(unsupported_group);
                 }
-                response
-                    .attribute_groups
-                    .insert(unsupported_group.tag, unsupported_group);
 
                 // insert printer-attributes group
-                let printer_attribute_group = AttributeGroup {
-                    tag: DelimiterTag::PrinterAttributes,
-                    attributes: supported
-                        .into_iter()
-                        .map(|attr| (attr.name.clone(), attr))
-                        .collect(),
-                };
+                let mut printer_attribute_group =
+                    AttributeGroup::new(DelimiterTag::PrinterAttributes);
+                printer_attribute_group.extend(supported);
+                response.attribute_groups.push(printer_attribute_group);
+            }
+            OperationID::GetJobs => {
+                // one job-attributes group per job
                 response
                     .attribute_groups
-                    .insert(printer_attribute_group.tag, printer_attribute_group);
+                    .append(&mut self.get_jobs(request));
             }
-            match request.operation_id().unwrap() {
-                OperationID::PrintJob => {
-                    let path = "data.ps";
-                    std::fs::write(path, &request.data).unwrap();
+            OperationID::CancelJob => {
+                response.operation_id_or_status_code = self.cancel_job(request) as u16;
+            }
+            OperationID::PausePrinter => {
+                self.set_state(PrinterState::Stopped);
+                response.attribute_groups.push(self.printer_state_group());
+            }
+            OperationID::ResumePrinter => {
+                self.set_state(PrinterState::Idle);
+                response.attribute_groups.push(self.printer_state_group());
+            }
+            OperationID::GetJobAttributes => match self.get_job_attributes(request) {
+                Ok(job_attribute_group) => {
+                    response.attribute_groups.push(job_attribute_group);
+                }
+                Err(status_code) => {
+                    response.operation_id_or_status_code = status_code as u16;
+                }
+            },
+            OperationID::ValidateJob => {
+                // nothing to do beyond validation, no job is created
+                if let Err((status_code, unsupported_group)) = self.validate_job(request) {
+                    response.operation_id_or_status_code = status_code as u16;
+                    response.attribute_groups.push(unsupported_group);
                 }
-                OperationID::GetPrinterAttributes
-                | OperationID::ValidateJob
-                | OperationID::CancelJob
-                | OperationID::GetJobAttributes
-                | OperationID::GetJobs => {}
-                _ => {}
             }
+            _ => {}
         }
 
-        println!("\nResponse: {}\n", response.to_json());
-
-        response.to_ipp()
+        Ok(())
     }
 }
 
 // operation attribute constructor
 impl IppPrinter {
     fn printer_uri(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Uri,
-            name: AttributeName::Operation(OperationAttribute::PrinterUri),
-            values: vec![AttributeValue::TextWithoutLang(self.uri.clone())],
-        }
-    }
-
-    fn attributes_charset(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Charset,
-            name: AttributeName::Operation(OperationAttribute::AttributesCharset),
-            values: vec![AttributeValue::TextWithoutLang(String::from("utf-8"))],
-        }
-    }
-
-    fn attributes_natural_language(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::NaturalLanguage,
-            name: AttributeName::Operation(OperationAttribute::AttributesNaturalLanguage),
-            values: vec![AttributeValue::TextWithoutLang(String::from("en-US"))],
-        }
-    }
-
-    fn request_operation_attributes(&self) -> AttributeGroup {
-        let printer_uri = self.printer_uri();
-        let attributes_charset = self.attributes_charset();
-        let attributes_natural_language = self.attributes_natural_language();
-
-        AttributeGroup {
-            tag: DelimiterTag::OperationAttributes,
-            attributes: HashMap::from([
-                (printer_uri.name.clone(), printer_uri),
-                (attributes_charset.name.clone(), attributes_charset),
-                (
-                    attributes_natural_language.name.clone(),
-                    attributes_natural_language,
-                ),
-            ]),
-        }
+        Attribute::uri(OperationAttribute::PrinterUri, self.uris.primary())
     }
 }
 
 // intrinsic printer attribute constructor
 impl IppPrinter {
     pub fn ipp_printer_versions_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Keyword,
-            name: AttributeName::Printer(PrinterAttribute::IppVersionsSupported),
-            values: vec![AttributeValue::TextWithoutLang(String::from("1.1"))],
-        }
+        Attribute::keyword_set(
+            PrinterAttribute::IppVersionsSupported,
+            VERSIONS_SUPPORTED.iter().map(IppVersion::to_string),
+        )
     }
 
     pub fn printer_uri_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Uri,
-            name: AttributeName::Printer(PrinterAttribute::PrinterUriSupported),
-            values: vec![AttributeValue::TextWithoutLang(self.uri.clone())],
-        }
+        self.uris.printer_uri_supported()
     }
 
     pub fn uri_security_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Keyword,
-            name: AttributeName::Printer(PrinterAttribute::UriSecuritySupported),
-            values: vec![AttributeValue::TextWithoutLang(
-                UriSecuritySupportedKeyword::None.to_string(),
-            )],
-        }
+        self.uris.uri_security_supported()
     }
 
     pub fn uri_authentication_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Keyword,
-            name: AttributeName::Printer(PrinterAttribute::UriAuthenticationSupported),
-            values: vec![AttributeValue::TextWithoutLang(
-                UriAuthenticationSupportedKeyword::None.to_string(),
-            )],
-        }
+        self.uris.uri_authentication_supported()
     }
 
     pub fn printer_name(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::NameWithLanguage,
-            name: AttributeName::Printer(PrinterAttribute::PrinterName),
-            values: vec![AttributeValue::TextWithLang(TextWithLang {
-                lang: String::from("en"),
-                text: self.name.clone(),
-            })],
-        }
+        Attribute::name_with_lang(PrinterAttribute::PrinterName, "en", self.name.clone())
+    }
+
+    pub fn printer_location(&self) -> Attribute {
+        Attribute::text(PrinterAttribute::PrinterLocation, self.location.clone())
+    }
+
+    pub fn printer_info(&self) -> Attribute {
+        Attribute::text(PrinterAttribute::PrinterInfo, self.info.clone())
+    }
+
+    /// the admin pages, only when they are enabled
+    pub fn printer_more_info(&self) -> Option<Attribute> {
+        self.admin_uri
+            .as_deref()
+            .map(|admin_uri| Attribute::uri(PrinterAttribute::PrinterMoreInfo, admin_uri))
+    }
+
+    pub fn printer_make_and_model(&self) -> Attribute {
+        Attribute::text(
+            PrinterAttribute::PrinterMakeAndModel,
+            self.make_and_model.clone(),
+        )
     }
 
     pub fn printer_state_reasons(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Keyword,
-            name: AttributeName::Printer(PrinterAttribute::PrinterStateReasons),
-            values: vec![AttributeValue::TextWithoutLang(
-                PrinterStateReasonKeyword::None.to_string(),
-            )],
-        }
+        let reason = match *self.state.lock().unwrap() {
+            PrinterState::Stopped => PrinterStateReasonKeyword::Paused,
+            _ => PrinterStateReasonKeyword::None,
+        };
+
+        Attribute::keyword(PrinterAttribute::PrinterStateReasons, reason.to_string())
+    }
+
+    /// human-readable counterpart of 'printer-state'
+    pub fn printer_state_message(&self) -> Attribute {
+        let message = match *self.state.lock().unwrap() {
+            PrinterState::Idle => "Ready",
+            PrinterState::Processing => "Printing",
+            PrinterState::Stopped => "Paused",
+        };
+
+        Attribute::text(PrinterAttribute::PrinterStateMessage, message)
     }
 
     pub fn printer_state(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Enum,
-            name: AttributeName::Printer(PrinterAttribute::PrinterState),
-            values: vec![AttributeValue::Number(self.state as i32)],
-        }
+        Attribute::enum_value(
+            PrinterAttribute::PrinterState,
+            *self.state.lock().unwrap() as i32,
+        )
     }
 
     pub fn operation_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Enum,
-            name: AttributeName::Printer(PrinterAttribute::OperationsSupported),
-            values: vec![
-                AttributeValue::Number(OperationID::PrintJob as i32),
-                AttributeValue::Number(OperationID::ValidateJob as i32),
-                AttributeValue::Number(OperationID::CancelJob as i32),
-                AttributeValue::Number(OperationID::GetPrinterAttributes as i32),
-                AttributeValue::Number(OperationID::GetJobAttributes as i32),
-                AttributeValue::Number(OperationID::GetJobs as i32),
+        Attribute::enum_value_set(
+            PrinterAttribute::OperationsSupported,
+            [
+                OperationID::PrintJob as i32,
+                OperationID::ValidateJob as i32,
+                OperationID::CreateJob as i32,
+                OperationID::SendDocument as i32,
+                OperationID::CancelJob as i32,
+                OperationID::GetPrinterAttributes as i32,
+                OperationID::GetJobAttributes as i32,
+                OperationID::GetJobs as i32,
+                OperationID::PausePrinter as i32,
+                OperationID::ResumePrinter as i32,
             ],
-        }
+        )
     }
 
     pub fn charset_configured(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Charset,
-            name: AttributeName::Printer(PrinterAttribute::CharsetConfigured),
-            values: vec![AttributeValue::TextWithoutLang(String::from("utf-8"))],
-        }
+        Attribute::charset(PrinterAttribute::CharsetConfigured, "utf-8")
     }
 
     pub fn charset_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Charset,
-            name: AttributeName::Printer(PrinterAttribute::CharsetSupported),
-            values: vec![AttributeValue::TextWithoutLang(String::from("utf-8"))],
-        }
+        Attribute::charset(PrinterAttribute::CharsetSupported, "utf-8")
     }
 
     pub fn natural_language_configured(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::NaturalLanguage,
-            name: AttributeName::Printer(PrinterAttribute::NaturalLanguageConfigured),
-            values: vec![AttributeValue::TextWithoutLang(String::from("en-US"))],
-        }
+        Attribute::natural_language(PrinterAttribute::NaturalLanguageConfigured, "en-US")
     }
 
     pub fn generated_natural_language_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::NaturalLanguage,
-            name: AttributeName::Printer(PrinterAttribute::GeneratedNaturalLanguageSupported),
-            values: vec![AttributeValue::TextWithoutLang(String::from("en-US"))],
-        }
+        Attribute::natural_language(PrinterAttribute::GeneratedNaturalLanguageSupported, "en-US")
     }
 
     pub fn document_format_default(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::MimeMediaType,
-            name: AttributeName::Printer(PrinterAttribute::DocumentFormatDefault),
-            values: vec![AttributeValue::TextWithoutLang(String::from(
-                "application/postscript",
-            ))],
-        }
+        Attribute::mime_media_type(
+            PrinterAttribute::DocumentFormatDefault,
+            "application/postscript",
+        )
     }
 
     pub fn document_format_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::MimeMediaType,
-            name: AttributeName::Printer(PrinterAttribute::DocumentFormatSupported),
-            values: vec![
-                AttributeValue::TextWithoutLang(String::from("text/html")),
-                AttributeValue::TextWithoutLang(String::from("text/plain")),
-                AttributeValue::TextWithoutLang(String::from("application/vnd.hp-PCL")),
-                AttributeValue::TextWithoutLang(String::from("application/octet-stream")),
-                AttributeValue::TextWithoutLang(String::from("application/pdf")),
-                AttributeValue::TextWithoutLang(String::from("application/postscript")),
+        Attribute::mime_media_type_set(
+            PrinterAttribute::DocumentFormatSupported,
+            [
+                "text/html",
+                "text/plain",
+                "application/vnd.hp-PCL",
+                "application/octet-stream",
+                "application/pdf",
+                "application/postscript",
             ],
-        }
+        )
     }
 
     pub fn printer_is_accepting_jobs(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Boolean,
-            name: AttributeName::Printer(PrinterAttribute::PrinterIsAcceptingJobs),
-            // FIXME: when is printer not accepting jobs?
-            values: vec![AttributeValue::Boolean(true)],
-        }
+        // FIXME: when is printer not accepting jobs?
+        Attribute::boolean(PrinterAttribute::PrinterIsAcceptingJobs, true)
     }
 
     pub fn queued_job_count(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Integer,
-            name: AttributeName::Printer(PrinterAttribute::QueuedJobCount),
-            values: vec![AttributeValue::Number(self.jobs.len() as i32)],
-        }
+        Attribute::integer(
+            PrinterAttribute::QueuedJobCount,
+            self.jobs
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|job| !job.is_completed())
+                .count() as i32,
+        )
     }
 
     pub fn pdl_override_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Keyword,
-            name: AttributeName::Printer(PrinterAttribute::PdlOverrideSupported),
-            values: vec![AttributeValue::TextWithoutLang(
-                PdlOverrideSupportedKeyword::NotAttempted.to_string(),
-            )],
-        }
+        Attribute::keyword(
+            PrinterAttribute::PdlOverrideSupported,
+            PdlOverrideSupportedKeyword::NotAttempted.to_string(),
+        )
     }
 
     pub fn printer_up_time(&self) -> Attribute {
         let now = Utc::now();
         let uptime = now - self.started_at;
 
-        Attribute {
-            tag: ValueTag::Integer,
-            name: AttributeName::Printer(PrinterAttribute::PrinterUpTime),
-            values: vec![AttributeValue::Number(uptime.num_seconds() as i32)],
-        }
+        Attribute::integer(PrinterAttribute::PrinterUpTime, uptime.num_seconds() as i32)
     }
 
     pub fn printer_current_time(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::DateTime,
-            name: AttributeName::Printer(PrinterAttribute::PrinterCurrentTime),
-            values: vec![AttributeValue::DateTime(Utc::now())],
-        }
+        Attribute::datetime(PrinterAttribute::PrinterCurrentTime, Utc::now())
     }
 
     pub fn compression_supported(&self) -> Attribute {
-        Attribute {
-            tag: ValueTag::Keyword,
-            name: AttributeName::Printer(PrinterAttribute::CompressionSupported),
-            values: vec![
-                AttributeValue::TextWithoutLang(CompressionSupportedKeyword::Deflate.to_string()),
-                AttributeValue::TextWithoutLang(CompressionSupportedKeyword::Gzip.to_string()),
+        Attribute::keyword_set(
+            PrinterAttribute::CompressionSupported,
+            [
+                CompressionSupportedKeyword::Deflate.to_string(),
+                CompressionSupportedKeyword::Gzip.to_string(),
+            ],
+        )
+    }
+
+    pub fn color_supported(&self) -> Attribute {
+        Attribute::boolean(PrinterAttribute::ColorSupported, true)
+    }
+
+    pub fn pages_per_minute(&self) -> Attribute {
+        Attribute::integer(PrinterAttribute::PagesPerMinute, PAGES_PER_MINUTE)
+    }
+
+    pub fn pages_per_minute_color(&self) -> Attribute {
+        Attribute::integer(
+            PrinterAttribute::PagesPerMinuteColor,
+            PAGES_PER_MINUTE_COLOR,
+        )
+    }
+
+    pub fn media_supported(&self) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::MediaSupported,
+            [
+                MediaKeyword::IsoA4.to_string(),
+                MediaKeyword::IsoA5.to_string(),
+                MediaKeyword::IsoDl.to_string(),
+                MediaKeyword::NaLetter.to_string(),
+                MediaKeyword::NaLegal.to_string(),
+                MediaKeyword::NaNumber10.to_string(),
             ],
+        )
+    }
+
+    pub fn media_source_supported(&self) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::MediaSourceSupported,
+            [
+                MediaSourceKeyword::Auto.to_string(),
+                MediaSourceKeyword::Main.to_string(),
+                MediaSourceKeyword::Manual.to_string(),
+            ],
+        )
+    }
+
+    pub fn media_type_supported(&self) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::MediaTypeSupported,
+            [
+                MediaTypeKeyword::Stationery.to_string(),
+                MediaTypeKeyword::Photographic.to_string(),
+                MediaTypeKeyword::Transparency.to_string(),
+                MediaTypeKeyword::Envelope.to_string(),
+            ],
+        )
+    }
+
+    pub fn copies_supported(&self) -> Attribute {
+        Attribute::range_of_integer(
+            PrinterAttribute::CopiesSupported,
+            RangeOfInteger::new(1, MAX_COPIES),
+        )
+    }
+
+    pub fn copies_default(&self) -> Attribute {
+        Attribute::integer(PrinterAttribute::CopiesDefault, 1)
+    }
+
+    pub fn sides_supported(&self) -> Attribute {
+        SidesKeyword::supported_attribute([
+            SidesKeyword::OneSided,
+            SidesKeyword::TwoSidedLongEdge,
+            SidesKeyword::TwoSidedShortEdge,
+        ])
+    }
+
+    pub fn sides_default(&self) -> Attribute {
+        SidesKeyword::OneSided.default_attribute()
+    }
+
+    pub fn print_quality_supported(&self) -> Attribute {
+        PrintQuality::supported_attribute([
+            PrintQuality::Draft,
+            PrintQuality::Normal,
+            PrintQuality::High,
+        ])
+    }
+
+    pub fn print_quality_default(&self) -> Attribute {
+        PrintQuality::Normal.default_attribute()
+    }
+
+    pub fn orientation_requested_supported(&self) -> Attribute {
+        Attribute::enum_value_set(
+            PrinterAttribute::OrientationRequestedSupported,
+            [
+                OrientationRequested::Portrait as i32,
+                OrientationRequested::Landscape as i32,
+                OrientationRequested::ReverseLandscape as i32,
+                OrientationRequested::ReversePortrait as i32,
+            ],
+        )
+    }
+
+    fn static_printer_attributes(&self) -> AttributeGroup {
+        let attributes = [
+            self.ipp_printer_versions_supported(),
+            self.printer_uri_supported(),
+            self.uri_security_supported(),
+            self.uri_authentication_supported(),
+            self.printer_name(),
+            self.printer_location(),
+            self.printer_info(),
+            self.printer_make_and_model(),
+            self.operation_supported(),
+            self.charset_configured(),
+            self.charset_supported(),
+            self.natural_language_configured(),
+            self.generated_natural_language_supported(),
+            self.document_format_default(),
+            self.document_format_supported(),
+            self.pdl_override_supported(),
+            self.compression_supported(),
+            self.color_supported(),
+            self.pages_per_minute(),
+            self.pages_per_minute_color(),
+            self.media_supported(),
+            self.media_source_supported(),
+            self.media_type_supported(),
+            self.copies_supported(),
+            self.copies_default(),
+            self.sides_supported(),
+            self.sides_default(),
+            self.orientation_requested_supported(),
+            self.print_quality_supported(),
+            self.print_quality_default(),
+        ];
+
+        let mut group = AttributeGroup::new(DelimiterTag::PrinterAttributes);
+        group.extend(attributes.into_iter().chain(self.printer_more_info()));
+
+        group
+    }
+
+    /// printer-attributes group reporting the current state, for Pause-Printer & Resume-Printer
+    fn printer_state_group(&self) -> AttributeGroup {
+        attributes!(
+            DelimiterTag::PrinterAttributes,
+            self.printer_state(),
+            self.printer_state_reasons(),
+            self.printer_state_message(),
+        )
+    }
+
+    /// every supported printer attribute, the static ones cloned from `printer_attributes`
+    fn printer_attribute_group(&self) -> AttributeGroup {
+        let mut group = self.printer_attributes.clone();
+
+        for attribute in [
+            self.printer_state(),
+            self.printer_state_reasons(),
+            self.printer_state_message(),
+            self.printer_is_accepting_jobs(),
+            self.queued_job_count(),
+            self.printer_up_time(),
+            self.printer_current_time(),
+        ] {
+            group.insert(attribute);
         }
+
+        group
+    }
+
+    /// checks before the operation is carried out, the error status and its 'status-message'
+    fn check_request(&self, request: &Operation) -> Result<(), (IppStatusCode, String)> {
+        if !request.version.is_supported(&VERSIONS_SUPPORTED) {
+            Err((
+                IppStatusCode::ServerErrorVersionNotSupported,
+                format!("IPP version {} is not supported", request.version),
+            ))
+        } else if !self
+            .operation_supported()
+            .as_integers()
+            .any(|operation_id| operation_id == request.operation_id_or_status_code as i32)
+        {
+            Err((
+                IppStatusCode::ServerErrorOperationNotSupported,
+                format!(
+                    "operation {:#06x} is not supported",
+                    request.operation_id_or_status_code
+                ),
+            ))
+        } else if !self.is_charset_supported(request) {
+            Err((
+                IppStatusCode::ClientErrorCharsetNotSupported,
+                format!(
+                    "charset {} is not supported",
+                    request.attributes_charset().unwrap_or_default()
+                ),
+            ))
+        } else {
+            request_error(request).map_or(Ok(()), |message| {
+                Err((IppStatusCode::ClientErrorBadRequest, message))
+            })
+        }
+    }
+
+    /// whether 'attributes-charset' of the request is one of 'charset-supported',
+    /// charset names are case-insensitive
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.1.4.1)
+    fn is_charset_supported(&self, request: &Operation) -> bool {
+        let charset = match request.attributes_charset() {
+            Some(charset) => charset,
+            // a request without charset is malformed, not in an unsupported charset
+            None => return true,
+        };
+
+        self.charset_supported()
+            .as_strings()
+            .any(|supported| supported.eq_ignore_ascii_case(charset))
     }
 
     fn request_printer_attribute(&self, attribute_name: &str) -> Option<Attribute> {
         match PrinterAttribute::from_str(attribute_name) {
             Ok(printer_attr_name) => match printer_attr_name {
-                PrinterAttribute::IppVersionsSupported => {
-                    Some(self.ipp_printer_versions_supported())
-                }
-                PrinterAttribute::PrinterUriSupported => Some(self.printer_uri_supported()),
-                PrinterAttribute::UriSecuritySupported => Some(self.uri_security_supported()),
-                PrinterAttribute::UriAuthenticationSupported => {
-                    Some(self.uri_authentication_supported())
-                }
-                PrinterAttribute::PrinterName => Some(self.printer_name()),
                 PrinterAttribute::PrinterState => Some(self.printer_state()),
                 PrinterAttribute::PrinterStateReasons => Some(self.printer_state_reasons()),
-                PrinterAttribute::OperationsSupported => Some(self.operation_supported()),
-                PrinterAttribute::CharsetConfigured => Some(self.charset_configured()),
-                PrinterAttribute::CharsetSupported => Some(self.charset_supported()),
-                PrinterAttribute::NaturalLanguageConfigured => {
-                    Some(self.natural_language_configured())
-                }
-                PrinterAttribute::GeneratedNaturalLanguageSupported => {
-                    Some(self.generated_natural_language_supported())
-                }
-                PrinterAttribute::DocumentFormatDefault => Some(self.document_format_default()),
-                PrinterAttribute::DocumentFormatSupported => Some(self.document_format_supported()),
+                PrinterAttribute::PrinterStateMessage => Some(self.printer_state_message()),
                 PrinterAttribute::PrinterIsAcceptingJobs => Some(self.printer_is_accepting_jobs()),
                 PrinterAttribute::QueuedJobCount => Some(self.queued_job_count()),
-                PrinterAttribute::PdlOverrideSupported => Some(self.pdl_override_supported()),
                 PrinterAttribute::PrinterUpTime => Some(self.printer_up_time()),
                 PrinterAttribute::PrinterCurrentTime => Some(self.printer_current_time()),
-                PrinterAttribute::CompressionSupported => Some(self.compression_supported()),
-                _ => None,
+                _ => self
+                    .printer_attributes
+                    .get_printer(printer_attr_name)
+                    .cloned(),
             },
             Err(_) => None,
         }
     }
 
-    fn request_printer_attributes(
-        &self,
-        request: &Operation,
-    ) -> Option<(Vec<Attribute>, Vec<String>)> {
-        match request
-            .attribute_groups
-            .get(&DelimiterTag::OperationAttributes)
-        {
-            Some(operation_attribute_group) => {
-                match operation_attribute_group
+    fn request_printer_attributes(&self, request: &Operation) -> (Vec<Attribute>, Vec<String>) {
+        filter_requested(
+            request,
+            "printer-description",
+            || {
+                self.printer_attribute_group()
                     .attributes
-                    .get(&AttributeName::Operation(
-                        OperationAttribute::RequestedAttributes,
-                    )) {
-                    Some(requested) => {
-                        let mut supported = Vec::new();
-                        let mut unsupported = Vec::new();
-
-                        for value in &requested.values {
-                            if let AttributeValue::TextWithoutLang(value_str) = value {
-                                if let Some(attribute) = self.request_printer_attribute(value_str) {
-                                    supported.push(attribute);
-                                } else {
-                                    unsupported.push(String::from(value_str));
-                                }
-                            }
-                        }
-
-                        Some((supported, unsupported))
+                    .into_values()
+                    .collect()
+            },
+            |name| self.request_printer_attribute(name),
+        )
+    }
+}
+
+// job operations
+impl IppPrinter {
+    /// out-of-band 'unsupported' attribute, for attributes the printer does not support at all
+    fn unsupported_attribute(name: AttributeName) -> Attribute {
+        Attribute {
+            tag: ValueTag::Unsupported,
+            name,
+            // out-of-band, encoded without a value
+            values: vec![AttributeValue::TextWithoutLang(String::new())],
+        }
+    }
+
+    fn unsupported_attributes_group(
+        attributes: impl IntoIterator<Item = Attribute>,
+    ) -> AttributeGroup {
+        let mut group = AttributeGroup::new(DelimiterTag::UnsupportedAttributes);
+        group.extend(attributes);
+
+        group
+    }
+
+    /// check 'document-format' and job-template attributes against what the printer supports,
+    /// returning the status code and unsupported-attributes group for the offenders
+    ///
+    /// A job-template attribute is supported when the printer advertises its
+    /// '<name>-supported' counterpart, its values are then checked against that attribute.
+    fn validate_job(&self, request: &Operation) -> Result<(), (IppStatusCode, AttributeGroup)> {
+        // every value of `attribute` is among the values of `supported`,
+        // or within one of its ranges for integers
+        let values_supported = |attribute: &Attribute, supported: &Attribute| {
+            let ranges = || {
+                supported
+                    .values
+                    .iter()
+                    .filter_map(AttributeValue::as_range_of_integer)
+            };
+
+            attribute
+                .as_strings()
+                .all(|value| supported.as_strings().any(|supported| supported == value))
+                && attribute.as_integers().all(|value| {
+                    supported.as_integers().any(|supported| supported == value)
+                        || ranges().any(|range| range.contains(value))
+                })
+        };
+
+        // no 'keyword' value is outside the spec enum of `attribute`
+        let keywords_valid = |attribute: &Attribute, name: JobTemplateAttribute| {
+            attribute
+                .values
+                .iter()
+                .filter_map(AttributeValue::as_keyword)
+                .all(|keyword| name.is_valid_keyword(keyword) != Some(false))
+        };
+
+        let mut status_code = IppStatusCode::ClientErrorAttributesOrValuesNotSupported;
+        let mut unsupported = Vec::new();
+
+        if let Some(document_format) = request.attribute(
+            DelimiterTag::OperationAttributes,
+            OperationAttribute::DocumentFormat,
+        ) {
+            if !values_supported(document_format, &self.document_format_supported()) {
+                status_code = IppStatusCode::ClientErrorDocumentFormatNotSupported;
+                unsupported.push(document_format.clone());
+            }
+        }
+
+        if let Some(job_attribute_group) = request.attribute_group(DelimiterTag::JobAttributes) {
+            for attribute in job_attribute_group.attributes.values() {
+                let advertised = match attribute.name {
+                    AttributeName::JobTemplate(name) => self
+                        .request_printer_attribute(&format!("{}-supported", name))
+                        .map(|supported| (name, supported)),
+                    _ => None,
+                };
+
+                let (name, supported) = match advertised {
+                    Some(advertised) => advertised,
+                    // unsupported attribute, reported without its values
+                    None => {
+                        unsupported.push(Self::unsupported_attribute(attribute.name.clone()));
+                        continue;
                     }
-                    None => None,
+                };
+
+                // supported attribute with keywords the spec does not define or values
+                // this printer does not support, reported with its values
+                if !keywords_valid(attribute, name) || !values_supported(attribute, &supported) {
+                    unsupported.push(attribute.clone());
                 }
             }
-            None => None,
         }
+
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err((status_code, Self::unsupported_attributes_group(unsupported)))
+        }
+    }
+
+    /// enqueue a new job for the request in `state`, returning its job-attributes group
+    fn create_job(
+        &self,
+        request: &Operation,
+        state: JobState,
+        document: Option<Vec<u8>>,
+    ) -> AttributeGroup {
+        let name = request.job_name().unwrap_or("Untitled");
+        let originating_user_name = request.requesting_user_name().unwrap_or("anonymous");
+        let time_at_creation = (Utc::now() - self.started_at).num_seconds() as i32;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut job = IppJob::new(
+            jobs.len() as i32 + 1,
+            self.uris.primary(),
+            self.admin_uri.as_deref(),
+            name,
+            originating_user_name,
+            time_at_creation,
+        );
+        job.state = state;
+        job.documents.extend(document);
+        let job_attribute_group = job.job_attributes();
+        jobs.push(job);
+
+        job_attribute_group
+    }
+
+    /// target job of the request, by 'job-id' or else by 'job-uri'
+    fn find_job<'a>(jobs: &'a mut [IppJob], request: &Operation) -> Option<&'a mut IppJob> {
+        if let Some(id) = request.job_id() {
+            jobs.iter_mut().find(|job| job.id == id)
+        } else if let Some(uri) = request
+            .attribute(DelimiterTag::OperationAttributes, JobAttribute::JobUri)
+            .and_then(Attribute::first_str)
+        {
+            jobs.iter_mut().find(|job| job.uri == uri)
+        } else {
+            None
+        }
+    }
+
+    /// append the document to a job from Create-Job, releasing it on 'last-document'
+    ///
+    /// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.3.1)
+    fn send_document(
+        &self,
+        request: &Operation,
+        data: Vec<u8>,
+    ) -> Result<AttributeGroup, IppStatusCode> {
+        let last_document = request
+            .attribute(
+                DelimiterTag::OperationAttributes,
+                OperationAttribute::LastDocument,
+            )
+            .and_then(Attribute::first_bool)
+            .ok_or(IppStatusCode::ClientErrorBadRequest)?;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = Self::find_job(&mut jobs, request).ok_or(IppStatusCode::ClientErrorNotFound)?;
+
+        // only jobs still waiting for documents accept more
+        if job.state != JobState::PendingHeld {
+            return Err(IppStatusCode::ClientErrorNotPossible);
+        }
+
+        // the last Send-Document may come without data, only closing the job
+        if !data.is_empty() {
+            job.documents.push(data);
+        }
+        if last_document {
+            job.state = JobState::Pending;
+        }
+
+        Ok(job.job_attributes())
+    }
+
+    fn cancel_job(&self, request: &Operation) -> IppStatusCode {
+        let mut jobs = self.jobs.lock().unwrap();
+
+        match Self::find_job(&mut jobs, request) {
+            None => IppStatusCode::ClientErrorNotFound,
+            Some(job) if job.is_completed() => IppStatusCode::ClientErrorNotPossible,
+            Some(job) => {
+                job.state = JobState::Canceled;
+                IppStatusCode::SuccessfulOk
+            }
+        }
+    }
+
+    /// job-attributes group of the target job, limited to 'requested-attributes'
+    fn get_job_attributes(&self, request: &Operation) -> Result<AttributeGroup, IppStatusCode> {
+        let mut jobs = self.jobs.lock().unwrap();
+
+        let job_attribute_group = Self::find_job(&mut jobs, request)
+            .map(|job| job.job_attributes())
+            .ok_or(IppStatusCode::ClientErrorNotFound)?;
+
+        // unsupported names are left out, there is no unsupported-attributes group for jobs
+        let (attributes, _) = filter_requested(
+            request,
+            "job-description",
+            || job_attribute_group.attributes.values().cloned().collect(),
+            |name| {
+                AttributeName::from_str(name)
+                    .ok()
+                    .and_then(|name| job_attribute_group.attributes.get(&name).cloned())
+            },
+        );
+
+        let mut group = AttributeGroup::new(DelimiterTag::JobAttributes);
+        group.extend(attributes);
+
+        Ok(group)
+    }
+
+    /// job-attributes groups for Get-Jobs, filtered by 'which-jobs' and 'limit'
+    fn get_jobs(&self, request: &Operation) -> Vec<AttributeGroup> {
+        let which_jobs = request
+            .attribute(
+                DelimiterTag::OperationAttributes,
+                OperationAttribute::WhichJobs,
+            )
+            .and_then(Attribute::first_str)
+            .and_then(|value| WhichJobsKeyword::from_str(value).ok())
+            .unwrap_or(WhichJobsKeyword::NotCompleted);
+
+        let limit = match request
+            .attribute(DelimiterTag::OperationAttributes, OperationAttribute::Limit)
+            .and_then(Attribute::first_i32)
+        {
+            Some(limit) if limit > 0 => limit as usize,
+            _ => usize::MAX,
+        };
+
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|job| job.is_completed() == (which_jobs == WhichJobsKeyword::Completed))
+            .take(limit)
+            .map(IppJob::job_attributes)
+            .collect()
+    }
+}
+
+/// log the validation issues of a request of a supported operation,
+/// the message of the first error if any
+fn request_error(request: &Operation) -> Option<String> {
+    let issues = request.validate_as_request(request.operation_id().unwrap());
+    for issue in &issues {
+        eprintln!("Invalid request: {}", issue);
+    }
+
+    issues
+        .iter()
+        .find(|issue| issue.severity == Severity::Error)
+        .map(|issue| issue.to_string())
+}
+
+/// Resolve 'requested-attributes' of `request` into attributes and the names not resolved
+///
+/// 'all' and `group_keyword` (e.g. 'printer-description') expand to `all()`, every other name
+/// goes through `resolver`. Without 'requested-attributes' everything is returned.
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-4.2.5.1)
+fn filter_requested<A, F>(
+    request: &Operation,
+    group_keyword: &str,
+    all: A,
+    resolver: F,
+) -> (Vec<Attribute>, Vec<String>)
+where
+    A: Fn() -> Vec<Attribute>,
+    F: Fn(&str) -> Option<Attribute>,
+{
+    let requested = request.requested_attributes();
+    if requested.is_empty() {
+        return (all(), Vec::new());
+    }
+
+    let mut supported = Vec::new();
+    let mut unsupported = Vec::new();
+
+    for name in requested {
+        if name == "all" || name == group_keyword {
+            supported.extend(all());
+        } else if let Some(attribute) = resolver(name) {
+            supported.push(attribute);
+        } else {
+            unsupported.push(String::from(name));
+        }
+    }
+
+    (supported, unsupported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ipp_encoder::encoder::OperationBuilder;
+
+    const PRINTER_URI: &str = "ipp://localhost:6363/";
+
+    fn printer() -> IppPrinter {
+        let uris = PrinterUris::new(
+            PRINTER_URI,
+            UriSecuritySupportedKeyword::None,
+            UriAuthenticationSupportedKeyword::None,
+        )
+        .unwrap();
+        IppPrinter::new(uris, "Test Printer")
+    }
+
+    /// request for `operation_id` to the printer from [`printer`]
+    fn request(operation_id: OperationID) -> OperationBuilder {
+        OperationBuilder::request(operation_id)
+            .charset("utf-8")
+            .natural_language("en-US")
+            .operation_attribute(Attribute::uri(OperationAttribute::PrinterUri, PRINTER_URI))
+    }
+
+    /// encode the request, have the printer handle it and decode its response
+    fn send(printer: &IppPrinter, request: OperationBuilder) -> Operation {
+        let response = printer.handle(&request.build().unwrap().to_ipp()).unwrap();
+        Operation::try_from_ipp(&response).unwrap()
+    }
+
+    /// Get-Printer-Attributes for `requested` only
+    fn get_printer_attributes(printer: &IppPrinter, requested: &[&str]) -> AttributeGroup {
+        let request =
+            request(OperationID::GetPrinterAttributes).operation_attribute(Attribute::keyword_set(
+                OperationAttribute::RequestedAttributes,
+                requested.iter().copied(),
+            ));
+        send(printer, request)
+            .attribute_group(DelimiterTag::PrinterAttributes)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn media_source_and_media_type_supported() {
+        let attributes = get_printer_attributes(
+            &printer(),
+            &["media-source-supported", "media-type-supported"],
+        );
+
+        let supported = |name| {
+            let attribute = attributes.get_printer(name).unwrap();
+            assert_eq!(attribute.tag, ValueTag::Keyword);
+            attribute.as_strings().map(String::from).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            supported(PrinterAttribute::MediaSourceSupported),
+            ["auto", "main", "manual"]
+        );
+        assert_eq!(
+            supported(PrinterAttribute::MediaTypeSupported),
+            ["stationery", "photographic", "transparency", "envelope"]
+        );
+    }
+
+    /// Create-Job named `name`, returning its job-id
+    fn create_job(printer: &IppPrinter, name: &str) -> i32 {
+        let request = request(OperationID::CreateJob)
+            .operation_attribute(Attribute::name(JobAttribute::JobName, name));
+        send(printer, request)
+            .attribute_group(DelimiterTag::JobAttributes)
+            .and_then(|group| group.get_job(JobAttribute::JobId))
+            .and_then(Attribute::first_i32)
+            .unwrap()
+    }
+
+    fn job_groups(response: &Operation) -> Vec<&AttributeGroup> {
+        response
+            .attribute_groups
+            .iter()
+            .filter(|group| group.tag == DelimiterTag::JobAttributes)
+            .collect()
+    }
+
+    #[test]
+    fn get_jobs_returns_a_group_per_job() {
+        let printer = printer();
+        for name in ["first", "second", "third"] {
+            create_job(&printer, name);
+        }
+
+        let response = send(&printer, request(OperationID::GetJobs));
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        let jobs = job_groups(&response);
+        assert_eq!(jobs.len(), 3);
+
+        for (id, (group, name)) in jobs.iter().zip(["first", "second", "third"]).enumerate() {
+            let get = |name| group.get_job(name).unwrap();
+            assert_eq!(get(JobAttribute::JobId).first_i32(), Some(id as i32 + 1));
+            assert_eq!(
+                get(JobAttribute::JobUri).first_str(),
+                Some(format!("ipp://localhost:6363/jobs/{}", id + 1).as_str())
+            );
+            assert_eq!(
+                get(JobAttribute::JobState).first_i32(),
+                Some(JobState::PendingHeld as i32)
+            );
+            assert_eq!(get(JobAttribute::JobName).first_str(), Some(name));
+        }
+
+        // filtered by 'limit' and 'which-jobs'
+        let limited = request(OperationID::GetJobs)
+            .operation_attribute(Attribute::integer(OperationAttribute::Limit, 2));
+        assert_eq!(job_groups(&send(&printer, limited)).len(), 2);
+        let completed = request(OperationID::GetJobs).operation_attribute(Attribute::keyword(
+            OperationAttribute::WhichJobs,
+            WhichJobsKeyword::Completed.to_string(),
+        ));
+        assert!(job_groups(&send(&printer, completed)).is_empty());
+    }
+
+    #[test]
+    fn with_uri_keeps_the_uri_attributes_aligned() {
+        let printer = printer()
+            .with_uri(
+                "ipps://localhost:6363/",
+                UriSecuritySupportedKeyword::TLS,
+                UriAuthenticationSupportedKeyword::Basic,
+            )
+            .unwrap();
+
+        let attributes = get_printer_attributes(
+            &printer,
+            &[
+                "printer-uri-supported",
+                "uri-security-supported",
+                "uri-authentication-supported",
+            ],
+        );
+        let values = |name| {
+            attributes
+                .get_printer(name)
+                .unwrap()
+                .as_strings()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            values(PrinterAttribute::PrinterUriSupported),
+            [PRINTER_URI, "ipps://localhost:6363/"]
+        );
+        assert_eq!(
+            values(PrinterAttribute::UriSecuritySupported),
+            ["none", "tls"]
+        );
+        assert_eq!(
+            values(PrinterAttribute::UriAuthenticationSupported),
+            ["none", "basic"]
+        );
+
+        assert!(matches!(
+            printer.with_uri(
+                PRINTER_URI,
+                UriSecuritySupportedKeyword::None,
+                UriAuthenticationSupportedKeyword::None,
+            ),
+            Err(PrinterUriError::Duplicate(_))
+        ));
+    }
+
+    #[test]
+    fn print_job_response_has_the_required_attributes() {
+        let request = request(OperationID::PrintJob).data(b"%!PS\nshowpage\n".to_vec());
+        let response = send(&printer(), request);
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert!(response
+            .missing_response_attributes(OperationID::PrintJob)
+            .is_empty());
+
+        let job = response
+            .attribute_group(DelimiterTag::JobAttributes)
+            .unwrap();
+        assert_eq!(
+            job.get_job(JobAttribute::JobUri)
+                .and_then(Attribute::first_str),
+            Some("ipp://localhost:6363/jobs/1")
+        );
+        assert_eq!(
+            job.get_job(JobAttribute::JobId)
+                .and_then(Attribute::first_i32),
+            Some(1)
+        );
+        assert_eq!(
+            job.get_job(JobAttribute::JobState)
+                .and_then(Attribute::first_i32),
+            Some(JobState::Pending as i32)
+        );
+    }
+
+    /// Cancel-Job for the job with `job_id`, returning the status-code of the response
+    fn cancel_job(printer: &IppPrinter, job_id: i32) -> Option<IppStatusCode> {
+        let request = request(OperationID::CancelJob)
+            .operation_attribute(Attribute::integer(JobAttribute::JobId, job_id));
+        send(printer, request).status_code()
+    }
+
+    #[test]
+    fn cancel_job_outcomes() {
+        let printer = printer();
+        let job_id = create_job(&printer, "cancelled");
+
+        assert_eq!(
+            cancel_job(&printer, job_id),
+            Some(IppStatusCode::SuccessfulOk)
+        );
+        // canceled is completed, it cannot be canceled again
+        assert_eq!(
+            cancel_job(&printer, job_id),
+            Some(IppStatusCode::ClientErrorNotPossible)
+        );
+        assert_eq!(
+            cancel_job(&printer, job_id + 1),
+            Some(IppStatusCode::ClientErrorNotFound)
+        );
+
+        // by 'job-uri' instead of 'job-id'
+        let job_id = create_job(&printer, "by uri");
+        let request = request(OperationID::CancelJob).operation_attribute(Attribute::uri(
+            JobAttribute::JobUri,
+            &format!("ipp://localhost:6363/jobs/{}", job_id),
+        ));
+        assert_eq!(
+            send(&printer, request).status_code(),
+            Some(IppStatusCode::SuccessfulOk)
+        );
+    }
+
+    #[test]
+    fn pause_reports_stopped() {
+        let printer = printer();
+        let state = |printer: &IppPrinter| {
+            let attributes =
+                get_printer_attributes(printer, &["printer-state", "printer-state-reasons"]);
+            (
+                attributes
+                    .get_printer(PrinterAttribute::PrinterState)
+                    .and_then(Attribute::first_i32),
+                attributes
+                    .get_printer(PrinterAttribute::PrinterStateReasons)
+                    .and_then(Attribute::first_str)
+                    .map(String::from),
+            )
+        };
+        assert_eq!(
+            state(&printer),
+            (Some(PrinterState::Idle as i32), Some(String::from("none")))
+        );
+
+        let response = send(&printer, request(OperationID::PausePrinter));
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert_eq!(
+            state(&printer),
+            (
+                Some(PrinterState::Stopped as i32),
+                Some(String::from("paused"))
+            )
+        );
+
+        send(&printer, request(OperationID::ResumePrinter));
+        assert_eq!(state(&printer).0, Some(PrinterState::Idle as i32));
+    }
+
+    /// Validate-Job with 'sides' set to `sides`
+    fn validate_sides(printer: &IppPrinter, sides: &str) -> Operation {
+        let request = request(OperationID::ValidateJob)
+            .job_attribute(Attribute::keyword(JobTemplateAttribute::Sides, sides));
+        send(printer, request)
+    }
+
+    #[test]
+    fn validate_job_checks_keywords() {
+        let printer = printer();
+
+        let response = validate_sides(&printer, "two-sided");
+        assert_eq!(
+            response.status_code(),
+            Some(IppStatusCode::ClientErrorAttributesOrValuesNotSupported)
+        );
+        let unsupported = response
+            .attribute_group(DelimiterTag::UnsupportedAttributes)
+            .unwrap();
+        assert_eq!(
+            unsupported
+                .get_job_template(JobTemplateAttribute::Sides)
+                .and_then(Attribute::first_str),
+            Some("two-sided")
+        );
+
+        let response = validate_sides(&printer, "two-sided-long-edge");
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert!(response
+            .attribute_group(DelimiterTag::UnsupportedAttributes)
+            .is_none());
+    }
+
+    #[test]
+    fn validate_job_accepts_supported_attributes() {
+        let printer = printer();
+
+        let request = request(OperationID::ValidateJob)
+            .operation_attribute(Attribute::mime_media_type(
+                OperationAttribute::DocumentFormat,
+                "application/pdf",
+            ))
+            .job_attribute(Attribute::keyword(
+                JobTemplateAttribute::Media,
+                MediaKeyword::IsoA4.to_string(),
+            ))
+            .job_attribute(Attribute::integer(JobTemplateAttribute::Copies, 2))
+            .job_attribute(Attribute::keyword(
+                JobTemplateAttribute::Sides,
+                SidesKeyword::TwoSidedLongEdge.to_string(),
+            ))
+            .job_attribute(Attribute::enum_value(
+                JobTemplateAttribute::PrintQuality,
+                PrintQuality::High as i32,
+            ));
+        let response = send(&printer, request);
+
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert!(response
+            .attribute_group(DelimiterTag::UnsupportedAttributes)
+            .is_none());
+        // validating does not create a job
+        assert!(printer.jobs.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn validate_job_refuses_an_unsupported_document_format() {
+        let printer = printer();
+
+        let request = request(OperationID::ValidateJob).operation_attribute(
+            Attribute::mime_media_type(OperationAttribute::DocumentFormat, "image/pwg-raster"),
+        );
+        let response = send(&printer, request);
+
+        assert_eq!(
+            response.status_code(),
+            Some(IppStatusCode::ClientErrorDocumentFormatNotSupported)
+        );
+        let unsupported = response
+            .attribute_group(DelimiterTag::UnsupportedAttributes)
+            .unwrap();
+        assert_eq!(
+            unsupported
+                .get_operation(OperationAttribute::DocumentFormat)
+                .and_then(Attribute::first_str),
+            Some("image/pwg-raster")
+        );
+    }
+
+    #[test]
+    fn validate_job_refuses_unadvertised_attributes_and_values() {
+        let printer = printer();
+
+        let request = request(OperationID::ValidateJob)
+            .job_attribute(Attribute::integer(JobTemplateAttribute::Copies, 1000))
+            .job_attribute(Attribute::integer(JobTemplateAttribute::NumberUp, 2));
+        let response = send(&printer, request);
+
+        assert_eq!(
+            response.status_code(),
+            Some(IppStatusCode::ClientErrorAttributesOrValuesNotSupported)
+        );
+        let unsupported = response
+            .attribute_group(DelimiterTag::UnsupportedAttributes)
+            .unwrap();
+        // out of 'copies-supported', reported with its value
+        assert_eq!(
+            unsupported
+                .get_job_template(JobTemplateAttribute::Copies)
+                .and_then(Attribute::first_i32),
+            Some(1000)
+        );
+        // no 'number-up-supported' at all
+        assert_eq!(
+            unsupported
+                .get_job_template(JobTemplateAttribute::NumberUp)
+                .map(|attribute| attribute.tag),
+            Some(ValueTag::Unsupported)
+        );
+    }
+
+    #[test]
+    fn job_printer_uri_and_more_info() {
+        let job_attributes = |printer: &IppPrinter, operation_id| {
+            let request = match operation_id {
+                OperationID::PrintJob => request(operation_id).data(b"%!PS\nshowpage\n".to_vec()),
+                _ => request(operation_id)
+                    .operation_attribute(Attribute::integer(JobAttribute::JobId, 1)),
+            };
+            let response = send(printer, request);
+            assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+            response
+                .attribute_group(DelimiterTag::JobAttributes)
+                .unwrap()
+                .clone()
+        };
+        let uri = |group: &AttributeGroup, name| {
+            group
+                .get_job(name)
+                .and_then(Attribute::first_str)
+                .map(String::from)
+        };
+
+        let printer = printer().with_admin_uri("http://localhost:6363/");
+        for operation_id in [OperationID::PrintJob, OperationID::GetJobAttributes] {
+            let job = job_attributes(&printer, operation_id);
+            assert_eq!(
+                uri(&job, JobAttribute::JobPrinterUri).as_deref(),
+                Some(PRINTER_URI)
+            );
+            assert_eq!(
+                uri(&job, JobAttribute::JobMoreInfo).as_deref(),
+                Some("http://localhost:6363/jobs/1")
+            );
+        }
+        assert!(printer.job_page(1).is_some());
+        assert!(printer.job_page(2).is_none());
+
+        // without the admin pages, nothing to point 'job-more-info' at
+        let printer = self::printer();
+        for operation_id in [OperationID::PrintJob, OperationID::GetJobAttributes] {
+            let job = job_attributes(&printer, operation_id);
+            assert_eq!(
+                uri(&job, JobAttribute::JobPrinterUri).as_deref(),
+                Some(PRINTER_URI)
+            );
+            assert_eq!(uri(&job, JobAttribute::JobMoreInfo), None);
+        }
+        assert!(printer.job_page(1).is_none());
+    }
+
+    /// Get-Job-Attributes of the job with `job_id` for `requested` only
+    fn get_job_attributes(printer: &IppPrinter, job_id: i32, requested: &[&str]) -> Operation {
+        let mut request = request(OperationID::GetJobAttributes)
+            .operation_attribute(Attribute::integer(JobAttribute::JobId, job_id));
+        if !requested.is_empty() {
+            request = request.operation_attribute(Attribute::keyword_set(
+                OperationAttribute::RequestedAttributes,
+                requested.iter().copied(),
+            ));
+        }
+        send(printer, request)
+    }
+
+    #[test]
+    fn get_job_attributes_returns_the_requested_subset() {
+        let printer = printer();
+        let job_id = create_job(&printer, "subset");
+        let names = |response: &Operation| {
+            let mut names = response
+                .attribute_group(DelimiterTag::JobAttributes)
+                .unwrap()
+                .attributes
+                .keys()
+                .map(AttributeName::to_string)
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        let response = get_job_attributes(&printer, job_id, &["job-state", "time-at-creation"]);
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert_eq!(names(&response), ["job-state", "time-at-creation"]);
+
+        // everything by default, the same as 'all'
+        let all = names(&get_job_attributes(&printer, job_id, &[]));
+        for name in [
+            "job-id",
+            "job-state",
+            "job-state-reasons",
+            "time-at-creation",
+            "job-originating-user-name",
+        ] {
+            assert!(all.iter().any(|all| all == name), "{} is missing", name);
+        }
+        assert_eq!(names(&get_job_attributes(&printer, job_id, &["all"])), all);
+
+        assert_eq!(
+            get_job_attributes(&printer, job_id + 1, &[]).status_code(),
+            Some(IppStatusCode::ClientErrorNotFound)
+        );
+    }
+
+    #[test]
+    fn requested_all_expands_to_every_printer_attribute() {
+        let printer = printer();
+        let names = |group: &AttributeGroup| {
+            group
+                .attributes
+                .keys()
+                .map(AttributeName::to_string)
+                .collect::<Vec<_>>()
+        };
+        let every = names(&printer.printer_attribute_group());
+        assert!(every.len() > 1);
+
+        assert_eq!(names(&get_printer_attributes(&printer, &["all"])), every);
+        assert_eq!(
+            names(&get_printer_attributes(&printer, &["printer-description"])),
+            every
+        );
+        // without 'requested-attributes'
+        let response = send(&printer, request(OperationID::GetPrinterAttributes));
+        assert_eq!(
+            names(
+                response
+                    .attribute_group(DelimiterTag::PrinterAttributes)
+                    .unwrap()
+            ),
+            every
+        );
+
+        // names next to 'all' add nothing, unknown ones are still reported
+        let response = send(
+            &printer,
+            request(OperationID::GetPrinterAttributes).operation_attribute(Attribute::keyword_set(
+                OperationAttribute::RequestedAttributes,
+                ["all", "printer-name", "x-bogus"],
+            )),
+        );
+        assert_eq!(
+            names(
+                response
+                    .attribute_group(DelimiterTag::PrinterAttributes)
+                    .unwrap()
+            ),
+            every
+        );
+        let unsupported = response
+            .attribute_group(DelimiterTag::UnsupportedAttributes)
+            .unwrap();
+        assert_eq!(names(unsupported), ["x-bogus"]);
+    }
+
+    /// Send-Document of `data` to the job with `job_id`
+    fn send_document(printer: &IppPrinter, job_id: i32, last: bool, data: &[u8]) -> Operation {
+        let request = request(OperationID::SendDocument)
+            .operation_attribute(Attribute::integer(JobAttribute::JobId, job_id))
+            .operation_attribute(Attribute::boolean(OperationAttribute::LastDocument, last))
+            .data(data.to_vec());
+        send(printer, request)
+    }
+
+    #[test]
+    fn create_job_then_send_documents() {
+        let printer = printer();
+        let job_id = create_job(&printer, "two documents");
+        let job = |response: &Operation, name| {
+            response
+                .attribute(DelimiterTag::JobAttributes, name)
+                .and_then(Attribute::first_i32)
+        };
+
+        let response = send_document(&printer, job_id, false, b"%!PS\n% first\n");
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert_eq!(
+            job(&response, JobAttribute::JobState),
+            Some(JobState::PendingHeld as i32)
+        );
+
+        let response = send_document(&printer, job_id, true, b"%!PS\n% second\n");
+        assert_eq!(response.status_code(), Some(IppStatusCode::SuccessfulOk));
+        assert_eq!(
+            job(&response, JobAttribute::JobState),
+            Some(JobState::Pending as i32)
+        );
+
+        let response = get_job_attributes(&printer, job_id, &["number-of-documents"]);
+        assert_eq!(job(&response, JobAttribute::NumberOfDocuments), Some(2));
+
+        // the job no longer takes documents
+        assert_eq!(
+            send_document(&printer, job_id, true, b"%!PS\n").status_code(),
+            Some(IppStatusCode::ClientErrorNotPossible)
+        );
+    }
+
+    #[test]
+    fn unsupported_charset_is_refused() {
+        let printer = printer();
+        let get_printer_attributes = |charset| {
+            let request = OperationBuilder::request(OperationID::GetPrinterAttributes)
+                .charset(charset)
+                .natural_language("en-US")
+                .operation_attribute(Attribute::uri(OperationAttribute::PrinterUri, PRINTER_URI));
+            send(&printer, request)
+        };
+
+        let response = get_printer_attributes("iso-8859-1");
+        assert_eq!(
+            response.status_code(),
+            Some(IppStatusCode::ClientErrorCharsetNotSupported)
+        );
+        // answered in the charset the printer supports
+        assert_eq!(response.attributes_charset(), Some("utf-8"));
+
+        // charset names are case-insensitive
+        assert_eq!(
+            get_printer_attributes("UTF-8").status_code(),
+            Some(IppStatusCode::SuccessfulOk)
+        );
+    }
+
+    #[test]
+    fn printer_description_is_configurable() {
+        let printer = printer()
+            .with_make_and_model("Acme LaserWriter 9000")
+            .with_location("Lab 2")
+            .with_info("Second floor laser")
+            .with_admin_uri("http://localhost:6363/");
+        let description = |name| {
+            get_printer_attributes(&printer, &[name])
+                .attributes
+                .values()
+                .next()
+                .and_then(Attribute::first_str)
+                .map(String::from)
+        };
+
+        assert_eq!(
+            description("printer-make-and-model").as_deref(),
+            Some("Acme LaserWriter 9000")
+        );
+        assert_eq!(description("printer-location").as_deref(), Some("Lab 2"));
+        assert_eq!(
+            description("printer-info").as_deref(),
+            Some("Second floor laser")
+        );
+        assert_eq!(
+            description("printer-more-info").as_deref(),
+            Some("http://localhost:6363/")
+        );
+
+        // 'printer-info' defaults to the printer name
+        let attributes = get_printer_attributes(&self::printer(), &["printer-info"]);
+        assert_eq!(
+            attributes
+                .get_printer(PrinterAttribute::PrinterInfo)
+                .and_then(Attribute::first_str),
+            Some("Test Printer")
+        );
+    }
+
+    #[test]
+    fn color_speed_and_media_are_reported() {
+        let attributes = get_printer_attributes(
+            &printer(),
+            &[
+                "color-supported",
+                "pages-per-minute",
+                "pages-per-minute-color",
+                "media-supported",
+            ],
+        );
+        assert_eq!(attributes.attributes.len(), 4);
+
+        assert_eq!(
+            attributes
+                .get_printer(PrinterAttribute::ColorSupported)
+                .and_then(Attribute::first_bool),
+            Some(true)
+        );
+        assert_eq!(
+            attributes
+                .get_printer(PrinterAttribute::PagesPerMinute)
+                .and_then(Attribute::first_i32),
+            Some(PAGES_PER_MINUTE)
+        );
+        assert_eq!(
+            attributes
+                .get_printer(PrinterAttribute::PagesPerMinuteColor)
+                .and_then(Attribute::first_i32),
+            Some(PAGES_PER_MINUTE_COLOR)
+        );
+        let media = attributes
+            .get_printer(PrinterAttribute::MediaSupported)
+            .unwrap();
+        assert_eq!(media.tag, ValueTag::Keyword);
+        assert!(media
+            .as_strings()
+            .any(|media| media == MediaKeyword::IsoA4.to_string()));
     }
 }
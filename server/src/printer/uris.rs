@@ -0,0 +1,194 @@
+use ipp_encoder::{
+    encoder::Attribute,
+    spec::{
+        attribute::PrinterAttribute,
+        value::{UriAuthenticationSupportedKeyword, UriSecuritySupportedKeyword},
+    },
+};
+
+#[derive(Debug)]
+pub enum PrinterUriError {
+    /// only 'ipp' and 'ipps' schemes are supported
+    UnsupportedScheme(String),
+    /// 'ipps' requires 'tls' security and 'ipp' requires 'none'
+    SecurityMismatch(String),
+    /// the same uri can only be listed once
+    Duplicate(String),
+}
+
+impl std::fmt::Display for PrinterUriError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedScheme(uri) => write!(f, "unsupported uri scheme: {}", uri),
+            Self::SecurityMismatch(uri) => write!(f, "uri security mismatch: {}", uri),
+            Self::Duplicate(uri) => write!(f, "duplicate uri: {}", uri),
+        }
+    }
+}
+
+struct PrinterUri {
+    uri: String,
+    security: UriSecuritySupportedKeyword,
+    authentication: UriAuthenticationSupportedKeyword,
+}
+
+/// Single source of truth for the (uri, security, authentication) triples
+///
+/// 'printer-uri-supported', 'uri-security-supported' and 'uri-authentication-supported'
+/// are parallel 1setOf values and must list their values in matching order
+///
+/// ref: [rfc8011](https://datatracker.ietf.org/doc/html/rfc8011#section-5.4.1)
+pub struct PrinterUris {
+    uris: Vec<PrinterUri>,
+}
+
+impl PrinterUris {
+    pub fn new(
+        uri: &str,
+        security: UriSecuritySupportedKeyword,
+        authentication: UriAuthenticationSupportedKeyword,
+    ) -> Result<Self, PrinterUriError> {
+        let mut uris = Self { uris: Vec::new() };
+        uris.push(uri, security, authentication)?;
+        Ok(uris)
+    }
+
+    pub fn push(
+        &mut self,
+        uri: &str,
+        security: UriSecuritySupportedKeyword,
+        authentication: UriAuthenticationSupportedKeyword,
+    ) -> Result<(), PrinterUriError> {
+        let expected_security = if uri.starts_with("ipps://") {
+            UriSecuritySupportedKeyword::TLS
+        } else if uri.starts_with("ipp://") {
+            UriSecuritySupportedKeyword::None
+        } else {
+            return Err(PrinterUriError::UnsupportedScheme(String::from(uri)));
+        };
+
+        if security != expected_security {
+            return Err(PrinterUriError::SecurityMismatch(String::from(uri)));
+        }
+
+        if self.uris.iter().any(|printer_uri| printer_uri.uri == uri) {
+            return Err(PrinterUriError::Duplicate(String::from(uri)));
+        }
+
+        self.uris.push(PrinterUri {
+            uri: String::from(uri),
+            security,
+            authentication,
+        });
+
+        Ok(())
+    }
+
+    /// the first uri, used as 'printer-uri' in responses and to derive job uris
+    pub fn primary(&self) -> &str {
+        &self.uris[0].uri
+    }
+
+    pub fn printer_uri_supported(&self) -> Attribute {
+        Attribute::uri_set(
+            PrinterAttribute::PrinterUriSupported,
+            self.uris.iter().map(|printer_uri| printer_uri.uri.as_str()),
+        )
+    }
+
+    pub fn uri_security_supported(&self) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::UriSecuritySupported,
+            self.uris
+                .iter()
+                .map(|printer_uri| printer_uri.security.to_string()),
+        )
+    }
+
+    pub fn uri_authentication_supported(&self) -> Attribute {
+        Attribute::keyword_set(
+            PrinterAttribute::UriAuthenticationSupported,
+            self.uris
+                .iter()
+                .map(|printer_uri| printer_uri.authentication.to_string()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// an ipp uri without authentication and an ipps one with basic authentication
+    fn ipp_and_ipps() -> PrinterUris {
+        let mut uris = PrinterUris::new(
+            "ipp://localhost:631/ipp/print",
+            UriSecuritySupportedKeyword::None,
+            UriAuthenticationSupportedKeyword::None,
+        )
+        .unwrap();
+        uris.push(
+            "ipps://localhost:631/ipp/print",
+            UriSecuritySupportedKeyword::TLS,
+            UriAuthenticationSupportedKeyword::Basic,
+        )
+        .unwrap();
+        uris
+    }
+
+    fn values(attribute: Attribute) -> Vec<String> {
+        attribute.as_strings().map(String::from).collect()
+    }
+
+    #[test]
+    fn attributes_list_the_uris_in_the_same_order() {
+        let uris = ipp_and_ipps();
+
+        assert_eq!(uris.primary(), "ipp://localhost:631/ipp/print");
+        assert_eq!(
+            values(uris.printer_uri_supported()),
+            [
+                "ipp://localhost:631/ipp/print",
+                "ipps://localhost:631/ipp/print"
+            ]
+        );
+        assert_eq!(values(uris.uri_security_supported()), ["none", "tls"]);
+        assert_eq!(
+            values(uris.uri_authentication_supported()),
+            ["none", "basic"]
+        );
+    }
+
+    #[test]
+    fn push_rejects_inconsistent_uris() {
+        let mut uris = ipp_and_ipps();
+
+        assert!(matches!(
+            uris.push(
+                "http://localhost:631/ipp/print",
+                UriSecuritySupportedKeyword::None,
+                UriAuthenticationSupportedKeyword::None,
+            ),
+            Err(PrinterUriError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            uris.push(
+                "ipps://localhost:8631/ipp/print",
+                UriSecuritySupportedKeyword::None,
+                UriAuthenticationSupportedKeyword::Basic,
+            ),
+            Err(PrinterUriError::SecurityMismatch(_))
+        ));
+        assert!(matches!(
+            uris.push(
+                "ipps://localhost:631/ipp/print",
+                UriSecuritySupportedKeyword::TLS,
+                UriAuthenticationSupportedKeyword::Digest,
+            ),
+            Err(PrinterUriError::Duplicate(_))
+        ));
+
+        // nothing was added by the failed pushes
+        assert_eq!(values(uris.printer_uri_supported()).len(), 2);
+    }
+}